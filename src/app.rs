@@ -1,61 +1,296 @@
 // app.rs
-use crate::todo::Todo;
+use crate::todo::{SyncBase, Todo, new_id};
 use crate::tui::parse_due_date;
 use chrono::Local;
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
-use std::fs::{File, OpenOptions};
+use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::path::{Path, PathBuf};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver};
 use std::thread;
 use std::time::Duration;
-use crate::planka::{self, PlankaBoard, PlankaClient, PlankaConfig, PlankaLists, PlankaCard, PlankaCardDetails, PlankaComment};
+use crate::oidc;
+use crate::profile;
+use crate::realtime;
+use crate::secrets;
+use crate::webhook;
+use crate::worker::Worker;
+use crate::planka::{self, PlankaBoard, PlankaClient, PlankaConfig, WebhookConfig, PlankaError, PlankaLabel, PlankaLists, PlankaUser, PlankaListDetails, WorkflowStage, PlankaCard, PlankaCardDetails, PlankaComment, PlankaNotification, PlankaAction, PlankaOidcConfig, PlankaTerms, PlankaBoardMembership};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum PlankaSetupStep {
     Url,
     Username,
     Password,
+    Sso, // waiting for the browser to come back from the identity provider
 }
 #[derive(Serialize, Deserialize, Clone)]
-pub enum PendingOpKind { Create, Move, Update, Delete }
+pub enum PendingOpKind { Create, Move, Update, Delete, AddLabel, RemoveLabel, AddMember, RemoveMember }
+
+impl PendingOpKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PendingOpKind::Create => "create",
+            PendingOpKind::Move => "move",
+            PendingOpKind::Update => "update",
+            PendingOpKind::Delete => "delete",
+            PendingOpKind::AddLabel => "label",
+            PendingOpKind::RemoveLabel => "unlabel",
+            PendingOpKind::AddMember => "assign",
+            PendingOpKind::RemoveMember => "unassign",
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct PendingOp {
+    #[serde(default = "new_id")]
+    pub id: String,
     pub kind: PendingOpKind,
     pub project: String,
+    // Todo::local_id this op belongs to (Create ops target it until a card id exists)
+    #[serde(default)]
+    pub local_id: Option<String>,
     pub card_id: Option<String>,
     pub list_id: Option<String>,
     pub name: Option<String>,
     pub due: Option<String>,
+    // Label added or removed by AddLabel/RemoveLabel
+    #[serde(default)]
+    pub label_id: Option<String>,
+    // User assigned or unassigned by AddMember/RemoveMember
+    #[serde(default)]
+    pub user_id: Option<String>,
     pub ts: i64,
+    #[serde(default)]
+    pub retry: RetryState,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct RetryState {
+    pub attempts: u32,
+    pub next_at: i64,
+    pub last_error: Option<String>,
+}
+
+// Failed ops back off 5s, 10s, 20s... capped at an hour; after this many they are dead-lettered
+const MAX_OP_ATTEMPTS: u32 = 10;
+const OP_BACKOFF_BASE_SECS: i64 = 5;
+const OP_BACKOFF_MAX_SECS: i64 = 3600;
+
+// The card (or not yet created todo) an op is about
+fn op_keys(op: &PendingOp) -> impl Iterator<Item = &str> {
+    [op.card_id.as_deref(), op.local_id.as_deref()].into_iter().flatten()
+}
+
+fn same_card(a: &PendingOp, b: &PendingOp) -> bool {
+    op_keys(a).any(|k| op_keys(b).any(|l| k == l))
+}
+
+// Ops to send now. Ops on the same card go out one at a time in queue order,
+// so a retried op can't land after (and undo) a newer one: everything behind
+// an unfinished op for its card waits until it succeeds or is dead-lettered.
+fn sendable_ops<'a>(ops: &'a [PendingOp], in_flight: &HashSet<String>, now: i64) -> Vec<&'a PendingOp> {
+    let mut busy: HashSet<&str> = ops.iter().filter(|op| in_flight.contains(&op.id)).flat_map(op_keys).collect();
+    let mut out = Vec::new();
+    for op in ops {
+        let blocked = op_keys(op).any(|k| busy.contains(k));
+        busy.extend(op_keys(op));
+        if !blocked && op.retry.next_at <= now && !in_flight.contains(&op.id) {
+            out.push(op);
+        }
+    }
+    out
+}
+
+// Where a revived dead op goes back into the queue: ahead of the newer ops for its card
+fn requeue_position(ops: &[PendingOp], op: &PendingOp) -> usize {
+    ops.iter().position(|p| same_card(p, op)).unwrap_or(ops.len())
+}
+
+impl RetryState {
+    // Count a failed attempt and schedule the next one; true once the op should be dead-lettered
+    fn fail(&mut self, error: String, now: i64) -> bool {
+        self.attempts += 1;
+        self.last_error = Some(error);
+        let backoff = OP_BACKOFF_BASE_SECS
+            .saturating_mul(1i64 << (self.attempts - 1).min(20))
+            .min(OP_BACKOFF_MAX_SECS);
+        self.next_at = now + backoff;
+        self.attempts >= MAX_OP_ATTEMPTS
+    }
 }
 
 #[derive(Clone, Debug)]
 pub enum Delta {
-    Upsert { project: String, id: String, name: String, due: Option<String>, created: Option<String>, done: bool, list_id: String },
-    // Delete could be added later when we compute removals in the poller
+    // Boards seen by a pull, so projects can be keyed and labelled by board id
+    Boards(Vec<PlankaBoard>),
+    // `labels` and `members` are None when the source doesn't carry them (socket card events)
+    Upsert { project: String, id: String, name: String, due: Option<String>, created: Option<String>, done: bool, list_id: String, labels: Option<Vec<String>>, members: Option<Vec<String>> },
+    // Card no longer exists on Planka
+    Delete { project: String, id: String },
+    // Card still exists but left the todo/doing/done lists (archived or moved away)
+    Archived { project: String, id: String },
+    // Every label of a board
+    Labels { project: String, labels: Vec<PlankaLabel> },
+    // One label created, renamed or recolored, or deleted from the board
+    Label { project: String, label: PlankaLabel, deleted: bool },
+    // A label was put on or taken off a card
+    CardLabel { card_id: String, label_id: String, added: bool },
+    // Users who are members of a board
+    Members { project: String, members: Vec<PlankaUser> },
+    // A user was assigned to a card or unassigned from it
+    CardMember { card_id: String, user_id: String, added: bool },
+    // The unread inbox as a whole (polls), or one new or updated notification (socket)
+    Notifications(Vec<PlankaNotification>),
+    Notification(PlankaNotification),
+    // Problem in a background thread worth showing in the title
+    Message(String),
+}
+// A login the server holds back until its terms are accepted
+pub struct PendingTerms {
+    cfg: PlankaConfig,
+    pending_token: String,
+    pub terms: Option<PlankaTerms>, // None while loading
+    pub scroll: u16,
+}
+
+// Admin change to another account, held until it is confirmed with y
+#[derive(Clone, Debug)]
+pub enum UserChange {
+    Role(String),
+    Deactivate(bool),
+    Delete,
+}
+
+// Fields of the new-user form, asked one after another
+const NEW_USER_FIELDS: [&str; 4] = ["Email", "Password", "Full name", "Username (optional)"];
+
+// Settings of the own account; each is changed through a short form
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountField { Password, Email, Username, Avatar }
+
+impl AccountField {
+    pub const ALL: [AccountField; 4] = [AccountField::Password, AccountField::Email, AccountField::Username, AccountField::Avatar];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AccountField::Password => "Password",
+            AccountField::Email => "Email",
+            AccountField::Username => "Username",
+            AccountField::Avatar => "Avatar",
+        }
+    }
+
+    // Questions of its form, in order; Planka wants the current password for all but the avatar
+    pub fn prompts(self) -> &'static [&'static str] {
+        match self {
+            AccountField::Password => &["New password", "Repeat the new password", "Current password"],
+            AccountField::Email => &["New email", "Current password"],
+            AccountField::Username => &["New username (empty removes it)", "Current password"],
+            AccountField::Avatar => &["Image file"],
+        }
+    }
+}
+
+// Typed answers to these are masked and taken as typed
+pub fn is_secret_prompt(prompt: &str) -> bool {
+    prompt.to_lowercase().contains("password")
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictField { Name, Due, List }
+
+// A field changed on both sides since the last sync
+#[derive(Clone, Debug)]
+pub struct Conflict {
+    pub project: String,
+    pub card_id: String,
+    pub field: ConflictField,
+    pub remote: SyncBase,
+}
+
+// Remote state fetched by a sync on the worker, merged on the UI thread
+struct RemotePull {
+    boards: Vec<PlankaBoard>,
+    pulled: Vec<BoardPull>,
+    errors: Vec<String>,
+}
+
+struct BoardPull {
+    project: String,
+    lists: PlankaLists,
+    cards: Vec<(PlankaCard, bool, String)>, // (card, done, list_id)
+    gone: Vec<(String, bool)>,              // (card_id, still exists but left the lists)
+    labels: Option<Vec<PlankaLabel>>,
+    members: Option<Vec<PlankaUser>>,
+}
+
+enum Merge<T> { Keep, Take(T), Conflict }
+
+fn merge3<T: PartialEq + Clone>(base: &T, local: &T, remote: &T) -> Merge<T> {
+    if local == remote || remote == base {
+        Merge::Keep
+    } else if local == base {
+        Merge::Take(remote.clone())
+    } else {
+        Merge::Conflict
+    }
+}
+
+// Done wins over the list id so "done offline" and "moved to Done" compare equal
+fn list_key(list_id: &Option<String>, done: bool) -> Option<String> {
+    if done { Some("done".to_string()) } else { list_id.clone() }
+}
+
+// Add or remove an id in a label or member set
+fn toggle_id(ids: &mut Vec<String>, id: &str, on: bool) {
+    if on {
+        if !ids.iter().any(|l| l == id) {
+            ids.push(id.to_string());
+        }
+    } else {
+        ids.retain(|l| l != id);
+    }
+}
+
+// Set-wise three-way merge: ids added or removed on either side since the
+// base are all kept. Local additions are dropped when `exists` says the id
+// is gone from the board (a deleted label, a user who left).
+fn merge_set(base: &[String], local: &[String], remote: &[String], exists: impl Fn(&str) -> bool) -> Vec<String> {
+    let mut merged: Vec<String> = remote
+        .iter()
+        .filter(|l| !base.contains(l) || local.contains(l))
+        .cloned()
+        .collect();
+    for l in local {
+        if !base.contains(l) && !merged.contains(l) && exists(l) {
+            merged.push(l.clone());
+        }
+    }
+    merged
 }
+
 fn default_projects() -> Vec<String> { vec!["Inbox".to_string()] }
 fn default_current_project() -> String { "Inbox".to_string() }
 
 pub fn get_data_file_path() -> PathBuf {
-    let base = std::env::var_os("XDG_CONFIG_HOME")
-        .map(PathBuf::from)
-        .unwrap_or_else(|| {
-            std::env::var_os("HOME")
-                .map(PathBuf::from)
-                .map(|p| p.join(".config"))
-                .unwrap_or_else(|| PathBuf::from("."))
-        });
-    let dir = base.join("Planky");
-    std::fs::create_dir_all(&dir).ok();
-    dir.join("todos.json")
+    profile::path("todos.json")
+}
+
+// Text handed to $EDITOR by the TUI loop, which owns the terminal
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditorTarget {
+    Description,
+    // The comment being written or edited (input_comment)
+    Comment,
 }
 
-#[derive(PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, PartialEq, Deserialize, Serialize)]
 pub enum InputMode {
     Normal,
     EditingDescription,
@@ -79,6 +314,26 @@ pub enum InputMode {
     CreatingCustomField,
     EditingCustomFieldValue,
     RenamingCustomFieldGroup,
+    ResolvingConflicts,
+    EditingConflictValue,
+    ViewingPendingOps,
+    SwitchingProfile,
+    CreatingProfile,
+    EditingWorkflow,
+    PickingLabel,   // toggle labels of the open card
+    CreatingLabel,
+    FilteringLabel, // pick the label the task list is filtered by
+    PickingMember,  // assign users to the open card
+    ViewingMyCards, // cards assigned to the logged-in user, across boards
+    ViewingNotifications,
+    ViewingBoardActivity,
+    AcceptingTerms, // login paused until the server's terms are accepted
+    ManagingUsers,  // admin: every user of the instance
+    CreatingUser,
+    EditingAccount, // the logged-in user's own password, email, username, avatar
+    ChangingAccount,
+    ManagingBoardMembers, // memberships of the current board
+    InvitingBoardMember,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -89,6 +344,9 @@ pub struct App {
     pub projects: Vec<String>,
     #[serde(default = "default_current_project")]
     pub current_project: String,
+    // Projects are keyed by Planka board id (local-only ones by name); this keeps the board names for display
+    #[serde(default)]
+    pub project_names: HashMap<String, String>,
     #[serde(skip)]
     pub input_project: String,
     #[serde(skip)]
@@ -123,11 +381,26 @@ pub struct App {
     pub input_planka: String,
     #[serde(skip)]
     pub planka_setup: Option<PlankaSetupStep>,
+    // SSO offered by the server being set up, and a sign-in in progress
+    #[serde(skip)]
+    pub oidc: Option<PlankaOidcConfig>,
+    #[serde(skip)]
+    sso: Option<oidc::SsoLogin>,
+    #[serde(skip)]
+    pub terms: Option<PendingTerms>,
     #[serde(skip)]
     pub pending_ops: Vec<PendingOp>,
     #[serde(skip)]
+    pub dead_ops: Vec<PendingOp>, // gave up after MAX_OP_ATTEMPTS; kept for inspection
+    #[serde(skip)]
+    pub ops_index: usize,
+    #[serde(skip)]
     pub inbound_rx: Option<Receiver<Delta>>,
     #[serde(skip)]
+    pub worker: Worker,
+    #[serde(skip)]
+    pub ops_in_flight: HashSet<String>, // PendingOp ids currently running on the worker
+    #[serde(skip)]
     pub control_center_index: usize,
     #[serde(skip)]
     pub editing_index: Option<usize>,
@@ -159,6 +432,125 @@ pub struct App {
     pub input_custom_field_name: String,
     #[serde(skip)]
     pub input_custom_field_value: String,
+    #[serde(skip)]
+    pub conflicts: Vec<Conflict>,
+    #[serde(skip)]
+    pub conflict_index: usize,
+    #[serde(skip)]
+    pub input_conflict: String,
+    #[serde(skip)]
+    pub sync_stop: Arc<AtomicBool>, // raised to end the background sync thread of the previous profile
+    #[serde(skip)]
+    pub profiles: Vec<(String, String)>, // (name, "user @ server") for the profile switcher
+    #[serde(skip)]
+    pub profile_index: usize,
+    #[serde(skip)]
+    pub input_profile: String,
+    #[serde(skip)]
+    pub workflow_rows: Vec<(WorkflowStage, bool)>, // board lists, stages first in order; bool = part of the workflow
+    #[serde(skip)]
+    pub workflow_index: usize,
+    // Layout choice: the board's lists as columns instead of the task list
+    #[serde(default)]
+    pub board_view: bool,
+    #[serde(skip)]
+    pub kanban: Vec<PlankaListDetails>,
+    #[serde(skip)]
+    pub kanban_col: usize,
+    #[serde(skip)]
+    pub kanban_row: usize,
+    #[serde(skip)]
+    pub kanban_loading: bool,
+    #[serde(skip)]
+    pub editor_request: Option<EditorTarget>,
+    // Labels per project key, kept so chips still render offline
+    #[serde(default)]
+    pub board_labels: HashMap<String, Vec<PlankaLabel>>,
+    // Task list shows only todos carrying this label id
+    #[serde(skip)]
+    pub label_filter: Option<String>,
+    #[serde(skip)]
+    pub label_index: usize,
+    #[serde(skip)]
+    pub input_label: String,
+    #[serde(skip)]
+    pub input_label_color: usize, // index into planka::LABEL_COLORS
+    // Board members per project key, for initials and the member picker
+    #[serde(default)]
+    pub board_members: HashMap<String, Vec<PlankaUser>>,
+    #[serde(skip)]
+    pub member_index: usize,
+    // Planka id of the logged-in user, looked up on first use
+    #[serde(skip)]
+    pub my_user_id: Option<String>,
+    #[serde(skip)]
+    pub my_cards: Vec<(String, String, PlankaCard)>, // (board id, list name, card)
+    #[serde(skip)]
+    pub my_cards_index: usize,
+    #[serde(skip)]
+    pub my_cards_loading: bool,
+    // Screen the open card was reached from; closing it goes back there
+    #[serde(skip)]
+    pub view_return: InputMode,
+    // Planka inbox of the logged-in user, newest first
+    #[serde(skip)]
+    pub notifications: Vec<PlankaNotification>,
+    #[serde(skip)]
+    pub notification_index: usize,
+    // Activity of the open card and of the current board, newest first; older
+    // pages are loaded as the list is scrolled to its end
+    #[serde(skip)]
+    pub card_actions: Vec<PlankaAction>,
+    #[serde(skip)]
+    pub card_actions_end: bool,
+    #[serde(skip)]
+    pub board_actions: Vec<PlankaAction>,
+    #[serde(skip)]
+    pub board_actions_end: bool,
+    #[serde(skip)]
+    pub board_action_index: usize,
+    // Board and card whose activity page is being fetched; kept apart so
+    // opening a card from the board activity isn't held up by a board page
+    #[serde(skip)]
+    pub board_actions_loading: Option<String>,
+    #[serde(skip)]
+    pub card_actions_loading: Option<String>,
+    // Display names by user id, for activity entries
+    #[serde(skip)]
+    pub user_names: HashMap<String, String>,
+    // Users screen (admins): all accounts, a change awaiting confirmation
+    // (user id, change), and the answers of the new-user form so far
+    #[serde(skip)]
+    pub users: Vec<PlankaUser>,
+    #[serde(skip)]
+    pub user_index: usize,
+    #[serde(skip)]
+    pub user_confirm: Option<(String, UserChange)>,
+    #[serde(skip)]
+    pub user_form: Vec<String>,
+    #[serde(skip)]
+    pub input_user: String,
+    // Account screen: the logged-in user, the selected setting and its form answers so far
+    #[serde(skip)]
+    pub account: Option<PlankaUser>,
+    #[serde(skip)]
+    pub account_index: usize,
+    #[serde(skip)]
+    pub account_form: Vec<String>,
+    #[serde(skip)]
+    pub input_account: String,
+    // Board members screen: memberships of the current board with their users,
+    // a removal awaiting y (membership id), and the users that can be invited
+    #[serde(skip)]
+    pub memberships: Vec<(PlankaBoardMembership, PlankaUser)>,
+    #[serde(skip)]
+    pub membership_index: usize,
+    #[serde(skip)]
+    pub membership_confirm: Option<String>,
+    #[serde(skip)]
+    pub invitable: Vec<PlankaUser>,
+    #[serde(skip)]
+    pub invite_index: usize,
 }
 
 impl Default for InputMode {
@@ -173,7 +565,7 @@ impl Default for App {
     }
 }
 
-fn format_planka_due(s: &str) -> Option<String> {
+pub fn format_planka_due(s: &str) -> Option<String> {
     // Try RFC3339 first, else return None to leave empty
     if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
         Some(dt.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
@@ -197,30 +589,47 @@ fn format_planka_created(s: &str) -> String {
 
 impl App {
     fn selected_index_in_all(&self) -> Option<usize> {
-        // Apply same project and search filtering as the TUI
+        // On the board the cursor picks a card; only cards tracked as todos can be changed this way
+        if self.board_view {
+            let cid = self.kanban_card()?.id.as_str();
+            return self.todos.iter().position(|t| t.planka_card_id.as_deref() == Some(cid) && !t.archived);
+        }
+        self.visible_indices().get(self.selected).copied()
+    }
+    // Workflow stage of a todo and the number of stages; projects without
+    // resolved lists only know Todo and Done
+    pub fn stage_of(&self, t: &Todo) -> (usize, usize) {
+        match self.planka_lists_by_board.get(&t.project) {
+            Some(l) => (l.stage_index(t.planka_list_id.as_deref(), t.done), l.stages.len()),
+            None => (usize::from(t.done), 2),
+        }
+    }
+
+    pub fn stage_name(&self, t: &Todo) -> String {
+        match self.planka_lists_by_board.get(&t.project) {
+            Some(l) => l.stages[l.stage_index(t.planka_list_id.as_deref(), t.done)].name.clone(),
+            None if t.done => "Done".to_string(),
+            None => "Todo".to_string(),
+        }
+    }
+
+    // Indices into `todos` in display order (same project and search filtering as the TUI)
+    pub fn visible_indices(&self) -> Vec<usize> {
         let q = if self.search_query.is_empty() {
             None
         } else {
             Some(self.search_query.to_lowercase())
         };
 
-        // Detect Doing/Done list ids for current project
-        let doing_id = self
-            .planka_lists_by_board
-            .get(&self.current_project)
-            .map(|l| l.doing_list_id.as_str());
-        let done_id = self
-            .planka_lists_by_board
-            .get(&self.current_project)
-            .map(|l| l.done_list_id.as_str());
-
-        // Build grouped indices: Doing first, then Todo, then Done
-        let mut doing: Vec<usize> = Vec::new();
-        let mut todo: Vec<usize> = Vec::new();
-        let mut done: Vec<usize> = Vec::new();
-
+        // Group by workflow stage: in-progress stages first (in workflow order), then new, then done
+        let mut keyed: Vec<((u8, usize), usize)> = Vec::new();
         for (i, t) in self.todos.iter().enumerate() {
-            if t.project != self.current_project {
+            if t.project != self.current_project || t.archived {
+                continue;
+            }
+            if let Some(ref label) = self.label_filter
+                && !t.labels.contains(label)
+            {
                 continue;
             }
             if let Some(ref ql) = q {
@@ -234,28 +643,19 @@ impl App {
                     continue;
                 }
             }
-            let in_doing = doing_id
-                .map(|id| t.planka_list_id.as_deref() == Some(id))
-                .unwrap_or(false);
-            let in_done = done_id
-                .map(|id| t.planka_list_id.as_deref() == Some(id))
-                .unwrap_or(false);
-
-            if !t.done && in_doing {
-                doing.push(i);
-            } else if t.done || in_done {
-                done.push(i);
+            let (stage, count) = self.stage_of(t);
+            let group = if stage + 1 == count {
+                2
+            } else if stage == 0 {
+                1
             } else {
-                todo.push(i);
-            }
+                0
+            };
+            keyed.push(((group, stage), i));
         }
-
-        let mut ordered: Vec<usize> = Vec::with_capacity(doing.len() + todo.len() + done.len());
-        ordered.extend(doing);
-        ordered.extend(todo);
-        ordered.extend(done);
-
-        ordered.get(self.selected).copied()
+        keyed.sort_by_key(|(k, _)| *k);
+        let ordered: Vec<usize> = keyed.into_iter().map(|(_, i)| i).collect();
+        ordered
     }
     fn selected_group_id(&self) -> Option<String> {
         let vc = self.view_card.as_ref()?;
@@ -278,6 +678,7 @@ impl App {
             search_query: String::new(), // Initialize search_query
             projects: default_projects(),
             current_project: default_current_project(),
+            project_names: HashMap::new(),
             input_project: String::new(),
             input_board: String::new(),
             create_board_projects: Vec::new(),
@@ -288,8 +689,15 @@ impl App {
             planka_boards: Vec::new(),
             input_planka: String::new(),
             planka_setup: None,
-            pending_ops: Self::load_pending_ops(),
+            oidc: None,
+            sso: None,
+            terms: None,
+            pending_ops: Self::load_ops(Self::pending_ops_path()),
+            dead_ops: Self::load_ops(Self::dead_ops_path()),
+            ops_index: 0,
             inbound_rx: None,
+            worker: Worker::default(),
+            ops_in_flight: HashSet::new(),
             control_center_index: 0,
             editing_index: None,
             view_card: None,
@@ -306,32 +714,77 @@ impl App {
             input_cfg_group_name: String::new(),
             input_custom_field_name: String::new(),
             input_custom_field_value: String::new(),
+            conflicts: Vec::new(),
+            conflict_index: 0,
+            input_conflict: String::new(),
+            sync_stop: Arc::new(AtomicBool::new(false)),
+            profiles: Vec::new(),
+            profile_index: 0,
+            input_profile: String::new(),
+            workflow_rows: Vec::new(),
+            workflow_index: 0,
+            board_view: false,
+            kanban: Vec::new(),
+            kanban_col: 0,
+            kanban_row: 0,
+            kanban_loading: false,
+            editor_request: None,
+            board_labels: HashMap::new(),
+            label_filter: None,
+            label_index: 0,
+            input_label: String::new(),
+            input_label_color: 0,
+            board_members: HashMap::new(),
+            member_index: 0,
+            my_user_id: None,
+            my_cards: Vec::new(),
+            my_cards_index: 0,
+            my_cards_loading: false,
+            view_return: InputMode::Normal,
+            notifications: Vec::new(),
+            notification_index: 0,
+            card_actions: Vec::new(),
+            card_actions_end: false,
+            board_actions: Vec::new(),
+            board_actions_end: false,
+            board_action_index: 0,
+            board_actions_loading: None,
+            card_actions_loading: None,
+            user_names: HashMap::new(),
+            users: Vec::new(),
+            user_index: 0,
+            user_confirm: None,
+            user_form: Vec::new(),
+            input_user: String::new(),
+            account: None,
+            account_index: 0,
+            account_form: Vec::new(),
+            input_account: String::new(),
+            memberships: Vec::new(),
+            membership_index: 0,
+            membership_confirm: None,
+            invitable: Vec::new(),
+            invite_index: 0,
         }
     }
 
     fn pending_ops_path() -> PathBuf {
-        let base = std::env::var_os("XDG_CONFIG_HOME")
-            .map(PathBuf::from)
-            .unwrap_or_else(|| {
-                std::env::var_os("HOME")
-                    .map(PathBuf::from)
-                    .map(|p| p.join(".config"))
-                    .unwrap_or_else(|| PathBuf::from("."))
-            });
-        let dir = base.join("Planky");
-        std::fs::create_dir_all(&dir).ok();
-        dir.join("pending_ops.json")
+        profile::path("pending_ops.json")
+    }
+
+    fn dead_ops_path() -> PathBuf {
+        Self::pending_ops_path().with_file_name("dead_ops.json")
     }
 
     fn save_pending_ops(&self) {
-        let path = Self::pending_ops_path();
-        if let Ok(file) = OpenOptions::new().create(true).write(true).truncate(true).open(path) {
-            let _ = serde_json::to_writer(BufWriter::new(file), &self.pending_ops);
+        for (path, ops) in [(Self::pending_ops_path(), &self.pending_ops), (Self::dead_ops_path(), &self.dead_ops)] {
+            if let Ok(file) = secrets::open_private(&path, false) {
+                let _ = serde_json::to_writer(BufWriter::new(file), ops);
+            }
         }
     }
 
-    fn load_pending_ops() -> Vec<PendingOp> {
-        let path = Self::pending_ops_path();
+    fn load_ops(path: PathBuf) -> Vec<PendingOp> {
         if let Ok(file) = File::open(path) {
             let reader = BufReader::new(file);
             serde_json::from_reader(reader).unwrap_or_default()
@@ -340,9 +793,11 @@ impl App {
         }
     }
 
+    // Queue an outbound change and start it on the worker right away
     fn enqueue_op(&mut self, op: PendingOp) {
         self.pending_ops.push(op);
         self.save_pending_ops();
+        self.process_pending_ops_tick();
     }
 
     pub fn pending_ops_len(&self) -> usize {
@@ -352,80 +807,154 @@ impl App {
     pub fn start_background_sync(&mut self) {
         let (tx, rx) = mpsc::channel::<Delta>();
         self.inbound_rx = Some(rx);
+        self.sync_stop.store(true, Ordering::Relaxed);
+        self.sync_stop = Arc::new(AtomicBool::new(false));
+        let stop = self.sync_stop.clone();
+        // Card ids last seen per board, seeded from local todos so stale ones are caught on the first pass
+        let mut known: HashMap<String, HashSet<String>> = HashMap::new();
+        for t in &self.todos {
+            if let Some(ref cid) = t.planka_card_id {
+                known.entry(t.project.clone()).or_default().insert(cid.clone());
+            }
+        }
+        let shared = Arc::new(webhook::Shared::default());
+        if let Some(hook) = self.planka_config.as_ref().and_then(|c| c.webhook.clone()).filter(|h| h.id.is_some()) {
+            webhook::spawn(hook, shared.clone(), tx.clone(), stop.clone());
+        }
         thread::spawn(move || {
-            loop {
+            while !stop.load(Ordering::Relaxed) {
                 // Load cfg fresh each tick to allow login during runtime
                 let cfg = planka::load_config();
                 if let Some(cfg) = cfg {
                     if let Ok((client, _)) = PlankaClient::from_config(cfg) {
-                        if let Ok(boards) = client.fetch_boards() {
-                            for b in boards {
-                                if let Ok(lists) = client.resolve_lists(&b.name) {
-                                    // todo + doing as not-done
-                                    if let Ok(cards) = client.fetch_cards(&lists.todo_list_id) {
-                                        for c in cards {
-                                            let _ = tx.send(Delta::Upsert {
-                                                project: b.name.clone(),
-                                                id: c.id.clone(),
-                                                name: c.name.clone(),
-                                                due: c.due.clone(),
-                                                created: c.created.clone(),
-                                                done: false,
-                                                list_id: lists.todo_list_id.clone(),
-                                            });
-                                        }
-                                    }
-                                    if let Ok(cards) = client.fetch_cards(&lists.doing_list_id) {
-                                        for c in cards {
-                                            let _ = tx.send(Delta::Upsert {
-                                                project: b.name.clone(),
-                                                id: c.id.clone(),
-                                                name: c.name.clone(),
-                                                due: c.due.clone(),
-                                                created: c.created.clone(),
-                                                done: false,
-                                                list_id: lists.doing_list_id.clone(),
-                                            });
-                                        }
-                                    }
-                                    if let Ok(cards) = client.fetch_cards(&lists.done_list_id) {
-                                        for c in cards {
-                                            let _ = tx.send(Delta::Upsert {
-                                                project: b.name.clone(),
-                                                id: c.id.clone(),
-                                                name: c.name.clone(),
-                                                due: c.due.clone(),
-                                                created: c.created.clone(),
-                                                done: true,
-                                                list_id: lists.done_list_id.clone(),
-                                            });
-                                        }
-                                    }
+                        // Full pull first, then stay on the socket until it drops
+                        let boards = Self::poll_boards(&client, &tx, &mut known);
+                        if let Ok(mut b) = shared.boards.lock() {
+                            *b = boards.clone();
+                        }
+                        if realtime::run(&client, &boards, &tx, &stop).is_ok() {
+                            // lists changed remotely: re-resolve and resubscribe now
+                            continue;
+                        }
+                    }
+                }
+                // Socket unavailable or dropped: fall back to polling. With the webhook
+                // receiver up, card changes arrive there and a full pull is rarely needed.
+                let wait = if shared.live.load(Ordering::Relaxed) { 300 } else { 15 };
+                for _ in 0..wait {
+                    if stop.load(Ordering::Relaxed) || shared.resync.swap(false, Ordering::Relaxed) {
+                        break;
+                    }
+                    thread::sleep(Duration::from_secs(1));
+                }
+            }
+        });
+    }
+
+    // Fetch the cards of every board's workflow lists as Upserts; returns the resolved lists
+    fn poll_boards(
+        client: &PlankaClient,
+        tx: &mpsc::Sender<Delta>,
+        known: &mut HashMap<String, HashSet<String>>,
+    ) -> Vec<(String, PlankaLists)> {
+        let mut resolved: Vec<(String, PlankaLists)> = Vec::new();
+        if let Ok(notifications) = client.fetch_notifications() {
+            let _ = tx.send(Delta::Notifications(notifications));
+        }
+        if let Ok(boards) = client.fetch_boards() {
+            let _ = tx.send(Delta::Boards(boards.clone()));
+            for b in boards {
+                if let Ok(details) = client.fetch_board_details(&b.id) {
+                    let _ = tx.send(Delta::Labels { project: b.id.clone(), labels: details.labels });
+                    let _ = tx.send(Delta::Members { project: b.id.clone(), members: details.members });
+                }
+                if let Ok(lists) = client.resolve_lists(&b.id) {
+                    let mut seen: HashSet<String> = HashSet::new();
+                    let mut complete = true;
+                    for stage in &lists.stages {
+                        let Ok(cards) = client.fetch_cards(&stage.list_id) else {
+                            complete = false;
+                            continue;
+                        };
+                        for c in cards {
+                            seen.insert(c.id.clone());
+                            let _ = tx.send(Delta::Upsert {
+                                project: b.id.clone(),
+                                id: c.id.clone(),
+                                name: c.name.clone(),
+                                due: c.due.clone(),
+                                created: c.created.clone(),
+                                done: stage.list_id == lists.done_list_id(),
+                                list_id: stage.list_id.clone(),
+                                labels: Some(c.label_ids.clone()),
+                                members: Some(c.member_ids.clone()),
+                            });
+                        }
+                    }
+                    // Removals: only trust the diff when every list was fetched
+                    if complete {
+                        let prev = known.remove(&b.id).unwrap_or_default();
+                        let gone: Vec<String> = prev.difference(&seen).cloned().collect();
+                        for id in &gone {
+                            match client.card_exists(id) {
+                                Ok(false) => {
+                                    let _ = tx.send(Delta::Delete { project: b.id.clone(), id: id.clone() });
+                                }
+                                Ok(true) => {
+                                    let _ = tx.send(Delta::Archived { project: b.id.clone(), id: id.clone() });
+                                }
+                                // unknown: keep it around and check again next pass
+                                Err(_) => {
+                                    seen.insert(id.clone());
                                 }
                             }
                         }
+                        known.insert(b.id.clone(), seen);
                     }
+                    resolved.push((b.id.clone(), lists));
                 }
-                thread::sleep(Duration::from_secs(15));
             }
-        });
+        }
+        resolved
     }
 
     pub fn apply_delta(&mut self, d: Delta) {
         match d {
-            Delta::Upsert { project, id, name, due, created, done, list_id } => {
-                // Skip overwriting local dirty items
-                if let Some(t) = self.todos.iter_mut().find(|t| t.project == project && t.planka_card_id.as_deref() == Some(id.as_str())) {
-                    if t.sync_dirty { return; }
-                    t.description = name;
-                    t.done = done;
-                    t.due_date = due.as_deref().and_then(|s| format_planka_due(s));
+            Delta::Boards(boards) => self.adopt_boards(&boards),
+            Delta::Upsert { project, id, name, due, created, done, list_id, labels, members } => {
+                // Card ids are unique across boards; a card moved to another board follows along
+                if let Some(idx) = self.todos.iter().position(|t| t.planka_card_id.as_deref() == Some(id.as_str())) {
                     if let Some(c) = created.as_deref() {
-                        t.created_date = format_planka_created(c);
+                        self.todos[idx].created_date = format_planka_created(c);
+                    }
+                    // Polls repeat unchanged cards; only a real change reloads the board view
+                    let before = (self.todos[idx].archived, self.todos[idx].base.clone());
+                    let board = project.clone();
+                    self.todos[idx].archived = false;
+                    self.todos[idx].planka_board_id = Some(project.clone());
+                    self.todos[idx].project = project;
+                    let remote = SyncBase {
+                        name,
+                        due: due.as_deref().and_then(format_planka_due),
+                        list_id: Some(list_id),
+                        done,
+                        labels: Vec::new(),
+                        members: Vec::new(),
+                    };
+                    self.merge_remote(idx, remote);
+                    if let Some(ref labels) = labels {
+                        self.merge_remote_labels(idx, labels);
+                    }
+                    if let Some(ref members) = members {
+                        self.merge_remote_members(idx, members);
+                    }
+                    if before != (self.todos[idx].archived, self.todos[idx].base.clone()) {
+                        self.kanban_stale(&board);
                     }
-                    t.planka_list_id = Some(list_id.clone());
                 } else {
+                    self.kanban_stale(&project);
                     self.todos.push(Todo {
+                        local_id: new_id(),
                         description: name,
                         done,
                         due_date: due.as_deref().and_then(|s| format_planka_due(s)),
@@ -433,421 +962,1387 @@ impl App {
                             .as_deref()
                             .map(|s| format_planka_created(s))
                             .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string()),
-                        project,
+                        project: project.clone(),
                         planka_card_id: Some(id),
                         planka_list_id: Some(list_id),
-                        planka_board_id: None,
+                        planka_board_id: Some(project),
+                        labels: labels.unwrap_or_default(),
+                        members: members.unwrap_or_default(),
+                        archived: false,
+                        base: None,
                         sync_dirty: false,
                     });
+                    if let Some(t) = self.todos.last_mut() {
+                        t.base = Some(t.snapshot());
+                    }
                 }
             }
-        }
-    }
-
-    pub fn process_pending_ops_tick(&mut self) {
-        if self.pending_ops.is_empty() {
-            return;
-        }
-        let client = match self.ensure_planka_client() {
-            Ok(c) => c,
-            Err(_) => return, // keep ops queued
-        };
-        // Work on a copy to allow removal while iterating
-        let ops = self.pending_ops.clone();
-        let mut any_removed = false;
-        for op in ops {
-            match op.kind {
-                PendingOpKind::Create => {
-                    // Resolve lists for the project
-                    if let Ok(lists) = client.resolve_lists(&op.project) {
-                        if let Some(name) = op.name.clone() {
-                            let due = op.due.as_deref();
-                            if let Ok(cid) = client.create_card(&lists.todo_list_id, &name, due) {
-                                // Update the first matching local todo without card id
-                                if let Some(t) = self.todos.iter_mut().find(|t| t.project == op.project && t.planka_card_id.is_none() && t.description == name) {
-                                    // Capture desired target before overwriting list_id
-                                    let wants_doing = t.planka_list_id.as_deref() == Some(lists.doing_list_id.as_str());
-                                    let wants_done = t.done;
-                                    t.planka_card_id = Some(cid.clone());
-                                    t.planka_list_id = Some(lists.todo_list_id.clone());
-                                    t.planka_board_id = Some(lists.board_id.clone());
-                                    t.sync_dirty = false;
-                                    // If user had toggled Doing (or Done) before create succeeded, move now
-                                    if wants_doing {
-                                        let _ = client.move_card(&cid, &lists.doing_list_id);
-                                        t.planka_list_id = Some(lists.doing_list_id.clone());
-                                    } else if wants_done {
-                                        let _ = client.move_card(&cid, &lists.done_list_id);
-                                        t.planka_list_id = Some(lists.done_list_id.clone());
-                                    }
-                                }
-                                // Remove op
-                                if let Some(pos) = self.pending_ops.iter().position(|p| p.ts == op.ts) {
-                                    self.pending_ops.remove(pos);
-                                    any_removed = true;
-                                }
-                            }
-                        }
-                    }
+            Delta::Delete { project, id } => {
+                // Local dirty edits win; the pending op will surface the error
+                let Some(idx) = self.todos.iter().position(|t| t.project == project && t.planka_card_id.as_deref() == Some(id.as_str())) else { return; };
+                if self.todos[idx].sync_dirty { return; }
+                self.todos.remove(idx);
+                self.kanban_stale(&project);
+                let len = self.visible_indices().len();
+                if self.selected >= len && self.selected > 0 {
+                    self.selected = len.saturating_sub(1);
                 }
-                PendingOpKind::Move => {
-                    if let (Some(ref cid), Some(ref lid)) = (op.card_id.as_ref(), op.list_id.as_ref()) {
-                        if client.move_card(cid, lid).is_ok() {
-                            if let Some(t) = self.todos.iter_mut().find(|t| t.planka_card_id.as_deref() == Some(cid.as_str())) {
-                                t.planka_list_id = Some(lid.to_string());
-                                t.sync_dirty = false;
-                            }
-                            if let Some(pos) = self.pending_ops.iter().position(|p| p.ts == op.ts) {
-                                self.pending_ops.remove(pos);
-                                any_removed = true;
-                            }
+            }
+            Delta::Archived { project, id } => {
+                if let Some(t) = self.todos.iter_mut().find(|t| t.project == project && t.planka_card_id.as_deref() == Some(id.as_str())) {
+                    if t.sync_dirty { return; }
+                    t.archived = true;
+                }
+                self.kanban_stale(&project);
+            }
+            Delta::Labels { project, labels } => {
+                self.board_labels.insert(project, labels);
+            }
+            Delta::Label { project, label, deleted } => {
+                let labels = self.board_labels.entry(project.clone()).or_default();
+                labels.retain(|l| l.id != label.id);
+                if deleted {
+                    // Planka drops the label from its cards along with it
+                    for t in self.todos.iter_mut().filter(|t| t.project == project) {
+                        t.labels.retain(|l| l != &label.id);
+                        if let Some(ref mut base) = t.base {
+                            base.labels.retain(|l| l != &label.id);
                         }
                     }
+                    if self.label_filter.as_deref() == Some(label.id.as_str()) {
+                        self.label_filter = None;
+                    }
+                } else {
+                    labels.push(label);
+                    labels.sort_by(|a, b| a.position.total_cmp(&b.position));
                 }
-                PendingOpKind::Delete => {
-                    if let Some(ref cid) = op.card_id {
-                        if client.delete_card(cid).is_ok() {
-                            if let Some(pos) = self.pending_ops.iter().position(|p| p.ts == op.ts) {
-                                self.pending_ops.remove(pos);
-                                any_removed = true;
-                            }
-                        }
+            }
+            Delta::CardLabel { card_id, label_id, added } => {
+                // Remote now has this label state, so it is also the new base
+                if let Some(t) = self.todos.iter_mut().find(|t| t.planka_card_id.as_deref() == Some(card_id.as_str())) {
+                    toggle_id(&mut t.labels, &label_id, added);
+                    if let Some(ref mut base) = t.base {
+                        toggle_id(&mut base.labels, &label_id, added);
                     }
                 }
-                PendingOpKind::Update => {
-                    if let Some(ref cid) = op.card_id {
-                        let _ = client.update_card(cid, op.name.as_deref(), op.due.as_deref());
-                        if let Some(pos) = self.pending_ops.iter().position(|p| p.ts == op.ts) {
-                            self.pending_ops.remove(pos);
-                            any_removed = true;
-                        }
+                self.note_card_label(&card_id, &label_id, added);
+            }
+            Delta::Members { project, members } => {
+                self.board_members.insert(project, members);
+            }
+            Delta::Notifications(list) => self.set_notifications(list),
+            Delta::Notification(n) => {
+                match self.notifications.iter_mut().find(|x| x.id == n.id) {
+                    // Updates (e.g. read elsewhere) carry no included users; keep the creator
+                    Some(x) => *x = PlankaNotification { creator: n.creator.or(x.creator.take()), ..n },
+                    None => self.notifications.insert(0, n),
+                }
+            }
+            Delta::CardMember { card_id, user_id, added } => {
+                if let Some(t) = self.todos.iter_mut().find(|t| t.planka_card_id.as_deref() == Some(card_id.as_str())) {
+                    toggle_id(&mut t.members, &user_id, added);
+                    if let Some(ref mut base) = t.base {
+                        toggle_id(&mut base.members, &user_id, added);
                     }
                 }
+                self.note_card_member(&card_id, &user_id, added);
             }
+            Delta::Message(msg) => self.error_message = Some(msg),
         }
-        if any_removed { self.save_pending_ops(); }
     }
 
-    pub fn drain_inbound(&mut self) {
-        let Some(rx) = self.inbound_rx.take() else { return; };
-        let rx = rx;
-        while let Ok(d) = rx.try_recv() {
-            self.apply_delta(d);
+    // Three-way merge of a remote snapshot into todos[idx]. Fields changed on
+    // one side only are taken from that side; fields changed on both sides are
+    // left alone and recorded in `conflicts` for the user to resolve. Labels and
+    // members are left to `merge_remote_labels`/`merge_remote_members`, as not
+    // every source carries them.
+    fn merge_remote(&mut self, idx: usize, mut remote: SyncBase) {
+        let Some(t) = self.todos.get_mut(idx) else { return; };
+        let card_id = t.planka_card_id.clone().unwrap_or_default();
+        remote.labels = t.base.as_ref().map(|b| b.labels.clone()).unwrap_or_else(|| t.labels.clone());
+        remote.members = t.base.as_ref().map(|b| b.members.clone()).unwrap_or_else(|| t.members.clone());
+        let Some(base) = t.base.clone() else {
+            // No base yet: remote wins unless there are unsynced local edits
+            if !t.sync_dirty {
+                t.description = remote.name.clone();
+                t.due_date = remote.due.clone();
+                t.planka_list_id = remote.list_id.clone();
+                t.done = remote.done;
+            }
+            t.base = Some(remote);
+            return;
+        };
+        let mut new_base = remote.clone();
+        let mut found: Vec<ConflictField> = Vec::new();
+        match merge3(&base.name, &t.description, &remote.name) {
+            Merge::Keep => {}
+            Merge::Take(v) => t.description = v,
+            Merge::Conflict => {
+                found.push(ConflictField::Name);
+                new_base.name = base.name.clone();
+            }
+        }
+        match merge3(&base.due, &t.due_date, &remote.due) {
+            Merge::Keep => {}
+            Merge::Take(v) => t.due_date = v,
+            Merge::Conflict => {
+                found.push(ConflictField::Due);
+                new_base.due = base.due.clone();
+            }
+        }
+        let base_list = list_key(&base.list_id, base.done);
+        let local_list = list_key(&t.planka_list_id, t.done);
+        let remote_list = list_key(&remote.list_id, remote.done);
+        match merge3(&base_list, &local_list, &remote_list) {
+            Merge::Keep => {}
+            Merge::Take(_) => {
+                t.planka_list_id = remote.list_id.clone();
+                t.done = remote.done;
+            }
+            Merge::Conflict => {
+                found.push(ConflictField::List);
+                new_base.list_id = base.list_id.clone();
+                new_base.done = base.done;
+            }
+        }
+        t.base = Some(new_base);
+        let project = t.project.clone();
+
+        // Drop conflicts for this card that have since converged, refresh the rest
+        self.conflicts.retain(|c| c.card_id != card_id || found.contains(&c.field));
+        for field in found {
+            if let Some(c) = self.conflicts.iter_mut().find(|c| c.card_id == card_id && c.field == field) {
+                c.remote = remote.clone();
+            } else {
+                self.conflicts.push(Conflict { project: project.clone(), card_id: card_id.clone(), field, remote: remote.clone() });
+            }
+        }
+        if self.conflict_index >= self.conflicts.len() {
+            self.conflict_index = self.conflicts.len().saturating_sub(1);
         }
-        self.inbound_rx = Some(rx);
     }
 
-    pub fn ensure_planka_client(&mut self) -> Result<PlankaClient, String> {
-        let cfg = self
-            .planka_config
-            .clone()
-            .ok_or_else(|| "Planka config not set. Press 'L' to login/setup.".to_string())?;
-        let (client, new_cfg) = PlankaClient::from_config(cfg)?;
-        self.planka_config = Some(new_cfg);
-        Ok(client)
+    // Set-wise three-way merge of a card's labels, so labels never conflict
+    fn merge_remote_labels(&mut self, idx: usize, remote: &[String]) {
+        let Some(project) = self.todos.get(idx).map(|t| t.project.clone()) else { return; };
+        let known: Option<HashSet<&str>> = self.board_labels.get(&project).map(|ls| ls.iter().map(|l| l.id.as_str()).collect());
+        let t = &mut self.todos[idx];
+        let base = t.base.as_ref().map(|b| b.labels.clone()).unwrap_or_default();
+        // A label deleted from the board can't be put back
+        t.labels = merge_set(&base, &t.labels, remote, |l| known.as_ref().is_none_or(|k| k.contains(l)));
+        if let Some(ref mut b) = t.base {
+            b.labels = remote.to_vec();
+        }
     }
 
-    pub fn sync_current_project_from_planka(&mut self) {
-        match self.ensure_planka_client() {
-            Ok(client) => {
-                // 1) Fetch boards and use them as “projects”
-                if let Ok(boards) = client.fetch_boards() {
-                    if !boards.is_empty() {
-                        let names: Vec<String> = boards.iter().map(|b| b.name.clone()).collect();
-                        self.planka_boards = boards;
-                        self.projects = names;
-                        if !self.projects.iter().any(|p| p == &self.current_project) {
-                            if let Some(first) = self.projects.get(0) {
-                                self.current_project = first.clone();
-                                self.selected = 0;
-                            }
-                        }
-                    }
-                }
-                // 2) Resolve lists for the current project (board name)
-                match client.resolve_lists(&self.current_project) {
-                    Ok(lists) => {
-                        self.planka_lists = Some(lists.clone());
-                        self.planka_lists_by_board
-                            .insert(self.current_project.clone(), lists.clone());
-                        // 3) Fetch cards from todo/doing/done lists
-                        let mut all_cards: Vec<(PlankaCard, String)> = Vec::new(); // (card, list_id)
-                        // todo list
-                        if let Ok(cards) = client.fetch_cards(&lists.todo_list_id) {
-                            all_cards.extend(
-                                cards
-                                    .into_iter()
-                                    .map(|c| (c, lists.todo_list_id.clone())),
-                            );
-                        }
-                        // doing list (treat as not done)
-                        if let Ok(cards) = client.fetch_cards(&lists.doing_list_id) {
-                            all_cards.extend(
-                                cards
-                                    .into_iter()
-                                    .map(|c| (c, lists.doing_list_id.clone())),
-                            );
-                        }
-                        // done list
-                        if let Ok(cards) = client.fetch_cards(&lists.done_list_id) {
-                            all_cards.extend(
-                                cards
-                                    .into_iter()
-                                    .map(|c| (c, lists.done_list_id.clone())),
-                            );
-                        }
+    // Same for the users assigned to a card
+    fn merge_remote_members(&mut self, idx: usize, remote: &[String]) {
+        let Some(project) = self.todos.get(idx).map(|t| t.project.clone()) else { return; };
+        let known: Option<HashSet<&str>> = self.board_members.get(&project).map(|us| us.iter().map(|u| u.id.as_str()).collect());
+        let t = &mut self.todos[idx];
+        let base = t.base.as_ref().map(|b| b.members.clone()).unwrap_or_default();
+        t.members = merge_set(&base, &t.members, remote, |u| known.as_ref().is_none_or(|k| k.contains(u)));
+        if let Some(ref mut b) = t.base {
+            b.members = remote.to_vec();
+        }
+    }
 
-                        // 4) Merge into local todos for the current project (by planka_card_id)
-                        let mut index_by_card: HashMap<String, usize> = HashMap::new();
-                        for (i, t) in self.todos.iter().enumerate() {
-                            if t.project == self.current_project {
-                                if let Some(ref cid) = t.planka_card_id {
-                                    index_by_card.insert(cid.clone(), i);
-                                }
-                            }
-                        }
+    pub fn open_conflicts(&mut self) {
+        if self.conflicts.is_empty() {
+            self.error_message = Some("No sync conflicts".to_string());
+            return;
+        }
+        if self.conflict_index >= self.conflicts.len() {
+            self.conflict_index = 0;
+        }
+        self.input_mode = InputMode::ResolvingConflicts;
+        self.error_message = None;
+    }
 
-                        for (card, list_id) in all_cards {
-                            if let Some(&idx) = index_by_card.get(&card.id) {
-                                // Update existing
-                                if let Some(t) = self.todos.get_mut(idx) {
-                                    let is_done = list_id == lists.done_list_id;
-                                    t.description = card.name.clone();
-                                    t.done = is_done;
-                                    t.due_date = card
-                                        .due
-                                        .as_deref()
-                                        .and_then(|s| format_planka_due(s));
-                                    if let Some(ref s) = card.created {
-                                        t.created_date = format_planka_created(s);
-                                    }
-                                    t.planka_list_id = Some(list_id.clone());
-                                    t.planka_board_id = Some(lists.board_id.clone());
-                                }
-                            } else {
-                                // Insert new
-                                self.todos.push(Todo {
-                                    description: card.name.clone(),
-                                    done: list_id == lists.done_list_id,
-                                    due_date: card.due.as_deref().and_then(|s| format_planka_due(s)),
-                                    created_date: card
-                                        .created
-                                        .as_deref()
-                                        .map(|s| format_planka_created(s))
-                                        .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string()),
-                                    project: self.current_project.clone(),
-                                    planka_card_id: Some(card.id.clone()),
-                                    planka_list_id: Some(list_id.clone()),
-                                    planka_board_id: Some(lists.board_id.clone()),
-                                    sync_dirty: false,
-                                });
-                            }
-                        }
-                        self.error_message = Some("Synced from Planka".to_string());
-                    }
-                    Err(e) => self.error_message = Some(e),
-                }
+    // Remove the selected conflict and return it with the index of its todo
+    fn take_selected_conflict(&mut self) -> Option<(Conflict, usize)> {
+        if self.conflict_index >= self.conflicts.len() {
+            return None;
+        }
+        let c = self.conflicts.remove(self.conflict_index);
+        if self.conflict_index >= self.conflicts.len() {
+            self.conflict_index = self.conflicts.len().saturating_sub(1);
+        }
+        if self.conflicts.is_empty() {
+            self.input_mode = InputMode::Normal;
+        }
+        let idx = self
+            .todos
+            .iter()
+            .position(|t| t.planka_card_id.as_deref() == Some(c.card_id.as_str()))?;
+        Some((c, idx))
+    }
+
+    // Mark the remote value as seen so later merges treat the local value as the newer one
+    fn accept_remote_as_base(&mut self, idx: usize, c: &Conflict) {
+        let Some(t) = self.todos.get_mut(idx) else { return; };
+        let mut base = t.base.clone().unwrap_or_else(|| c.remote.clone());
+        match c.field {
+            ConflictField::Name => base.name = c.remote.name.clone(),
+            ConflictField::Due => base.due = c.remote.due.clone(),
+            ConflictField::List => {
+                base.list_id = c.remote.list_id.clone();
+                base.done = c.remote.done;
             }
-            Err(e) => self.error_message = Some(e),
         }
+        t.base = Some(base);
     }
 
-    pub fn sync_all_projects_from_planka(&mut self) {
-        let client = match self.ensure_planka_client() {
-            Ok(c) => c,
-            Err(e) => {
-                self.error_message = Some(e);
+    fn push_conflict_field(&mut self, idx: usize, c: &Conflict) {
+        let (local_id, name, due, done, list_id) = {
+            let t = &self.todos[idx];
+            (t.local_id.clone(), t.description.clone(), t.due_date.clone(), t.done, t.planka_list_id.clone())
+        };
+        let op = match c.field {
+            ConflictField::Name | ConflictField::Due => {
+                let (n, d) = if c.field == ConflictField::Name { (Some(name), None) } else { (None, due) };
+                PendingOp {
+                    id: new_id(),
+                    kind: PendingOpKind::Update,
+                    project: c.project.clone(),
+                    local_id: Some(local_id),
+                    card_id: Some(c.card_id.clone()),
+                    list_id: None,
+                    name: n,
+                    due: d,
+                    label_id: None,
+                    user_id: None,
+                    ts: Local::now().timestamp(),
+                    retry: RetryState::default(),
+                }
+            }
+            ConflictField::List => {
+                let target = if done {
+                    self.planka_lists_by_board.get(&c.project).map(|l| l.done_list_id().to_string())
+                } else {
+                    list_id
+                };
+                let Some(target) = target else {
+                    self.error_message = Some("Cannot resolve list: board lists unknown".to_string());
+                    return;
+                };
+                PendingOp {
+                    id: new_id(),
+                    kind: PendingOpKind::Move,
+                    project: c.project.clone(),
+                    local_id: Some(local_id),
+                    card_id: Some(c.card_id.clone()),
+                    list_id: Some(target),
+                    name: None,
+                    due: None,
+                    label_id: None,
+                    user_id: None,
+                    ts: Local::now().timestamp(),
+                    retry: RetryState::default(),
+                }
+            }
+        };
+        if let Some(t) = self.todos.get_mut(idx) {
+            t.sync_dirty = true;
+        }
+        self.enqueue_op(op);
+    }
+
+    pub fn resolve_conflict_local(&mut self) {
+        let Some((c, idx)) = self.take_selected_conflict() else { return; };
+        self.accept_remote_as_base(idx, &c);
+        self.push_conflict_field(idx, &c);
+    }
+
+    pub fn resolve_conflict_remote(&mut self) {
+        let Some((c, idx)) = self.take_selected_conflict() else { return; };
+        if let Some(t) = self.todos.get_mut(idx) {
+            match c.field {
+                ConflictField::Name => t.description = c.remote.name.clone(),
+                ConflictField::Due => t.due_date = c.remote.due.clone(),
+                ConflictField::List => {
+                    t.planka_list_id = c.remote.list_id.clone();
+                    t.done = c.remote.done;
+                }
+            }
+        }
+        self.accept_remote_as_base(idx, &c);
+    }
+
+    pub fn begin_manual_conflict(&mut self) {
+        let Some(c) = self.conflicts.get(self.conflict_index) else { return; };
+        let Some(t) = self.todos.iter().find(|t| t.planka_card_id.as_deref() == Some(c.card_id.as_str())) else { return; };
+        self.input_conflict = match c.field {
+            ConflictField::Name => t.description.clone(),
+            ConflictField::Due => t.due_date.clone().unwrap_or_default(),
+            ConflictField::List => {
+                self.error_message = Some("Pick local (l) or remote (r) for list conflicts".to_string());
                 return;
             }
         };
-        // 1) Fetch boards and reflect as projects
-        if let Ok(boards) = client.fetch_boards() {
-            if !boards.is_empty() {
-                self.planka_boards = boards.clone();
-                self.projects = boards.iter().map(|b| b.name.clone()).collect();
-                if !self.projects.iter().any(|p| p == &self.current_project) {
-                    if let Some(first) = self.projects.get(0) {
-                        self.current_project = first.clone();
-                        self.selected = 0;
-                    }
+        self.input_mode = InputMode::EditingConflictValue;
+        self.error_message = None;
+    }
+
+    pub fn submit_manual_conflict(&mut self) -> Result<(), String> {
+        let field = self.conflicts.get(self.conflict_index).map(|c| c.field);
+        let value = self.input_conflict.trim().to_string();
+        let due = match field {
+            Some(ConflictField::Name) if value.is_empty() => {
+                return Err("Description cannot be empty.".to_string());
+            }
+            Some(ConflictField::Due) if !value.is_empty() => Some(parse_due_date(&value)?),
+            _ => None,
+        };
+        self.input_mode = InputMode::ResolvingConflicts;
+        let Some((c, idx)) = self.take_selected_conflict() else { return Ok(()); };
+        if let Some(t) = self.todos.get_mut(idx) {
+            match c.field {
+                ConflictField::Name => t.description = value,
+                ConflictField::Due => t.due_date = due,
+                ConflictField::List => {}
+            }
+        }
+        self.input_conflict.clear();
+        self.accept_remote_as_base(idx, &c);
+        self.push_conflict_field(idx, &c);
+        Ok(())
+    }
+
+    // Dispatch every due op to the worker; results come back through `finish_op`
+    pub fn process_pending_ops_tick(&mut self) {
+        if self.planka_config.is_none() {
+            return; // keep ops queued
+        }
+        let now = Local::now().timestamp();
+        let due: Vec<PendingOp> = sendable_ops(&self.pending_ops, &self.ops_in_flight, now).into_iter().cloned().collect();
+        let mut changed = false;
+        for op in due {
+            if let PendingOpKind::Create = op.kind
+                && !self.create_target(&op).map(|i| self.todos[i].planka_card_id.is_none()).unwrap_or(false)
+            {
+                // Todo was deleted locally or already created (e.g. by a full sync)
+                self.pending_ops.retain(|p| p.id != op.id);
+                changed = true;
+                continue;
+            }
+            self.ops_in_flight.insert(op.id.clone());
+            let job_op = op.clone();
+            self.spawn_job(
+                move |client| Self::run_pending_op(client, &job_op),
+                move |app, res| app.finish_op(op, res),
+            );
+        }
+        if changed { self.save_pending_ops(); }
+    }
+
+    // Target the todo by its local id; older queued ops only carry the description
+    fn create_target(&self, op: &PendingOp) -> Option<usize> {
+        let name = op.name.as_deref().unwrap_or_default();
+        self.todos.iter().position(|t| match op.local_id {
+            Some(ref lid) => &t.local_id == lid,
+            None => t.project == op.project && t.planka_card_id.is_none() && t.description == name,
+        })
+    }
+
+    // Network half of an op; runs on the worker
+    fn run_pending_op(client: &PlankaClient, op: &PendingOp) -> Result<Option<(String, PlankaLists)>, PlankaError> {
+        match op.kind {
+            PendingOpKind::Create => {
+                let name = op.name.clone().ok_or_else(|| PlankaError::Config("Create op has no name".to_string()))?;
+                let lists = client.resolve_lists(&op.project)?;
+                let cid = client.create_card(lists.first_list_id(), &name, op.due.as_deref())?;
+                Ok(Some((cid, lists)))
+            }
+            PendingOpKind::Move => {
+                let (Some(cid), Some(lid)) = (op.card_id.as_ref(), op.list_id.as_ref()) else {
+                    return Err(PlankaError::Config("Move op has no card or list".to_string()));
+                };
+                client.move_card(cid, lid)?;
+                Ok(None)
+            }
+            PendingOpKind::Delete => {
+                let cid = op.card_id.as_ref().ok_or_else(|| PlankaError::Config("Delete op has no card".to_string()))?;
+                client.delete_card(cid)?;
+                Ok(None)
+            }
+            PendingOpKind::Update => {
+                let cid = op.card_id.as_ref().ok_or_else(|| PlankaError::Config("Update op has no card".to_string()))?;
+                client.update_card(cid, op.name.as_deref(), op.due.as_deref())?;
+                Ok(None)
+            }
+            PendingOpKind::AddLabel | PendingOpKind::RemoveLabel => {
+                let (Some(cid), Some(lid)) = (op.card_id.as_ref(), op.label_id.as_ref()) else {
+                    return Err(PlankaError::Config("Label op has no card or label".to_string()));
+                };
+                let add = matches!(op.kind, PendingOpKind::AddLabel);
+                let res = if add { client.add_label_to_card(cid, lid) } else { client.remove_label_from_card(cid, lid) };
+                match res {
+                    // Already on / already off the card: nothing left to do
+                    Err(PlankaError::Server { status: 409, .. }) if add => Ok(None),
+                    Err(PlankaError::NotFound(_)) if !add => Ok(None),
+                    r => r.map(|_| None),
+                }
+            }
+            PendingOpKind::AddMember | PendingOpKind::RemoveMember => {
+                let (Some(cid), Some(uid)) = (op.card_id.as_ref(), op.user_id.as_ref()) else {
+                    return Err(PlankaError::Config("Member op has no card or user".to_string()));
+                };
+                let add = matches!(op.kind, PendingOpKind::AddMember);
+                let res = if add { client.add_member_to_card(cid, uid) } else { client.remove_member_from_card(cid, uid) };
+                match res {
+                    Err(PlankaError::Server { status: 409, .. }) if add => Ok(None),
+                    Err(PlankaError::NotFound(_)) if !add => Ok(None),
+                    r => r.map(|_| None),
                 }
             }
         }
-        // 2) For each board: resolve lists, pull remote, merge; then push local diffs
-        for b in &self.planka_boards {
-            let proj = b.name.clone();
-            // Resolve and cache lists for this board
-            let lists = if let Some(l) = self.planka_lists_by_board.get(&proj).cloned() {
-                l
-            } else {
-                match client.resolve_lists(&proj) {
-                    Ok(l) => {
-                        self.planka_lists_by_board.insert(proj.clone(), l.clone());
-                        if proj == self.current_project {
-                            self.planka_lists = Some(l.clone());
-                        }
-                        l
+    }
+
+    // UI half of an op: drop it from the queue on success, back off on failure
+    fn finish_op(&mut self, op: PendingOp, result: Result<Option<(String, PlankaLists)>, PlankaError>) {
+        self.ops_in_flight.remove(&op.id);
+        let pos = self.pending_ops.iter().position(|p| p.id == op.id);
+        match result {
+            Ok(created) => {
+                if let Some(pos) = pos {
+                    self.pending_ops.remove(pos);
+                }
+                // Apply even if the op was discarded meanwhile: the card exists remotely now
+                self.apply_op_result(&op, created);
+                self.kanban_stale(&op.project);
+            }
+            // The card is gone remotely: a delete is done, other changes re-create it
+            Err(PlankaError::NotFound(_)) if !matches!(op.kind, PendingOpKind::Create) => {
+                if let Some(pos) = pos {
+                    self.pending_ops.remove(pos);
+                }
+                // A label or member op can also fail because the label or user is gone; the next pull sorts it out
+                if !matches!(
+                    op.kind,
+                    PendingOpKind::Delete | PendingOpKind::AddLabel | PendingOpKind::RemoveLabel | PendingOpKind::AddMember | PendingOpKind::RemoveMember
+                ) {
+                    self.relink_lost_card(&op);
+                }
+            }
+            Err(e) => {
+                let Some(pos) = pos else { return; };
+                let now = Local::now().timestamp();
+                // Bad requests won't get better by waiting; park them for the user straight away
+                let permanent = !e.is_transient() && !matches!(e, PlankaError::Unauthorized(_));
+                let retry = &mut self.pending_ops[pos].retry;
+                let exhausted = retry.fail(e.to_string(), now);
+                if permanent {
+                    self.error_message = Some(format!("Planka {} failed: {}", op.kind.label(), e));
+                } else if retry.attempts == 1 {
+                    self.error_message = Some(format!("Planka {} failed (queued for retry): {}", op.kind.label(), e));
+                }
+                if permanent || exhausted {
+                    let dead = self.pending_ops.remove(pos);
+                    self.dead_ops.push(dead);
+                }
+            }
+        }
+        self.save_pending_ops();
+        // Ops that waited behind this one for the same card can go now
+        self.process_pending_ops_tick();
+    }
+
+    // Unlink a todo whose card was deleted remotely while it had local changes,
+    // and queue a create so those changes aren't lost
+    fn relink_lost_card(&mut self, op: &PendingOp) {
+        let Some(ref cid) = op.card_id else { return; };
+        self.pending_ops.retain(|p| p.card_id.as_ref() != Some(cid));
+        let Some(t) = self.todos.iter_mut().find(|t| t.planka_card_id.as_ref() == Some(cid)) else { return; };
+        t.planka_card_id = None;
+        t.base = None;
+        t.sync_dirty = true;
+        let create = PendingOp {
+            id: new_id(),
+            kind: PendingOpKind::Create,
+            project: t.project.clone(),
+            local_id: Some(t.local_id.clone()),
+            card_id: None,
+            list_id: None,
+            name: Some(t.description.clone()),
+            due: t.due_date.clone(),
+            label_id: None,
+            user_id: None,
+            ts: Local::now().timestamp(),
+            retry: RetryState::default(),
+        };
+        self.enqueue_op(create);
+    }
+
+    fn apply_op_result(&mut self, op: &PendingOp, created: Option<(String, PlankaLists)>) {
+        match op.kind {
+            PendingOpKind::Create => {
+                let Some((cid, lists)) = created else { return; };
+                self.planka_lists_by_board.entry(op.project.clone()).or_insert_with(|| lists.clone());
+                let Some(idx) = self.create_target(op) else { return; };
+                let t = &mut self.todos[idx];
+                if t.planka_card_id.is_some() {
+                    return;
+                }
+                // Capture desired target before overwriting list_id
+                let wants = lists.stage_index(t.planka_list_id.as_deref(), t.done);
+                t.planka_card_id = Some(cid.clone());
+                t.planka_list_id = Some(lists.first_list_id().to_string());
+                t.planka_board_id = Some(lists.board_id.clone());
+                t.sync_dirty = false;
+                t.base = Some(SyncBase {
+                    name: op.name.clone().unwrap_or_default(),
+                    due: op.due.clone(),
+                    list_id: Some(lists.first_list_id().to_string()),
+                    done: false,
+                    labels: Vec::new(),
+                    members: Vec::new(),
+                });
+                let local_id = t.local_id.clone();
+                // Changes made while the create was queued or running follow as their own ops
+                let (name, due) = (t.description.clone(), t.due_date.clone());
+                let target = if wants > 0 { Some(lists.stages[wants].list_id.clone()) } else { None };
+                let labels = t.labels.clone();
+                let members = t.members.clone();
+                if let Some(ref target) = target {
+                    t.planka_list_id = Some(target.clone());
+                }
+                if Some(&name) != op.name.as_ref() || due != op.due {
+                    t.sync_dirty = true;
+                    self.enqueue_op(PendingOp {
+                        id: new_id(),
+                        kind: PendingOpKind::Update,
+                        project: op.project.clone(),
+                        local_id: Some(local_id.clone()),
+                        card_id: Some(cid.clone()),
+                        list_id: None,
+                        name: Some(name),
+                        due,
+                        label_id: None,
+                        user_id: None,
+                        ts: Local::now().timestamp(),
+                        retry: RetryState::default(),
+                    });
+                }
+                // Labels and members kept from a card that was deleted remotely
+                for label in labels {
+                    self.todos[idx].sync_dirty = true;
+                    self.enqueue_op(PendingOp {
+                        id: new_id(),
+                        kind: PendingOpKind::AddLabel,
+                        project: op.project.clone(),
+                        local_id: Some(local_id.clone()),
+                        card_id: Some(cid.clone()),
+                        list_id: None,
+                        name: None,
+                        due: None,
+                        label_id: Some(label),
+                        user_id: None,
+                        ts: Local::now().timestamp(),
+                        retry: RetryState::default(),
+                    });
+                }
+                for user in members {
+                    self.todos[idx].sync_dirty = true;
+                    self.enqueue_op(PendingOp {
+                        id: new_id(),
+                        kind: PendingOpKind::AddMember,
+                        project: op.project.clone(),
+                        local_id: Some(local_id.clone()),
+                        card_id: Some(cid.clone()),
+                        list_id: None,
+                        name: None,
+                        due: None,
+                        label_id: None,
+                        user_id: Some(user),
+                        ts: Local::now().timestamp(),
+                        retry: RetryState::default(),
+                    });
+                }
+                if let Some(target) = target {
+                    self.todos[idx].sync_dirty = true;
+                    self.enqueue_op(PendingOp {
+                        id: new_id(),
+                        kind: PendingOpKind::Move,
+                        project: op.project.clone(),
+                        local_id: Some(local_id),
+                        card_id: Some(cid),
+                        list_id: Some(target),
+                        name: None,
+                        due: None,
+                        label_id: None,
+                        user_id: None,
+                        ts: Local::now().timestamp(),
+                        retry: RetryState::default(),
+                    });
+                }
+            }
+            PendingOpKind::Move
+            | PendingOpKind::Update
+            | PendingOpKind::AddLabel
+            | PendingOpKind::RemoveLabel
+            | PendingOpKind::AddMember
+            | PendingOpKind::RemoveMember => {
+                let Some(ref cid) = op.card_id else { return; };
+                // Later ops for the same card still carry newer local state
+                if self.pending_ops.iter().any(|p| p.card_id.as_ref() == Some(cid)) {
+                    return;
+                }
+                if let Some(t) = self.todos.iter_mut().find(|t| t.planka_card_id.as_ref() == Some(cid)) {
+                    if let (PendingOpKind::Move, Some(lid)) = (&op.kind, &op.list_id) {
+                        t.planka_list_id = Some(lid.clone());
                     }
+                    t.sync_dirty = false;
+                }
+            }
+            PendingOpKind::Delete => {}
+        }
+    }
+
+    // Sync state of a todo's queued changes: Some(true) while a request runs, Some(false) while waiting
+    pub fn row_pending(&self, local_id: &str) -> Option<bool> {
+        let mut queued = false;
+        for op in self.pending_ops.iter().filter(|op| op.local_id.as_deref() == Some(local_id)) {
+            if self.ops_in_flight.contains(&op.id) {
+                return Some(true);
+            }
+            queued = true;
+        }
+        if queued { Some(false) } else { None }
+    }
+
+    fn op_index(&self, dead: bool) -> Option<usize> {
+        let pending = self.pending_ops.len();
+        match (dead, self.ops_index < pending) {
+            (false, true) => Some(self.ops_index),
+            (true, false) if self.ops_index - pending < self.dead_ops.len() => Some(self.ops_index - pending),
+            _ => None,
+        }
+    }
+
+    pub fn open_pending_ops(&mut self) {
+        self.ops_index = 0;
+        self.input_mode = InputMode::ViewingPendingOps;
+        self.error_message = None;
+    }
+
+    // Retry the selected op now; dead ops go back to the queue with a fresh budget
+    pub fn retry_selected_op(&mut self) {
+        if let Some(i) = self.op_index(false) {
+            self.pending_ops[i].retry.next_at = 0;
+        } else if let Some(i) = self.op_index(true) {
+            let mut op = self.dead_ops.remove(i);
+            op.retry = RetryState::default();
+            let at = requeue_position(&self.pending_ops, &op);
+            self.pending_ops.insert(at, op);
+        } else {
+            return;
+        }
+        self.save_pending_ops();
+        self.error_message = Some("Operation queued for retry".to_string());
+    }
+
+    pub fn discard_selected_op(&mut self) {
+        let op = if let Some(i) = self.op_index(false) {
+            self.pending_ops.remove(i)
+        } else if let Some(i) = self.op_index(true) {
+            self.dead_ops.remove(i)
+        } else {
+            return;
+        };
+        // Nothing else will push this todo's change; let remote updates apply again
+        if let Some(ref lid) = op.local_id
+            && !self.pending_ops.iter().any(|p| p.local_id.as_ref() == Some(lid))
+            && let Some(t) = self.todos.iter_mut().find(|t| &t.local_id == lid)
+        {
+            t.sync_dirty = false;
+        }
+        let total = self.pending_ops.len() + self.dead_ops.len();
+        if self.ops_index >= total {
+            self.ops_index = total.saturating_sub(1);
+        }
+        self.save_pending_ops();
+        self.error_message = Some("Operation discarded".to_string());
+    }
+
+    pub fn drain_inbound(&mut self) {
+        let Some(rx) = self.inbound_rx.take() else { return; };
+        let rx = rx;
+        while let Ok(d) = rx.try_recv() {
+            self.apply_delta(d);
+        }
+        self.inbound_rx = Some(rx);
+    }
+
+    // Run `work` on the network worker and hand its result to `done` on the UI thread
+    pub fn spawn_job<T, W, D>(&mut self, work: W, done: D)
+    where
+        T: Send + 'static,
+        W: FnOnce(&PlankaClient) -> Result<T, PlankaError> + Send + 'static,
+        D: FnOnce(&mut App, Result<T, PlankaError>) + Send + 'static,
+    {
+        match self.planka_config.clone() {
+            Some(cfg) => self.spawn_job_with(cfg, work, done),
+            None => done(self, Err(PlankaError::Config("Planka config not set. Press 'L' to login/setup.".to_string()))),
+        }
+    }
+
+    // Same as `spawn_job` without logging in, for requests made before there is a login
+    pub fn spawn_anonymous_job<T, W, D>(&mut self, server_url: String, work: W, done: D)
+    where
+        T: Send + 'static,
+        W: FnOnce(&PlankaClient) -> Result<T, PlankaError> + Send + 'static,
+        D: FnOnce(&mut App, Result<T, PlankaError>) + Send + 'static,
+    {
+        self.worker.submit_anonymous(
+            server_url,
+            Box::new(move |client| {
+                let res = client.and_then(work);
+                Box::new(move |app: &mut App| done(app, res))
+            }),
+        );
+    }
+
+    // Same as `spawn_job` against an explicit config (used while logging in)
+    pub fn spawn_job_with<T, W, D>(&mut self, cfg: PlankaConfig, work: W, done: D)
+    where
+        T: Send + 'static,
+        W: FnOnce(&PlankaClient) -> Result<T, PlankaError> + Send + 'static,
+        D: FnOnce(&mut App, Result<T, PlankaError>) + Send + 'static,
+    {
+        self.worker.submit(
+            cfg,
+            Box::new(move |client| {
+                let res = client.and_then(work);
+                Box::new(move |app: &mut App| done(app, res))
+            }),
+        );
+    }
+
+    pub fn drain_worker(&mut self) {
+        for apply in self.worker.finished() {
+            apply(self);
+        }
+    }
+
+    // Block until every submitted job, and any job it chains, has been applied
+    pub fn wait_for_jobs(&mut self) {
+        while self.worker.in_flight > 0 {
+            let Some(apply) = self.worker.wait_one() else { break; };
+            apply(self);
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.worker.in_flight > 0
+    }
+
+    fn cache_lists(&mut self, project: &str, lists: PlankaLists) {
+        if project == self.current_project {
+            self.planka_lists = Some(lists.clone());
+        }
+        self.planka_lists_by_board.insert(project.to_string(), lists);
+    }
+
+    // Run `then` with the project's lists, resolving them on the worker when not cached
+    fn with_lists<F>(&mut self, project: String, then: F)
+    where
+        F: FnOnce(&mut App, PlankaLists) + Send + 'static,
+    {
+        if let Some(lists) = self.planka_lists_by_board.get(&project).cloned() {
+            then(self, lists);
+            return;
+        }
+        let p = project.clone();
+        self.spawn_job(
+            move |client| client.resolve_lists(&p),
+            move |app, res| match res {
+                Ok(lists) => {
+                    app.cache_lists(&project, lists.clone());
+                    then(app, lists);
+                }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
+    }
+
+    // Fetch the board list once, so board and "Project/Board" names can be resolved
+    pub fn ensure_boards(&mut self) {
+        if self.planka_config.is_none() || !self.planka_boards.is_empty() {
+            return;
+        }
+        self.spawn_job(
+            |client| client.fetch_boards(),
+            |app, res| {
+                let Ok(boards) = res else { return; };
+                app.adopt_boards(&boards);
+                app.planka_boards = boards;
+            },
+        );
+    }
+
+    // Fill the board and list caches for the current project in the background
+    pub fn ensure_lists(&mut self) {
+        if self.planka_config.is_none() || self.planka_lists_by_board.contains_key(&self.current_project) {
+            return;
+        }
+        let project = self.current_project.clone();
+        // Ensure boards are cached so the header can show "Project - Board";
+        // a project still keyed by name also needs them to find its board id
+        let need_boards = !self.planka_boards.iter().any(|b| b.id == project);
+        let p = project.clone();
+        self.spawn_job(
+            move |client| {
+                let boards = if need_boards { client.fetch_boards().unwrap_or_default() } else { Vec::new() };
+                let mut named = boards.iter().filter(|b| b.name.eq_ignore_ascii_case(&p));
+                let board_id = match (named.next(), named.next()) {
+                    (Some(b), None) if !boards.iter().any(|b| b.id == p) => b.id.clone(),
+                    _ => p,
+                };
+                Ok((boards, client.resolve_lists(&board_id).ok()))
+            },
+            move |app, res| {
+                let Ok((boards, lists)) = res else { return; };
+                if !boards.is_empty() {
+                    app.adopt_boards(&boards);
+                    app.planka_boards = boards;
+                }
+                if let Some(lists) = lists {
+                    let key = if app.projects.contains(&lists.board_id) { lists.board_id.clone() } else { project };
+                    app.cache_lists(&key, lists);
+                    app.kanban_stale(&key);
+                }
+            },
+        );
+    }
+
+    // Card ids per project that a pull may find removed remotely
+    fn stale_candidates(&self) -> HashMap<String, Vec<String>> {
+        let mut out: HashMap<String, Vec<String>> = HashMap::new();
+        for t in self.todos.iter().filter(|t| !t.sync_dirty && !t.archived) {
+            if let Some(ref cid) = t.planka_card_id {
+                out.entry(t.project.clone()).or_default().push(cid.clone());
+            }
+        }
+        out
+    }
+
+    // Network half of a sync: boards, lists and todo/doing/done cards. With
+    // `only` set just that board is pulled (falling back to the first board
+    // when it no longer exists).
+    fn pull_remote(
+        client: &PlankaClient,
+        only: Option<String>,
+        cached_boards: Vec<PlankaBoard>,
+        cached_lists: HashMap<String, PlankaLists>,
+        local: HashMap<String, Vec<String>>,
+    ) -> RemotePull {
+        let mut pull = RemotePull { boards: Vec::new(), pulled: Vec::new(), errors: Vec::new() };
+        if let Ok(boards) = client.fetch_boards()
+            && !boards.is_empty()
+        {
+            pull.boards = boards;
+        }
+        let known = if pull.boards.is_empty() { &cached_boards } else { &pull.boards };
+        let projects: Vec<String> = match only {
+            Some(p) if known.is_empty() => vec![p],
+            Some(p) => match known.iter().find(|b| b.id == p).or_else(|| known.iter().find(|b| b.name == p)) {
+                Some(b) => vec![b.id.clone()],
+                None => vec![known[0].id.clone()],
+            },
+            None => known.iter().map(|b| b.id.clone()).collect(),
+        };
+        for project in projects {
+            let lists = match cached_lists.get(&project) {
+                Some(l) => l.clone(),
+                None => match client.resolve_lists(&project) {
+                    Ok(l) => l,
                     Err(e) => {
-                        self.error_message = Some(e);
+                        pull.errors.push(e.to_string());
                         continue;
                     }
-                }
+                },
             };
-            // Pull: fetch remote cards (todo/doing/done)
-            let mut remote_by_id: HashMap<String, (PlankaCard, bool, String)> = HashMap::new();
-            if let Ok(cards) = client.fetch_cards(&lists.todo_list_id) {
-                for c in cards {
-                    remote_by_id.insert(c.id.clone(), (c, false, lists.todo_list_id.clone()));
+            let mut cards: Vec<(PlankaCard, bool, String)> = Vec::new();
+            let mut complete = true;
+            for stage in &lists.stages {
+                let done = stage.list_id == lists.done_list_id();
+                match client.fetch_cards(&stage.list_id) {
+                    Ok(cs) => cards.extend(cs.into_iter().map(|c| (c, done, stage.list_id.clone()))),
+                    Err(_) => complete = false,
                 }
             }
-            if let Ok(cards) = client.fetch_cards(&lists.doing_list_id) {
-                for c in cards {
-                    remote_by_id.insert(c.id.clone(), (c, false, lists.doing_list_id.clone()));
+            // Removed remotely: only trust the diff with a full remote view
+            let mut gone: Vec<(String, bool)> = Vec::new();
+            if complete {
+                let seen: HashSet<&str> = cards.iter().map(|(c, _, _)| c.id.as_str()).collect();
+                for cid in local.get(&project).into_iter().flatten() {
+                    if !seen.contains(cid.as_str())
+                        && let Ok(exists) = client.card_exists(cid)
+                    {
+                        gone.push((cid.clone(), exists));
+                    }
                 }
             }
-            if let Ok(cards) = client.fetch_cards(&lists.done_list_id) {
-                for c in cards {
-                    remote_by_id.insert(c.id.clone(), (c, true, lists.done_list_id.clone()));
+            let details = client.fetch_board_details(&lists.board_id).ok();
+            let (labels, members) = details.map(|d| (d.labels, d.members)).unzip();
+            pull.pulled.push(BoardPull { project, lists, cards, gone, labels, members });
+        }
+        pull
+    }
+
+    // UI half of a sync: merge remote state, then queue local changes the remote lacks
+    fn apply_pull(&mut self, pull: RemotePull, push: bool) {
+        if !pull.boards.is_empty() {
+            self.adopt_boards(&pull.boards);
+            self.projects = pull.boards.iter().map(|b| b.id.clone()).collect();
+            self.planka_boards = pull.boards;
+            if !self.projects.iter().any(|p| p == &self.current_project)
+                && let Some(first) = self.projects.first()
+            {
+                self.current_project = first.clone();
+                self.selected = 0;
+            }
+        }
+        let synced = !pull.pulled.is_empty();
+        for b in pull.pulled {
+            let proj = b.project.clone();
+            let lists = b.lists.clone();
+            self.cache_lists(&proj, lists.clone());
+            // Before merging cards, so labels deleted from the board are dropped
+            if let Some(labels) = b.labels {
+                self.board_labels.insert(proj.clone(), labels);
+            }
+            if let Some(members) = b.members {
+                self.board_members.insert(proj.clone(), members);
+            }
+            // Delete or tombstone clean local todos whose cards went away
+            for (cid, exists) in b.gone {
+                let Some(idx) = self.todos.iter().position(|t| t.planka_card_id.as_deref() == Some(cid.as_str())) else { continue; };
+                if self.todos[idx].sync_dirty {
+                    continue;
+                }
+                if exists {
+                    self.todos[idx].archived = true;
+                } else {
+                    self.todos.remove(idx);
                 }
             }
             // Local index by card id for this project
             let mut local_index: HashMap<String, usize> = HashMap::new();
             for (i, t) in self.todos.iter().enumerate() {
-                if t.project == proj {
-                    if let Some(ref cid) = t.planka_card_id {
-                        local_index.insert(cid.clone(), i);
-                    }
+                if t.project == proj
+                    && let Some(ref cid) = t.planka_card_id
+                {
+                    local_index.insert(cid.clone(), i);
                 }
             }
             // Merge remote -> local
-            for (_cid, (rcard, rdone, rlist)) in &remote_by_id {
+            for (rcard, rdone, rlist) in &b.cards {
                 if let Some(&idx) = local_index.get(&rcard.id) {
                     if let Some(t) = self.todos.get_mut(idx) {
-                        t.description = rcard.name.clone();
-                        t.done = *rdone;
-                        t.due_date = rcard
-                            .due
-                            .as_deref()
-                            .and_then(|s| format_planka_due(s));
                         if let Some(ref s) = rcard.created {
                             t.created_date = format_planka_created(s);
                         }
-                        t.planka_list_id = Some(rlist.clone());
                         t.planka_board_id = Some(lists.board_id.clone());
+                        t.archived = false;
                     }
+                    let remote = SyncBase {
+                        name: rcard.name.clone(),
+                        due: rcard.due.as_deref().and_then(format_planka_due),
+                        list_id: Some(rlist.clone()),
+                        done: *rdone,
+                        labels: Vec::new(),
+                        members: Vec::new(),
+                    };
+                    self.merge_remote(idx, remote);
+                    self.merge_remote_labels(idx, &rcard.label_ids);
+                    self.merge_remote_members(idx, &rcard.member_ids);
                 } else {
                     // Create local for remote-only card
                     self.todos.push(Todo {
+                        local_id: new_id(),
                         description: rcard.name.clone(),
                         done: *rdone,
-                        due_date: rcard.due.as_deref().and_then(|s| format_planka_due(s)),
+                        due_date: rcard.due.as_deref().and_then(format_planka_due),
                         created_date: rcard
                             .created
                             .as_deref()
-                            .map(|s| format_planka_created(s))
+                            .map(format_planka_created)
                             .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string()),
                         project: proj.clone(),
                         planka_card_id: Some(rcard.id.clone()),
                         planka_list_id: Some(rlist.clone()),
                         planka_board_id: Some(lists.board_id.clone()),
+                        labels: rcard.label_ids.clone(),
+                        members: rcard.member_ids.clone(),
+                        archived: false,
+                        base: None,
                         sync_dirty: false,
                     });
-                }
-            }
-            // Push local -> remote
-            for t in self.todos.iter_mut().filter(|t| t.project == proj) {
-                // New local: create remote
-                if t.planka_card_id.is_none() {
-                    let initial_list = if t.done {
-                        &lists.done_list_id
-                    } else if t.planka_list_id.as_deref() == Some(lists.doing_list_id.as_str()) {
-                        &lists.doing_list_id
-                    } else {
-                        &lists.todo_list_id
-                    };
-                    match client.create_card(initial_list, &t.description, t.due_date.as_deref()) {
-                        Ok(cid) => {
-                            t.planka_card_id = Some(cid);
-                            t.planka_list_id = Some(initial_list.clone());
-                            t.planka_board_id = Some(lists.board_id.clone());
-                        }
-                        Err(e) => {
-                            self.error_message = Some(format!("Planka create card failed: {}", e));
-                        }
-                    }
-                    continue;
-                }
-                // Existing linked: ensure list matches done-state and fields updated
-                if let Some(ref cid) = t.planka_card_id.clone() {
-                    let desired_list = if t.done {
-                        &lists.done_list_id
-                    } else if t.planka_list_id.as_deref() == Some(lists.doing_list_id.as_str()) {
-                        &lists.doing_list_id
-                    } else {
-                        &lists.todo_list_id
-                    };
-                    let remote = remote_by_id.get(cid);
-                    // Move if list differs
-                    if remote.map(|(_, _, l)| l.as_str()) != Some(desired_list.as_str()) {
-                        if let Err(e) = client.move_card(cid, desired_list) {
-                            self.error_message = Some(format!("Planka move failed: {}", e));
-                        } else {
-                            t.planka_list_id = Some(desired_list.clone());
-                        }
-                    }
-                    // Update name/due if differ
-                    if let Some((rcard, _rdone, _)) = remote {
-                        let due_disp = t.due_date.clone();
-                        let due_raw = due_disp.as_deref();
-                        let name_changed = rcard.name != t.description;
-                        let due_changed = rcard.due.as_deref() != due_raw;
-                        if name_changed || due_changed {
-                            let _ = client.update_card(
-                                cid,
-                                if name_changed { Some(&t.description) } else { None },
-                                if due_changed { due_raw } else { None },
-                            );
-                        }
+                    if let Some(t) = self.todos.last_mut() {
+                        t.base = Some(t.snapshot());
                     }
                 }
             }
+            if push {
+                self.push_local_changes(&proj, &lists, &b.cards);
+            }
         }
-        self.error_message = Some("Synced all projects from Planka".to_string());
-    }
-
-    pub fn start_planka_setup(&mut self) {
-        self.planka_setup = Some(PlankaSetupStep::Url);
-        self.input_planka = self
-            .planka_config
-            .as_ref()
-            .map(|c| c.server_url.clone())
-            .unwrap_or_default();
-        self.input_mode = InputMode::EditingPlanka;
-        self.error_message = None;
+        self.error_message = Some(match pull.errors.first() {
+            Some(e) if !synced => e.clone(),
+            _ => "Synced from Planka".to_string(),
+        });
     }
 
-    pub fn submit_planka_setup(&mut self) {
-        let step = match self.planka_setup {
-            Some(s) => s,
-            None => return,
-        };
-        let mut cfg = self.planka_config.clone().unwrap_or_default();
-        match step {
+    // Queue ops for local todos that differ from the pulled remote cards
+    fn push_local_changes(&mut self, proj: &str, lists: &PlankaLists, cards: &[(PlankaCard, bool, String)]) {
+        let remote_by_id: HashMap<&str, &(PlankaCard, bool, String)> =
+            cards.iter().map(|c| (c.0.id.as_str(), c)).collect();
+        let mut ops: Vec<PendingOp> = Vec::new();
+        for t in self.todos.iter().filter(|t| t.project == proj && !t.archived) {
+            // Todos with queued ops are already on their way
+            if self.pending_ops.iter().any(|op| op.local_id.as_ref() == Some(&t.local_id)) {
+                continue;
+            }
+            let op = |kind, card_id: Option<String>, list_id, name, due| PendingOp {
+                id: new_id(),
+                kind,
+                project: proj.to_string(),
+                local_id: Some(t.local_id.clone()),
+                card_id,
+                list_id,
+                name,
+                due,
+                label_id: None,
+                user_id: None,
+                ts: Local::now().timestamp(),
+                retry: RetryState::default(),
+            };
+            // New local: create remote (the create moves it to Doing/Done afterwards)
+            let Some(ref cid) = t.planka_card_id else {
+                ops.push(op(PendingOpKind::Create, None, None, Some(t.description.clone()), t.due_date.clone()));
+                continue;
+            };
+            let Some((rcard, _, rlist)) = remote_by_id.get(cid.as_str()) else { continue; };
+            // Existing linked: ensure list matches done-state and fields updated
+            let desired_list = &lists.stages[lists.stage_index(t.planka_list_id.as_deref(), t.done)].list_id;
+            // Fields with an open conflict wait for the user's decision
+            let conflicted = |f: ConflictField| self.conflicts.iter().any(|c| &c.card_id == cid && c.field == f);
+            if !conflicted(ConflictField::List) && rlist != desired_list {
+                ops.push(op(PendingOpKind::Move, Some(cid.clone()), Some(desired_list.clone()), None, None));
+            }
+            let name_changed = !conflicted(ConflictField::Name) && rcard.name != t.description;
+            let due_changed = !conflicted(ConflictField::Due)
+                && rcard.due.as_deref().and_then(format_planka_due) != t.due_date;
+            if name_changed || due_changed {
+                ops.push(op(
+                    PendingOpKind::Update,
+                    Some(cid.clone()),
+                    None,
+                    if name_changed { Some(t.description.clone()) } else { None },
+                    if due_changed { t.due_date.clone() } else { None },
+                ));
+            }
+            for label in t.labels.iter().filter(|l| !rcard.label_ids.contains(l)) {
+                ops.push(PendingOp { label_id: Some(label.clone()), ..op(PendingOpKind::AddLabel, Some(cid.clone()), None, None, None) });
+            }
+            for label in rcard.label_ids.iter().filter(|l| !t.labels.contains(l)) {
+                ops.push(PendingOp { label_id: Some(label.clone()), ..op(PendingOpKind::RemoveLabel, Some(cid.clone()), None, None, None) });
+            }
+            for user in t.members.iter().filter(|u| !rcard.member_ids.contains(u)) {
+                ops.push(PendingOp { user_id: Some(user.clone()), ..op(PendingOpKind::AddMember, Some(cid.clone()), None, None, None) });
+            }
+            for user in rcard.member_ids.iter().filter(|u| !t.members.contains(u)) {
+                ops.push(PendingOp { user_id: Some(user.clone()), ..op(PendingOpKind::RemoveMember, Some(cid.clone()), None, None, None) });
+            }
+        }
+        for op in ops {
+            self.enqueue_op(op);
+        }
+    }
+
+    pub fn sync_current_project_from_planka(&mut self) {
+        self.start_sync(Some(self.current_project.clone()), false);
+    }
+
+    pub fn sync_all_projects_from_planka(&mut self) {
+        self.start_sync(None, true);
+    }
+
+    fn start_sync(&mut self, only: Option<String>, push: bool) {
+        let boards = self.planka_boards.clone();
+        let lists = self.planka_lists_by_board.clone();
+        let local = self.stale_candidates();
+        self.error_message = Some("Syncing with Planka…".to_string());
+        self.spawn_job(
+            move |client| Ok(Self::pull_remote(client, only, boards, lists, local)),
+            move |app, res| match res {
+                Ok(pull) => app.apply_pull(pull, push),
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
+    }
+
+    pub fn webhook_enabled(&self) -> bool {
+        self.planka_config.as_ref().and_then(|c| c.webhook.as_ref()).is_some_and(|w| w.id.is_some())
+    }
+
+    // Register a Planka webhook pointing at the local receiver, or remove it again.
+    // The listen address, public URL and token are kept in planka.json across toggles.
+    pub fn toggle_webhook(&mut self) {
+        let Some(cfg) = self.planka_config.as_ref() else {
+            self.error_message = Some("Planka config not set. Press 'L' to login/setup.".to_string());
+            return;
+        };
+        let hook = cfg.webhook.clone().unwrap_or_else(|| WebhookConfig {
+            listen: webhook::DEFAULT_LISTEN.to_string(),
+            url: None,
+            access_token: new_id(),
+            id: None,
+        });
+        self.error_message = Some(if hook.id.is_some() { "Removing webhook…" } else { "Registering webhook…" }.to_string());
+        self.input_mode = InputMode::Normal;
+        self.spawn_job(
+            move |client| match hook.id.clone() {
+                Some(id) => match client.delete_webhook(&id) {
+                    // Already deleted on Planka
+                    Ok(()) | Err(PlankaError::NotFound(_)) => Ok(WebhookConfig { id: None, ..hook }),
+                    Err(e) => Err(e),
+                },
+                None => {
+                    let id = client.create_webhook("Planky", &hook.target_url(), Some(&hook.access_token), Some(webhook::EVENTS), None)?;
+                    Ok(WebhookConfig { id: Some(id), ..hook })
+                }
+            },
+            |app, res| match res {
+                Ok(hook) => {
+                    app.error_message = Some(match hook.id {
+                        Some(_) => format!("Webhook registered: Planka now posts to {}", hook.target_url()),
+                        None => "Webhook removed".to_string(),
+                    });
+                    if let Some(ref mut cfg) = app.planka_config {
+                        cfg.webhook = Some(hook);
+                        if let Err(e) = planka::save_config(cfg) {
+                            app.error_message = Some(e);
+                        }
+                    }
+                    // Start or stop the receiver
+                    app.start_background_sync();
+                }
+                Err(e) => app.error_message = Some(format!("Webhook change failed: {}", e)),
+            },
+        );
+    }
+
+    // Load the current board's lists into the workflow editor
+    pub fn open_workflow(&mut self) {
+        let board = self.current_project.clone();
+        let Some(ref cfg) = self.planka_config else {
+            self.error_message = Some("Log in to Planka first (L).".to_string());
+            return;
+        };
+        if !self.planka_boards.iter().any(|b| b.id == board) {
+            self.error_message = Some("The current project is not a Planka board.".to_string());
+            return;
+        }
+        let saved = cfg.workflows.get(&board).cloned();
+        self.error_message = Some("Loading lists…".to_string());
+        self.spawn_job(
+            move |client| client.fetch_board_lists(&board),
+            move |app, res| {
+                let lists = match res {
+                    Ok(l) => l,
+                    Err(e) => {
+                        app.error_message = Some(e.to_string());
+                        return;
+                    }
+                };
+                let stages = saved.or_else(|| planka::guess_workflow(&lists)).unwrap_or_default();
+                let mut rows: Vec<(WorkflowStage, bool)> = stages
+                    .into_iter()
+                    .filter_map(|s| lists.iter().find(|(id, _)| *id == s.list_id).map(|(_, name)| (WorkflowStage { name: name.clone(), ..s }, true)))
+                    .collect();
+                for (id, name) in &lists {
+                    if !rows.iter().any(|(s, _)| s.list_id == *id) {
+                        rows.push((WorkflowStage { name: name.clone(), list_id: id.clone() }, false));
+                    }
+                }
+                app.workflow_rows = rows;
+                app.workflow_index = 0;
+                app.input_mode = InputMode::EditingWorkflow;
+                app.error_message = None;
+            },
+        );
+    }
+
+    pub fn toggle_workflow_row(&mut self) {
+        if let Some(row) = self.workflow_rows.get_mut(self.workflow_index) {
+            row.1 = !row.1;
+        }
+    }
+
+    // Shift the selected list up (-1) or down (+1) in the workflow
+    pub fn move_workflow_row(&mut self, step: isize) {
+        let i = self.workflow_index;
+        let Some(j) = i.checked_add_signed(step).filter(|j| *j < self.workflow_rows.len()) else { return; };
+        self.workflow_rows.swap(i, j);
+        self.workflow_index = j;
+    }
+
+    // Store the edited workflow (None: back to guessing from list names) and re-read the board with it
+    pub fn save_workflow(&mut self, custom: bool) -> Result<(), String> {
+        let board = self.current_project.clone();
+        let stages: Vec<WorkflowStage> = self.workflow_rows.iter().filter(|(_, on)| *on).map(|(s, _)| s.clone()).collect();
+        if custom && stages.len() < 2 {
+            return Err("A workflow needs at least two lists (first = new, last = done).".to_string());
+        }
+        let Some(ref mut cfg) = self.planka_config else { return Ok(()); };
+        if custom {
+            cfg.workflows.insert(board.clone(), stages);
+        } else {
+            cfg.workflows.remove(&board);
+        }
+        planka::save_config(cfg)?;
+        self.planka_lists_by_board.remove(&board);
+        self.planka_lists = None;
+        self.workflow_rows.clear();
+        self.input_mode = InputMode::Normal;
+        // The sync thread keeps its own copy of the config and list layout
+        self.start_background_sync();
+        self.start_sync(Some(board), false);
+        self.error_message = Some("Workflow saved".to_string());
+        Ok(())
+    }
+
+    pub fn open_profiles(&mut self) {
+        let active = profile::active();
+        self.profiles = profile::list()
+            .into_iter()
+            .map(|name| {
+                let summary = planka::load_config_from(&profile::dir_of(&name).join("planka.json"))
+                    .map(|c| format!("{} @ {}", c.email_or_username, c.server_url))
+                    .unwrap_or_else(|| "not logged in".to_string());
+                (name, summary)
+            })
+            .collect();
+        self.profile_index = self.profiles.iter().position(|(p, _)| *p == active).unwrap_or(0);
+        self.input_profile.clear();
+        self.input_mode = InputMode::SwitchingProfile;
+        self.error_message = None;
+    }
+
+    pub fn switch_to_selected_profile(&mut self) {
+        if let Some((name, _)) = self.profiles.get(self.profile_index).cloned() {
+            self.switch_profile(&name);
+        }
+    }
+
+    pub fn submit_new_profile(&mut self) -> Result<(), String> {
+        let name = self.input_profile.trim().to_string();
+        if !profile::is_valid_name(&name) {
+            return Err("Profile names may use letters, digits, - and _".to_string());
+        }
+        self.input_profile.clear();
+        self.switch_profile(&name);
+        if self.planka_config.is_none() {
+            self.start_planka_setup();
+        }
+        Ok(())
+    }
+
+    // Save everything under the current profile, then reload the app from the
+    // other one. The worker and the sync thread are replaced too, so nothing
+    // still running against the old server lands in the new profile.
+    pub fn switch_profile(&mut self, name: &str) {
+        if name == profile::active() {
+            self.input_mode = InputMode::Normal;
+            return;
+        }
+        if let Err(e) = self.save_to_file(get_data_file_path()) {
+            self.error_message = Some(e);
+            return;
+        }
+        self.save_pending_ops();
+        self.sync_stop.store(true, Ordering::Relaxed);
+        profile::set_active(name);
+        *self = App::load_from_file(get_data_file_path());
+        self.start_background_sync();
+        self.ensure_lists();
+        self.error_message = Some(format!("Switched to profile '{}'", name));
+    }
+
+    pub fn start_planka_setup(&mut self) {
+        self.planka_setup = Some(PlankaSetupStep::Url);
+        self.input_planka = self
+            .planka_config
+            .as_ref()
+            .map(|c| c.server_url.clone())
+            .unwrap_or_default();
+        self.input_mode = InputMode::EditingPlanka;
+        self.error_message = None;
+    }
+
+    // The server rejected the token and no saved password could renew it: ask for one
+    pub fn prompt_relogin(&mut self) {
+        if self.planka_setup.is_some() {
+            return;
+        }
+        let sso = self.planka_config.as_ref().is_some_and(|c| c.sso);
+        if sso || !matches!(self.input_mode, InputMode::Normal) {
+            self.error_message = Some("Planka session expired. Press 'L' to log in again.".to_string());
+            return;
+        }
+        self.planka_setup = Some(PlankaSetupStep::Password);
+        self.input_planka.clear();
+        self.input_mode = InputMode::EditingPlanka;
+        self.error_message = Some("Planka session expired. Enter your password to log in again.".to_string());
+    }
+
+    pub fn submit_planka_setup(&mut self) {
+        let step = match self.planka_setup {
+            Some(s) => s,
+            None => return,
+        };
+        let mut cfg = self.planka_config.clone().unwrap_or_default();
+        match step {
             PlankaSetupStep::Url => {
                 cfg.server_url = self.input_planka.trim().to_string();
                 self.input_planka.clear();
                 // persist partial config so next step sees server_url
                 self.planka_config = Some(cfg.clone());
-                self.planka_setup = Some(PlankaSetupStep::Username);
-                // optional: prefill username if already present
-                if let Some(existing) = self.planka_config.as_ref() {
-                    if !existing.email_or_username.is_empty() {
-                        self.input_planka = existing.email_or_username.clone();
-                    }
-                }
+                // The input comes back once we know whether the server offers SSO
+                self.planka_setup = None;
+                self.input_mode = InputMode::Normal;
+                self.error_message = Some("Checking server…".to_string());
+                self.spawn_anonymous_job(
+                    cfg.server_url.clone(),
+                    |client| client.fetch_server_config(),
+                    |app, res| {
+                        // Without a usable answer the password login is tried; its errors say more
+                        app.oidc = res.ok().and_then(|c| c.oidc).filter(|o| !o.authorization_url.is_empty());
+                        app.error_message = None;
+                        if app.oidc.as_ref().is_some_and(|o| o.is_enforced) {
+                            app.start_sso();
+                            return;
+                        }
+                        app.planka_setup = Some(PlankaSetupStep::Username);
+                        app.input_mode = InputMode::EditingPlanka;
+                        // optional: prefill username if already present
+                        if let Some(existing) = app.planka_config.as_ref()
+                            && !existing.email_or_username.is_empty()
+                            && !existing.sso
+                        {
+                            app.input_planka = existing.email_or_username.clone();
+                        }
+                    },
+                );
             }
             PlankaSetupStep::Username => {
+                if self.input_planka.trim().is_empty() && self.oidc.is_some() {
+                    self.start_sso();
+                    return;
+                }
                 cfg.email_or_username = self.input_planka.trim().to_string();
                 self.input_planka.clear();
                 // persist partial config so next step sees server_url + username
@@ -862,38 +2357,231 @@ impl App {
             }
             PlankaSetupStep::Password => {
                 cfg.password = self.input_planka.clone();
+                // Always log in fresh; the stored token may be the one that expired.
+                // Logging in also replaces a configured API key.
+                cfg.token = None;
+                cfg.http_only_token = None;
+                cfg.api_key = None;
+                cfg.sso = false;
                 self.input_planka.clear();
-                match PlankaClient::from_config(cfg.clone()) {
-                    Ok((_client, saved)) => {
-                        self.planka_config = Some(saved);
-                        let _ = planka::save_config(self.planka_config.as_ref().unwrap());
-                        self.error_message = Some("Planka login successful".to_string());
-                        // Populate projects from Planka boards now.
-                        self.sync_current_project_from_planka();
-                    }
-                    Err(e) => {
-                        self.error_message = Some(format!("Planka login failed: {}", e));
-                    }
-                }
                 self.planka_setup = None;
                 self.input_mode = InputMode::Normal;
+                self.error_message = Some("Logging in…".to_string());
+                // The worker logs in while building its client; only the token
+                // (and an HTTP-only session cookie) comes back
+                self.spawn_job_with(
+                    cfg.clone(),
+                    |client| Ok((client.token(), client.http_only_token())),
+                    move |app, res| match res {
+                        Ok((token, cookie)) => app.complete_login(cfg, token, cookie),
+                        Err(PlankaError::Unauthorized(_)) => {
+                            app.error_message = Some("Planka login failed: wrong username or password".to_string());
+                        }
+                        Err(e) => app.login_failed(cfg, e),
+                    },
+                );
+            }
+            PlankaSetupStep::Sso => {
+                let input = std::mem::take(&mut self.input_planka);
+                let Some(ref sso) = self.sso else { return; };
+                // Nothing typed: show the sign-in page again
+                if input.trim().is_empty() {
+                    self.error_message = oidc::open_browser(&sso.url).err().map(|e| format!("{}; open the sign-in page yourself", e));
+                    return;
+                }
+                match oidc::code_from_redirect(&input, &sso.state) {
+                    Ok(code) => self.finish_sso(code),
+                    Err(e) => self.error_message = Some(e),
+                }
             }
         }
     }
 
+    pub fn cancel_planka_setup(&mut self) {
+        self.planka_setup = None;
+        self.input_planka.clear();
+        self.input_mode = InputMode::Normal;
+        self.sso = None;
+    }
+
+    // A token was issued: keep it (with the password, for password logins) and pull
+    fn complete_login(&mut self, mut cfg: PlankaConfig, token: String, http_only_token: Option<String>) {
+        cfg.token = Some(token);
+        cfg.http_only_token = http_only_token;
+        cfg.api_key = None;
+        let stored = if cfg.sso { Ok(()) } else { secrets::store_password(&cfg, &cfg.password) };
+        let sso = cfg.sso;
+        let _ = planka::save_config(&cfg);
+        self.planka_config = Some(cfg);
+        self.error_message = Some(match stored {
+            Ok(()) => "Planka login successful".to_string(),
+            Err(e) => format!("Planka login successful, but the password wasn't saved: {}", e),
+        });
+        // Ops that failed on the old token can go right away
+        for op in self.pending_ops.iter_mut() {
+            op.retry.next_at = 0;
+        }
+        // Populate projects from Planka boards now.
+        self.sync_current_project_from_planka();
+        if sso {
+            // Name the account in the config, for the profile list
+            self.spawn_job(
+                |client| client.fetch_user("me"),
+                |app, res| {
+                    if let (Ok(me), Some(cfg)) = (res, app.planka_config.as_mut()) {
+                        cfg.email_or_username = me.username.or(me.email).unwrap_or(me.name);
+                        let _ = planka::save_config(cfg);
+                    }
+                },
+            );
+        }
+    }
+
+    fn login_failed(&mut self, cfg: PlankaConfig, e: PlankaError) {
+        match e {
+            PlankaError::TermsRequired { pending_token, terms_type } => self.open_terms(cfg, pending_token, terms_type),
+            e => self.error_message = Some(format!("Planka login failed: {}", e)),
+        }
+    }
+
+    // Show the terms the server wants accepted before it issues a token
+    fn open_terms(&mut self, cfg: PlankaConfig, pending_token: String, terms_type: String) {
+        self.planka_setup = None;
+        self.input_planka.clear();
+        self.terms = Some(PendingTerms { cfg: cfg.clone(), pending_token, terms: None, scroll: 0 });
+        self.input_mode = InputMode::AcceptingTerms;
+        self.error_message = Some("Loading the terms of service…".to_string());
+        self.spawn_anonymous_job(
+            cfg.server_url,
+            move |client| client.fetch_terms(&terms_type, None),
+            |app, res| match res {
+                Ok(t) => {
+                    if let Some(ref mut p) = app.terms {
+                        p.terms = Some(t);
+                    }
+                    app.error_message = None;
+                }
+                Err(e) => app.error_message = Some(format!("Couldn't load the terms: {}", e)),
+            },
+        );
+    }
+
+    pub fn scroll_terms(&mut self, step: i32) {
+        if let Some(ref mut p) = self.terms {
+            let lines = p.terms.as_ref().map(|t| t.content.lines().count()).unwrap_or(0) as i32;
+            p.scroll = (p.scroll as i32 + step).clamp(0, lines.max(1) - 1) as u16;
+        }
+    }
+
+    // Accept the shown terms: the pending token is traded for a real one
+    pub fn accept_terms(&mut self) {
+        let Some(p) = self.terms.as_ref() else { return; };
+        let Some(signature) = p.terms.as_ref().map(|t| t.signature.clone()) else {
+            self.error_message = Some("The terms are still loading".to_string());
+            return;
+        };
+        let Some(p) = self.terms.take() else { return; };
+        self.input_mode = InputMode::Normal;
+        self.error_message = Some("Logging in…".to_string());
+        let pending = p.pending_token;
+        let cfg = p.cfg;
+        self.spawn_anonymous_job(
+            cfg.server_url.clone(),
+            move |client| client.accept_terms(&pending, &signature).map(|t| (t, client.http_only_token())),
+            move |app, res| match res {
+                Ok((token, cookie)) => app.complete_login(cfg, token, cookie),
+                Err(e) => app.error_message = Some(format!("Planka login failed: {}", e)),
+            },
+        );
+    }
+
+    // Decline: the pending token is revoked and no login happens
+    pub fn decline_terms(&mut self) {
+        let Some(p) = self.terms.take() else { return; };
+        self.input_mode = InputMode::Normal;
+        self.error_message = Some("Terms declined; not logged in".to_string());
+        let pending = p.pending_token;
+        self.spawn_anonymous_job(
+            p.cfg.server_url,
+            move |client| client.revoke_pending_token(&pending),
+            |app, res| {
+                if let Err(e) = res {
+                    app.error_message = Some(format!("Terms declined, but the pending login wasn't revoked: {}", e));
+                }
+            },
+        );
+    }
+
+    // Open the server's SSO page in the browser; the user pastes back the URL it ends on
+    fn start_sso(&mut self) {
+        let Some(ref o) = self.oidc else { return; };
+        let sso = match oidc::SsoLogin::start(&o.authorization_url) {
+            Ok(s) => s,
+            Err(e) => {
+                self.error_message = Some(e);
+                return;
+            }
+        };
+        self.error_message = Some(match oidc::open_browser(&sso.url) {
+            Ok(()) => "Sign in in your browser, then paste the URL it ends on here".to_string(),
+            Err(e) => format!("{}; open the sign-in page yourself", e),
+        });
+        self.sso = Some(sso);
+        self.input_planka.clear();
+        self.planka_setup = Some(PlankaSetupStep::Sso);
+        self.input_mode = InputMode::EditingPlanka;
+    }
+
+    pub fn sso_url(&self) -> Option<&str> {
+        self.sso.as_ref().map(|s| s.url.as_str())
+    }
+
+    fn finish_sso(&mut self, code: String) {
+        let Some(sso) = self.sso.take() else { return; };
+        let nonce = sso.nonce.clone();
+        let mut cfg = self.planka_config.clone().unwrap_or_default();
+        let http_only = cfg.with_http_only_token.then_some(true);
+        self.planka_setup = None;
+        self.input_planka.clear();
+        self.input_mode = InputMode::Normal;
+        self.error_message = Some("Signing in…".to_string());
+        self.spawn_anonymous_job(
+            cfg.server_url.clone(),
+            move |client| client.exchange_with_oidc(&code, &nonce, http_only).map(|t| (t, client.http_only_token())),
+            move |app, res| {
+                cfg.sso = true;
+                cfg.password.clear();
+                match res {
+                    Ok((token, cookie)) => app.complete_login(cfg, token, cookie),
+                    Err(e) => app.login_failed(cfg, e),
+                }
+            },
+        );
+    }
+
     pub fn begin_create_board(&mut self) {
         self.input_board.clear();
         self.error_message = None;
+        self.input_mode = InputMode::CreatingBoard;
 
         // Ensure boards cache is present
-        if self.planka_boards.is_empty() {
-            if let Ok(client) = self.ensure_planka_client() {
-                if let Ok(boards) = client.fetch_boards() {
-                    self.planka_boards = boards;
-                }
-            }
+        if self.planka_boards.is_empty() && self.planka_config.is_some() {
+            self.spawn_job(
+                |client| client.fetch_boards(),
+                |app, res| {
+                    if let Ok(boards) = res {
+                        app.planka_boards = boards;
+                    }
+                    if app.input_mode == InputMode::CreatingBoard {
+                        app.fill_create_board_projects();
+                    }
+                },
+            );
         }
+        self.fill_create_board_projects();
+    }
 
+    fn fill_create_board_projects(&mut self) {
         // Build unique project list from boards
         let mut seen: HashSet<String> = HashSet::new();
         let mut projects: Vec<(String, String)> = Vec::new();
@@ -913,7 +2601,7 @@ impl App {
         if let Some(cur_pid) = self
             .planka_boards
             .iter()
-            .find(|b| b.name == self.current_project)
+            .find(|b| b.id == self.current_project)
             .and_then(|b| b.project_id.clone())
         {
             if let Some(pos) = projects.iter().position(|(id, _)| *id == cur_pid) {
@@ -923,8 +2611,28 @@ impl App {
 
         self.create_board_projects = projects;
         self.create_board_project_index = sel;
+    }
 
-        self.input_mode = InputMode::CreatingBoard;
+    // Replace the board cache after a create and switch to the new board
+    fn show_created_board(&mut self, res: Result<(String, Vec<PlankaBoard>), PlankaError>, name: String, msg: &str) {
+        match res {
+            Ok((board_id, boards)) => {
+                self.project_names.insert(board_id.clone(), name);
+                if !boards.is_empty() {
+                    self.adopt_boards(&boards);
+                    self.projects = boards.iter().map(|b| b.id.clone()).collect();
+                    self.planka_boards = boards;
+                }
+                if !self.projects.contains(&board_id) {
+                    self.projects.push(board_id.clone());
+                }
+                self.current_project = board_id;
+                self.selected = 0;
+                self.ensure_lists();
+                self.error_message = Some(msg.to_string());
+            }
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
     }
 
     pub fn submit_create_board(&mut self) -> Result<(), String> {
@@ -932,7 +2640,6 @@ impl App {
         if name.is_empty() {
             return Err("Board name cannot be empty.".to_string());
         }
-        let client = self.ensure_planka_client()?;
         let proj_id = if let Some((id, _name)) = self
             .create_board_projects
             .get(self.create_board_project_index)
@@ -941,20 +2648,21 @@ impl App {
         } else {
             self.planka_boards
                 .iter()
-                .find(|b| b.name == self.current_project)
+                .find(|b| b.id == self.current_project)
                 .and_then(|b| b.project_id.clone())
                 .ok_or_else(|| "No project selected and current board not found on Planka; sync first.".to_string())?
         };
-        let _bid = client.create_board(&proj_id, &name)?;
-        if let Ok(boards) = client.fetch_boards() {
-            self.planka_boards = boards.clone();
-            self.projects = boards.iter().map(|b| b.name.clone()).collect();
-        }
-        self.current_project = name;
-        self.selected = 0;
+        let board = name.clone();
+        self.spawn_job(
+            move |client| {
+                let bid = client.create_board(&proj_id, &board)?;
+                Ok((bid, client.fetch_boards().unwrap_or_default()))
+            },
+            move |app, res| app.show_created_board(res, name, "Board created"),
+        );
         self.input_board.clear();
         self.input_mode = InputMode::Normal;
-        self.error_message = Some("Board created".to_string());
+        self.error_message = Some("Creating board…".to_string());
         self.create_board_projects.clear();
         self.create_board_project_index = 0;
         Ok(())
@@ -971,20 +2679,20 @@ impl App {
         if name.is_empty() {
             return Err("Project name cannot be empty.".to_string());
         }
-        let client = self.ensure_planka_client()?;
-        let pid = client.create_project(&name)?;
         // Create a first board in the new project
         let first_board = "Main".to_string();
-        let _bid = client.create_board(&pid, &first_board)?;
-        if let Ok(boards) = client.fetch_boards() {
-            self.planka_boards = boards.clone();
-            self.projects = boards.iter().map(|b| b.name.clone()).collect();
-        }
-        self.current_project = first_board;
-        self.selected = 0;
+        let board = first_board.clone();
+        self.spawn_job(
+            move |client| {
+                let pid = client.create_project(&name)?;
+                let bid = client.create_board(&pid, &board)?;
+                Ok((bid, client.fetch_boards().unwrap_or_default()))
+            },
+            move |app, res| app.show_created_board(res, first_board, "Project created"),
+        );
         self.input_project.clear();
         self.input_mode = InputMode::Normal;
-        self.error_message = Some("Project created".to_string());
+        self.error_message = Some("Creating project…".to_string());
         Ok(())
     }
 
@@ -1014,38 +2722,26 @@ impl App {
             t.description = self.input_description.clone();
             t.due_date = due_date_str.clone();
         }
+        // Cards not created yet pick the edit up once the create lands
         let card_id = self.todos[idx].planka_card_id.clone();
         if let Some(cid) = card_id {
-            if let Ok(client) = self.ensure_planka_client() {
-                if let Err(e) = client.update_card(&cid, Some(&self.input_description), due_date_str.as_deref()) {
-                    self.error_message = Some(format!("Planka update failed: {}", e));
-                    if let Some(t) = self.todos.get_mut(idx) {
-                        t.sync_dirty = true;
-                    }
-                    self.enqueue_op(PendingOp {
-                        kind: PendingOpKind::Update,
-                        project: self.current_project.clone(),
-                        card_id: Some(cid),
-                        list_id: None,
-                        name: Some(self.input_description.clone()),
-                        due: due_date_str.clone(),
-                        ts: Local::now().timestamp(),
-                    });
-                }
-            } else {
-                if let Some(t) = self.todos.get_mut(idx) {
-                    t.sync_dirty = true;
-                }
-                self.enqueue_op(PendingOp {
-                    kind: PendingOpKind::Update,
-                    project: self.current_project.clone(),
-                    card_id: Some(cid),
-                    list_id: None,
-                    name: Some(self.input_description.clone()),
-                    due: due_date_str.clone(),
-                    ts: Local::now().timestamp(),
-                });
+            if let Some(t) = self.todos.get_mut(idx) {
+                t.sync_dirty = true;
             }
+            self.enqueue_op(PendingOp {
+                id: new_id(),
+                kind: PendingOpKind::Update,
+                project: self.current_project.clone(),
+                local_id: Some(self.todos[idx].local_id.clone()),
+                card_id: Some(cid),
+                list_id: None,
+                name: Some(self.input_description.clone()),
+                due: due_date_str.clone(),
+                label_id: None,
+                user_id: None,
+                ts: Local::now().timestamp(),
+                retry: RetryState::default(),
+            });
         }
         // clear inputs and editing state
         self.input_description.clear();
@@ -1065,7 +2761,10 @@ impl App {
             Some(parse_due_date(&self.input_due_date)?)
         };
 
-        let mut todo = Todo {
+        // Added locally right away; on a board the create op makes the card in the background
+        let on_planka = self.on_planka(&self.current_project);
+        let todo = Todo {
+            local_id: new_id(),
             description: self.input_description.clone(),
             done: false,
             due_date: due_date_str.clone(),
@@ -1074,614 +2773,1824 @@ impl App {
             planka_card_id: None,
             planka_list_id: None,
             planka_board_id: None,
-            sync_dirty: false,
+            labels: Vec::new(),
+            members: Vec::new(),
+            archived: false,
+            base: None,
+            sync_dirty: on_planka,
         };
-        if let Ok(client) = self.ensure_planka_client() {
-            let lists_opt = if let Some(l) = self.planka_lists_by_board.get(&self.current_project).cloned() {
-                Some(l)
-            } else {
-                match client.resolve_lists(&self.current_project) {
-                    Ok(l) => {
-                        self.planka_lists_by_board.insert(self.current_project.clone(), l.clone());
-                        self.planka_lists = Some(l.clone());
-                        Some(l)
-                    }
-                    Err(e) => {
-                        self.error_message = Some(e);
-                        None
-                    }
-                }
-            };
-            if let Some(lists) = lists_opt {
-                match client.create_card(&lists.todo_list_id, &todo.description, due_date_str.as_deref()) {
-                    Ok(card_id) => {
-                        todo.planka_card_id = Some(card_id);
-                        todo.planka_list_id = Some(lists.todo_list_id.clone());
-                        todo.planka_board_id = Some(lists.board_id.clone());
-                    }
-                    Err(e) => {
-                        self.error_message = Some(format!("Planka create card failed: {}", e));
-                        // queue create
-                        todo.sync_dirty = true;
-                        self.enqueue_op(PendingOp {
-                            kind: PendingOpKind::Create,
-                            project: self.current_project.clone(),
-                            card_id: None,
-                            list_id: None,
-                            name: Some(self.input_description.clone()),
-                            due: due_date_str.clone(),
-                            ts: Local::now().timestamp(),
-                        });
-                    }
-                }
-            } else {
-                // no lists: queue create for later
-                todo.sync_dirty = true;
-                self.enqueue_op(PendingOp {
-                    kind: PendingOpKind::Create,
-                    project: self.current_project.clone(),
-                    card_id: None,
-                    list_id: None,
-                    name: Some(self.input_description.clone()),
-                    due: due_date_str.clone(),
-                    ts: Local::now().timestamp(),
-                });
-            }
-        } else {
-            // no client: queue create
-            todo.sync_dirty = true;
-            self.enqueue_op(PendingOp {
+        let local_id = todo.local_id.clone();
+        self.todos.push(todo);
+        if on_planka {
+            let op = PendingOp {
+                id: new_id(),
                 kind: PendingOpKind::Create,
                 project: self.current_project.clone(),
+                local_id: Some(local_id),
                 card_id: None,
                 list_id: None,
                 name: Some(self.input_description.clone()),
                 due: due_date_str.clone(),
+                label_id: None,
+                user_id: None,
                 ts: Local::now().timestamp(),
-            });
+                retry: RetryState::default(),
+            };
+            self.enqueue_op(op);
         }
-        self.todos.push(todo);
 
-        if !self.projects.iter().any(|p| p == &self.current_project) {
-            self.projects.push(self.current_project.clone());
+        if !self.projects.iter().any(|p| p == &self.current_project) {
+            self.projects.push(self.current_project.clone());
+        }
+
+        // clear inputs after adding
+        self.input_description.clear();
+        self.input_due_date.clear();
+        self.error_message = None;
+
+        Ok(())
+    }
+
+    pub fn delete_todo(&mut self) {
+        let Some(idx) = self.selected_index_in_all() else { return; };
+        let card_id = self.todos[idx].planka_card_id.clone();
+        let t = self.todos.remove(idx);
+        if let Some(cid) = card_id {
+            self.enqueue_op(PendingOp {
+                id: new_id(),
+                kind: PendingOpKind::Delete,
+                project: self.current_project.clone(),
+                local_id: Some(t.local_id),
+                card_id: Some(cid),
+                list_id: None,
+                name: None,
+                due: None,
+                label_id: None,
+                user_id: None,
+                ts: Local::now().timestamp(),
+                retry: RetryState::default(),
+            });
+        }
+        if self.selected > 0 {
+            self.selected -= 1;
+        }
+    }
+
+    // Toggle between the last workflow stage (done) and the first
+    pub fn mark_done(&mut self) {
+        let Some(idx) = self.selected_index_in_all() else { return; };
+        let (stage, count) = self.stage_of(&self.todos[idx]);
+        self.move_to_stage(idx, if stage + 1 == count { 0 } else { usize::MAX });
+    }
+
+    // Move the selected todo one workflow stage forward
+    pub fn advance(&mut self) {
+        let Some(idx) = self.selected_index_in_all() else { return; };
+        let (stage, count) = self.stage_of(&self.todos[idx]);
+        if stage + 1 < count {
+            self.move_to_stage(idx, stage + 1);
+        }
+    }
+
+    // Move the selected todo one workflow stage back
+    pub fn retreat(&mut self) {
+        let Some(idx) = self.selected_index_in_all() else { return; };
+        let (stage, _) = self.stage_of(&self.todos[idx]);
+        if stage > 0 {
+            self.move_to_stage(idx, stage - 1);
+        }
+    }
+
+    // Whether `project` is a Planka board rather than a local-only project
+    fn on_planka(&self, project: &str) -> bool {
+        self.planka_config.is_some()
+            && (self.project_names.contains_key(project)
+                || self.planka_lists_by_board.contains_key(project)
+                || self.planka_boards.iter().any(|b| b.id == project))
+    }
+
+    // `usize::MAX` stands for the last stage, whatever the board's workflow turns out to be
+    fn move_to_stage(&mut self, idx: usize, stage: usize) {
+        let local_id = self.todos[idx].local_id.clone();
+        let project = self.todos[idx].project.clone();
+        // Local-only projects just flip the done flag
+        if !self.on_planka(&project) {
+            self.todos[idx].done = stage > 0;
+            return;
+        }
+        self.with_lists(project.clone(), move |app, lists| {
+            let stage = stage.min(lists.stages.len() - 1);
+            let target = lists.stages[stage].list_id.clone();
+            let Some(t) = app.todos.iter_mut().find(|t| t.local_id == local_id) else { return; };
+            t.done = stage + 1 == lists.stages.len();
+            t.planka_list_id = Some(target.clone());
+            t.sync_dirty = true;
+            // No remote id: reflect locally; the queued create moves it once the card exists
+            let Some(cid) = t.planka_card_id.clone() else { return; };
+            app.enqueue_op(PendingOp {
+                id: new_id(),
+                kind: PendingOpKind::Move,
+                project,
+                local_id: Some(local_id),
+                card_id: Some(cid),
+                list_id: Some(target),
+                name: None,
+                due: None,
+                label_id: None,
+                user_id: None,
+                ts: Local::now().timestamp(),
+                retry: RetryState::default(),
+            });
+        });
+    }
+
+    pub fn next_project(&mut self) {
+        if self.projects.is_empty() {
+            self.projects = default_projects();
+        }
+        if let Some(pos) = self.projects.iter().position(|p| p == &self.current_project) {
+            let next = (pos + 1) % self.projects.len();
+            self.current_project = self.projects[next].clone();
+        } else {
+            self.current_project = self.projects[0].clone();
+        }
+        self.label_filter = None;
+        self.ensure_lists();
+        self.kanban.clear();
+        self.refresh_kanban();
+    }
+    pub fn prev_project(&mut self) {
+        if self.projects.is_empty() {
+            self.projects = default_projects();
+        }
+        if let Some(pos) = self.projects.iter().position(|p| p == &self.current_project) {
+            let prev = (pos + self.projects.len() - 1) % self.projects.len();
+            self.current_project = self.projects[prev].clone();
+        } else {
+            self.current_project = self.projects[0].clone();
+        }
+        self.label_filter = None;
+        self.ensure_lists();
+        self.kanban.clear();
+        self.refresh_kanban();
+    }
+    // Select a project by key, board name or "Project/Board"; unknown names start a local project
+    pub fn set_current_project<S: Into<String>>(&mut self, name: S) {
+        let name = name.into().trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let key = self.find_project(&name).unwrap_or_else(|| name.clone());
+        if !self.projects.iter().any(|p| p == &key) {
+            self.projects.push(key.clone());
+        }
+        self.current_project = key;
+        self.label_filter = None;
+        self.ensure_lists();
+        self.kanban.clear();
+        self.refresh_kanban();
+    }
+
+    pub fn find_project(&self, query: &str) -> Option<String> {
+        if self.projects.iter().any(|p| p == query) {
+            return Some(query.to_string());
+        }
+        let q = query.to_lowercase();
+        let qualified = |key: &String| {
+            self.planka_boards
+                .iter()
+                .find(|b| &b.id == key)
+                .and_then(|b| b.project_name.as_ref())
+                .map(|pn| format!("{}/{}", pn, self.project_name(key)).to_lowercase() == q)
+                .unwrap_or(false)
+        };
+        self.projects
+            .iter()
+            .find(|key| qualified(key))
+            .or_else(|| self.projects.iter().find(|key| self.project_name(key).to_lowercase() == q))
+            .cloned()
+    }
+
+    // Board name for a project key
+    pub fn project_name(&self, key: &str) -> String {
+        self.project_names.get(key).cloned().unwrap_or_else(|| key.to_string())
+    }
+
+    // Record board names and move projects still keyed by a board name over
+    // to that board's id, when the name is unambiguous
+    pub fn adopt_boards(&mut self, boards: &[PlankaBoard]) {
+        for b in boards {
+            self.project_names.insert(b.id.clone(), b.name.clone());
+        }
+        let mut keys: Vec<String> = self.todos.iter().map(|t| t.project.clone()).collect();
+        keys.extend(self.projects.iter().cloned());
+        keys.push(self.current_project.clone());
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            if boards.iter().any(|b| b.id == key) {
+                continue;
+            }
+            let mut matching = boards.iter().filter(|b| b.name.eq_ignore_ascii_case(&key));
+            if let (Some(b), None) = (matching.next(), matching.next()) {
+                let id = b.id.clone();
+                self.rekey_project(&key, &id);
+            }
+        }
+    }
+
+    fn rekey_project(&mut self, old: &str, new: &str) {
+        for t in self.todos.iter_mut().filter(|t| t.project == old) {
+            t.project = new.to_string();
+        }
+        for op in self.pending_ops.iter_mut().chain(self.dead_ops.iter_mut()).filter(|op| op.project == old) {
+            op.project = new.to_string();
+        }
+        for c in self.conflicts.iter_mut().filter(|c| c.project == old) {
+            c.project = new.to_string();
+        }
+        if let Some(lists) = self.planka_lists_by_board.remove(old) {
+            self.planka_lists_by_board.insert(new.to_string(), lists);
+        }
+        self.projects.retain(|p| p != new);
+        for p in self.projects.iter_mut().filter(|p| *p == old) {
+            *p = new.to_string();
+        }
+        if self.current_project == old {
+            self.current_project = new.to_string();
+        }
+        self.project_names.entry(new.to_string()).or_insert_with(|| old.to_string());
+        self.save_pending_ops();
+    }
+
+    // todos.json files from before board ids: todos that know their board
+    // take their project's key along (name collisions stay apart, since each
+    // linked todo moves by its own board id)
+    fn migrate_project_keys(&mut self) {
+        let mut ids_by_name: HashMap<String, HashSet<String>> = HashMap::new();
+        for t in self.todos.iter_mut() {
+            if let Some(ref bid) = t.planka_board_id
+                && t.project != *bid
+                && !t.project.is_empty()
+            {
+                ids_by_name.entry(t.project.clone()).or_default().insert(bid.clone());
+                self.project_names.entry(bid.clone()).or_insert_with(|| t.project.clone());
+                t.project = bid.clone();
+            }
+        }
+        for (name, ids) in ids_by_name {
+            if ids.len() == 1
+                && let Some(id) = ids.into_iter().next()
+            {
+                self.rekey_project(&name, &id);
+            }
+        }
+    }
+
+    pub fn refresh_projects_from_todos(&mut self) {
+        let mut uniq: Vec<String> = self
+            .todos
+            .iter()
+            .map(|t| t.project.clone())
+            .filter(|p| !p.is_empty())
+            .collect();
+        uniq.sort_by_key(|p| self.project_name(p).to_lowercase());
+        uniq.dedup();
+        if uniq.is_empty() {
+            uniq = default_projects();
+        }
+        self.projects = uniq;
+        if !self.projects.iter().any(|p| p == &self.current_project) {
+            self.current_project = self.projects[0].clone();
+        }
+    }
+
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let file = secrets::open_private(path.as_ref(), false)
+            .map_err(|e| format!("Failed to open file: {}", e))?;
+
+        let writer = BufWriter::new(file);
+
+        serde_json::to_writer_pretty(writer, self)
+            .map_err(|e| format!("Failed to write JSON!: {}", e))
+    }
+
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Self {
+        let file = File::open(&path);
+        if let Ok(file) = file {
+            let reader = BufReader::new(file);
+            {
+                let mut app: App =
+                    serde_json::from_reader(reader).unwrap_or_else(|_| App::new());
+                // Re-load Planka config each run (it’s not persisted in todos.json)
+                app.planka_config = planka::load_config();
+                // Same for the outbound queue, which lives in its own files
+                app.pending_ops = Self::load_ops(Self::pending_ops_path());
+                app.dead_ops = Self::load_ops(Self::dead_ops_path());
+                app.migrate_project_keys();
+                app.refresh_projects_from_todos();
+                app
+            }
+        } else {
+            App::new()
+        }
+    }
+
+    pub fn toggle_board_view(&mut self) {
+        self.board_view = !self.board_view;
+        self.kanban.clear();
+        if self.board_view {
+            self.kanban_col = 0;
+            self.kanban_row = 0;
+            self.refresh_kanban();
+        }
+    }
+
+    // Reload the board's columns in the background (no-op while one load runs)
+    pub fn refresh_kanban(&mut self) {
+        if !self.board_view || self.kanban_loading || self.planka_config.is_none() {
+            return;
+        }
+        let board = self.current_project.clone();
+        if !self.planka_boards.iter().any(|b| b.id == board) && !self.planka_lists_by_board.contains_key(&board) {
+            self.kanban.clear();
+            return;
+        }
+        self.kanban_loading = true;
+        self.spawn_job(
+            move |client| {
+                let details = client.fetch_board_details(&board)?;
+                let mut columns = Vec::with_capacity(details.lists.len());
+                for (id, name) in &details.lists {
+                    let mut list = client.fetch_list_details(id)?;
+                    if list.name.is_empty() {
+                        list.name = name.clone();
+                    }
+                    columns.push(list);
+                }
+                Ok((board, columns))
+            },
+            |app, res| {
+                app.kanban_loading = false;
+                match res {
+                    Ok((board, columns)) if board == app.current_project => {
+                        // Keep the cursor on the same card when it is still there
+                        let keep = app.kanban_card().map(|c| c.id.clone());
+                        app.kanban = columns;
+                        let found = keep.and_then(|id| {
+                            app.kanban.iter().enumerate().find_map(|(ci, col)| col.cards.iter().position(|c| c.id == id).map(|ri| (ci, ri)))
+                        });
+                        if let Some((ci, ri)) = found {
+                            app.kanban_col = ci;
+                            app.kanban_row = ri;
+                        }
+                        app.clamp_kanban_cursor();
+                    }
+                    // The user switched projects meanwhile
+                    Ok(_) => app.refresh_kanban(),
+                    Err(e) => app.error_message = Some(e.to_string()),
+                }
+            },
+        );
+    }
+
+    // Remote state of the current board changed: reload the columns if they are shown
+    fn kanban_stale(&mut self, project: &str) {
+        if self.board_view && project == self.current_project {
+            self.refresh_kanban();
+        }
+    }
+
+    fn clamp_kanban_cursor(&mut self) {
+        self.kanban_col = self.kanban_col.min(self.kanban.len().saturating_sub(1));
+        let cards = self.kanban.get(self.kanban_col).map(|c| c.cards.len()).unwrap_or(0);
+        self.kanban_row = self.kanban_row.min(cards.saturating_sub(1));
+    }
+
+    pub fn kanban_card(&self) -> Option<&PlankaCard> {
+        self.kanban.get(self.kanban_col)?.cards.get(self.kanban_row)
+    }
+
+    pub fn kanban_step_column(&mut self, step: isize) {
+        if let Some(c) = self.kanban_col.checked_add_signed(step).filter(|c| *c < self.kanban.len()) {
+            self.kanban_col = c;
+            self.clamp_kanban_cursor();
+        }
+    }
+
+    pub fn kanban_step_row(&mut self, step: isize) {
+        if let Some(r) = self.kanban_row.checked_add_signed(step) {
+            self.kanban_row = r;
+            self.clamp_kanban_cursor();
+        }
+    }
+
+    // Move the card under the cursor to the end of the neighbouring column
+    pub fn kanban_move_card(&mut self, step: isize) {
+        let from = self.kanban_col;
+        let Some(to) = from.checked_add_signed(step).filter(|c| *c < self.kanban.len()) else { return; };
+        if self.kanban_card().is_none() {
+            return;
+        }
+        let mut card = self.kanban[from].cards.remove(self.kanban_row);
+        card.position = self.kanban[to].cards.last().map(|c| c.position).unwrap_or(0.0) + 65536.0;
+        let list_id = self.kanban[to].id.clone();
+        self.kanban[to].cards.push(card.clone());
+        self.kanban_col = to;
+        self.kanban_row = self.kanban[to].cards.len() - 1;
+        self.send_kanban_move(card, list_id);
+    }
+
+    // Swap the card under the cursor with its neighbour above (-1) or below (+1)
+    pub fn kanban_reorder(&mut self, step: isize) {
+        let (col, i) = (self.kanban_col, self.kanban_row);
+        let Some(cards) = self.kanban.get_mut(col).map(|l| &mut l.cards) else { return; };
+        let Some(j) = i.checked_add_signed(step).filter(|j| *j < cards.len()) else { return; };
+        cards.swap(i, j);
+        // Land halfway between the new neighbours
+        let before = if j > 0 { cards[j - 1].position } else { 0.0 };
+        let after = cards.get(j + 1).map(|c| c.position).unwrap_or(before + 2.0 * 65536.0);
+        cards[j].position = (before + after) / 2.0;
+        let card = cards[j].clone();
+        let list_id = self.kanban[col].id.clone();
+        self.kanban_row = j;
+        self.send_kanban_move(card, list_id);
+    }
+
+    fn send_kanban_move(&mut self, card: PlankaCard, list_id: String) {
+        self.note_remote_move(&card.id, &list_id);
+        let project = self.current_project.clone();
+        self.spawn_job(
+            move |client| client.move_card_to(&card.id, &list_id, card.position),
+            move |app, res| {
+                if let Err(e) = res {
+                    app.error_message = Some(e.to_string());
+                    app.kanban_stale(&project);
+                }
+            },
+        );
+    }
+
+    // A card was moved on the board view: the linked todo follows, and since
+    // Planka gets the same move the new list is also the agreed base
+    fn note_remote_move(&mut self, card_id: &str, list_id: &str) {
+        let Some(t) = self.todos.iter_mut().find(|t| t.planka_card_id.as_deref() == Some(card_id)) else { return; };
+        let Some(lists) = self.planka_lists_by_board.get(&t.project) else { return; };
+        t.archived = !lists.contains(list_id);
+        t.done = lists.done_list_id() == list_id;
+        t.planka_list_id = Some(list_id.to_string());
+        if let Some(ref mut base) = t.base {
+            base.list_id = t.planka_list_id.clone();
+            base.done = t.done;
+        }
+    }
+
+    pub fn open_selected_card(&mut self) {
+        // The board view can open any card, tracked or not
+        let cid = match self.kanban_card() {
+            Some(c) if self.board_view => c.id.clone(),
+            _ => {
+                let Some(idx) = self.selected_index_in_all() else { return; };
+                match self.todos[idx].planka_card_id.as_deref() {
+                    Some(c) => c.to_string(),
+                    None => {
+                        self.error_message = Some("This task is not linked to a Planka card yet".to_string());
+                        return;
+                    }
+                }
+            }
+        };
+        self.open_card(cid);
+    }
+
+    fn open_card(&mut self, cid: String) {
+        self.error_message = Some("Loading card…".to_string());
+        self.spawn_job(
+            move |client| {
+                let details = client.fetch_card_details(&cid)?;
+                Ok((details, client.fetch_comments(&cid)))
+            },
+            |app, res| match res {
+                Ok((details, comments)) => {
+                    // Don't pull the user out of whatever they started meanwhile
+                    if !matches!(
+                        app.input_mode,
+                        InputMode::Normal | InputMode::ViewingMyCards | InputMode::ViewingNotifications | InputMode::ViewingBoardActivity
+                    ) {
+                        return;
+                    }
+                    app.view_return = app.input_mode;
+                    app.view_card = Some(details);
+                    app.card_actions.clear();
+                    app.card_actions_end = false;
+                    match comments {
+                        Ok(comments) => app.view_comments = comments,
+                        Err(e) => { app.view_comments = Vec::new(); app.error_message = Some(e.to_string()); }
+                    }
+                    app.view_scroll = 0;
+                    app.input_mode = InputMode::ViewingCard;
+                    app.view_card_tab = 0;
+                    app.error_message = None;
+                }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
+    }
+
+    pub fn close_view(&mut self) {
+        self.view_card = None;
+        self.view_scroll = 0;
+        self.input_mode = std::mem::take(&mut self.view_return);
+    }
+
+    // Run a change against the open card on the worker, then reload the card
+    // and its comments; `done_msg` is shown once both finished.
+    fn card_action<F>(&mut self, done_msg: Option<&'static str>, action: F)
+    where
+        F: FnOnce(&PlankaClient) -> Result<(), PlankaError> + Send + 'static,
+    {
+        let card_id = match self.view_card.as_ref() { Some(c) => c.id.clone(), None => return };
+        let id = card_id.clone();
+        self.spawn_job(
+            move |client| {
+                action(client)?;
+                Ok((client.fetch_card_details(&id).ok(), client.fetch_comments(&id).ok()))
+            },
+            move |app, res| match res {
+                Ok((details, comments)) => {
+                    // The card may have been closed or switched meanwhile
+                    if app.view_card.as_ref().map(|c| c.id.as_str()) == Some(card_id.as_str()) {
+                        if let Some(details) = details {
+                            app.view_card = Some(details);
+                        }
+                        if let Some(comments) = comments {
+                            app.view_comments = comments;
+                        }
+                    }
+                    if let Some(msg) = done_msg {
+                        app.error_message = Some(msg.to_string());
+                    }
+                }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
+    }
+
+    // Board whose labels the picker shows: the open card's, else the current project
+    fn label_project(&self) -> String {
+        self.view_card
+            .as_ref()
+            .filter(|_| self.input_mode != InputMode::FilteringLabel)
+            .and_then(|c| c.board_id.clone())
+            .unwrap_or_else(|| self.current_project.clone())
+    }
+
+    pub fn picker_labels(&self) -> &[PlankaLabel] {
+        self.board_labels.get(&self.label_project()).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+
+    pub fn label(&self, project: &str, id: &str) -> Option<&PlankaLabel> {
+        self.board_labels.get(project)?.iter().find(|l| l.id == id)
+    }
+
+    // Reflect a card's label change in the open card and on the board view
+    fn note_card_label(&mut self, card_id: &str, label_id: &str, added: bool) {
+        if let Some(card) = self.view_card.as_mut().filter(|c| c.id == card_id) {
+            toggle_id(&mut card.label_ids, label_id, added);
+            let project = card.board_id.clone().unwrap_or_else(|| self.current_project.clone());
+            let labels = self.board_labels.get(&project);
+            card.labels = card
+                .label_ids
+                .iter()
+                .filter_map(|id| labels?.iter().find(|l| &l.id == id).map(|l| l.name.clone()))
+                .collect();
+        }
+        for card in self.kanban.iter_mut().flat_map(|l| l.cards.iter_mut()).filter(|c| c.id == card_id) {
+            toggle_id(&mut card.label_ids, label_id, added);
+        }
+    }
+
+    // Reload a board's labels and members, e.g. for a picker about to open
+    fn refresh_labels(&mut self, project: String) {
+        // Local-only projects have no board to ask
+        if self.planka_config.is_none() || !self.project_names.contains_key(&project) {
+            return;
+        }
+        let board = project.clone();
+        self.spawn_job(
+            move |client| client.fetch_board_details(&board),
+            move |app, res| match res {
+                Ok(details) => {
+                    app.board_labels.insert(project.clone(), details.labels);
+                    app.board_members.insert(project, details.members);
+                    let n = app.picker_labels().len();
+                    if app.label_index >= n {
+                        app.label_index = n.saturating_sub(1);
+                    }
+                    let n = app.picker_members().len();
+                    if app.member_index >= n {
+                        app.member_index = n.saturating_sub(1);
+                    }
+                }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
+    }
+
+    pub fn open_label_picker(&mut self) {
+        if self.view_card.is_none() {
+            return;
+        }
+        self.input_mode = InputMode::PickingLabel;
+        self.label_index = 0;
+        self.error_message = None;
+        self.refresh_labels(self.label_project());
+    }
+
+    // Put the selected label on the open card or take it off; goes through
+    // the op queue like any other change
+    pub fn toggle_selected_label(&mut self) {
+        let Some(label) = self.picker_labels().get(self.label_index).cloned() else { return; };
+        let Some(card) = self.view_card.as_ref() else { return; };
+        let card_id = card.id.clone();
+        let added = !card.label_ids.contains(&label.id);
+        let mut project = self.label_project();
+        let mut local_id = None;
+        if let Some(t) = self.todos.iter_mut().find(|t| t.planka_card_id.as_deref() == Some(card_id.as_str())) {
+            toggle_id(&mut t.labels, &label.id, added);
+            t.sync_dirty = true;
+            local_id = Some(t.local_id.clone());
+            project = t.project.clone();
+        }
+        self.note_card_label(&card_id, &label.id, added);
+        self.enqueue_op(PendingOp {
+            id: new_id(),
+            kind: if added { PendingOpKind::AddLabel } else { PendingOpKind::RemoveLabel },
+            project,
+            local_id,
+            card_id: Some(card_id),
+            list_id: None,
+            name: None,
+            due: None,
+            label_id: Some(label.id),
+            user_id: None,
+            ts: Local::now().timestamp(),
+            retry: RetryState::default(),
+        });
+    }
+
+    pub fn begin_create_label(&mut self) {
+        self.input_label.clear();
+        self.input_label_color = self.picker_labels().len() % planka::LABEL_COLORS.len();
+        self.input_mode = InputMode::CreatingLabel;
+    }
+
+    pub fn cycle_label_color(&mut self, step: isize) {
+        let n = planka::LABEL_COLORS.len() as isize;
+        self.input_label_color = (self.input_label_color as isize + step).rem_euclid(n) as usize;
+    }
+
+    // Create a board label and put it on the open card
+    pub fn submit_create_label(&mut self) -> Result<(), String> {
+        let name = self.input_label.trim().to_string();
+        let color = planka::LABEL_COLORS[self.input_label_color].to_string();
+        let project = self.label_project();
+        let position = self.picker_labels().iter().map(|l| l.position).fold(0.0, f64::max) as i64 + 65536;
+        self.input_mode = InputMode::PickingLabel;
+        self.input_label.clear();
+        let board = project.clone();
+        self.spawn_job(
+            move |client| {
+                let id = client.create_label(&board, &color, Some(name.as_str()).filter(|n| !n.is_empty()), Some(position))?;
+                Ok(PlankaLabel { id, name, color, position: position as f64 })
+            },
+            move |app, res| match res {
+                Ok(label) => {
+                    let labels = app.board_labels.entry(project).or_default();
+                    labels.retain(|l| l.id != label.id);
+                    labels.push(label.clone());
+                    app.label_index = labels.len() - 1;
+                    if app.input_mode == InputMode::PickingLabel {
+                        app.toggle_selected_label();
+                    }
+                }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn open_label_filter(&mut self) {
+        self.input_mode = InputMode::FilteringLabel;
+        self.label_index = self
+            .label_filter
+            .as_ref()
+            .and_then(|f| self.picker_labels().iter().position(|l| &l.id == f))
+            .unwrap_or(0);
+        self.error_message = None;
+        self.refresh_labels(self.current_project.clone());
+    }
+
+    pub fn apply_label_filter(&mut self) {
+        self.label_filter = self.picker_labels().get(self.label_index).map(|l| l.id.clone());
+        self.input_mode = InputMode::Normal;
+        self.selected = 0;
+    }
+
+    pub fn clear_label_filter(&mut self) {
+        self.label_filter = None;
+        self.input_mode = InputMode::Normal;
+        self.selected = 0;
+    }
+
+    pub fn picker_members(&self) -> &[PlankaUser] {
+        self.board_members.get(&self.label_project()).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+
+    // A user by id, looked up among the members of every known board
+    pub fn member(&self, id: &str) -> Option<&PlankaUser> {
+        self.board_members.values().flatten().find(|u| u.id == id)
+    }
+
+    // Reflect a card's member change in the open card, the board view and My cards
+    fn note_card_member(&mut self, card_id: &str, user_id: &str, added: bool) {
+        if let Some(card) = self.view_card.as_mut().filter(|c| c.id == card_id) {
+            toggle_id(&mut card.member_ids, user_id, added);
+        }
+        for card in self.kanban.iter_mut().flat_map(|l| l.cards.iter_mut()).filter(|c| c.id == card_id) {
+            toggle_id(&mut card.member_ids, user_id, added);
+        }
+        for (_, _, card) in self.my_cards.iter_mut().filter(|(_, _, c)| c.id == card_id) {
+            toggle_id(&mut card.member_ids, user_id, added);
+        }
+    }
+
+    pub fn open_member_picker(&mut self) {
+        if self.view_card.is_none() {
+            return;
+        }
+        self.input_mode = InputMode::PickingMember;
+        self.member_index = 0;
+        self.error_message = None;
+        self.refresh_labels(self.label_project());
+        if self.my_user_id.is_none() {
+            self.spawn_job(
+                |client| client.fetch_user("me"),
+                |app, res| {
+                    if let Ok(me) = res {
+                        app.my_user_id = Some(me.id);
+                    }
+                },
+            );
+        }
+    }
+
+    // Assign the selected user to the open card or unassign them; queued like labels
+    pub fn toggle_selected_member(&mut self) {
+        let Some(user) = self.picker_members().get(self.member_index).cloned() else { return; };
+        let Some(card) = self.view_card.as_ref() else { return; };
+        let card_id = card.id.clone();
+        let added = !card.member_ids.contains(&user.id);
+        let mut project = self.label_project();
+        let mut local_id = None;
+        if let Some(t) = self.todos.iter_mut().find(|t| t.planka_card_id.as_deref() == Some(card_id.as_str())) {
+            toggle_id(&mut t.members, &user.id, added);
+            t.sync_dirty = true;
+            local_id = Some(t.local_id.clone());
+            project = t.project.clone();
+        }
+        self.note_card_member(&card_id, &user.id, added);
+        self.enqueue_op(PendingOp {
+            id: new_id(),
+            kind: if added { PendingOpKind::AddMember } else { PendingOpKind::RemoveMember },
+            project,
+            local_id,
+            card_id: Some(card_id),
+            list_id: None,
+            name: None,
+            due: None,
+            label_id: None,
+            user_id: Some(user.id),
+            ts: Local::now().timestamp(),
+            retry: RetryState::default(),
+        });
+    }
+
+    pub fn open_my_cards(&mut self) {
+        if self.planka_config.is_none() {
+            self.error_message = Some("Log in to Planka first (L)".to_string());
+            return;
+        }
+        self.input_mode = InputMode::ViewingMyCards;
+        self.error_message = None;
+        self.refresh_my_cards();
+    }
+
+    // Cards the logged-in user is a member of, on every board, through the
+    // list-cards endpoint's user filter
+    pub fn refresh_my_cards(&mut self) {
+        if self.my_cards_loading {
+            return;
+        }
+        self.my_cards_loading = true;
+        let me = self.my_user_id.clone();
+        let boards: Vec<String> = self.planka_boards.iter().map(|b| b.id.clone()).collect();
+        self.spawn_job(
+            move |client| {
+                let me = match me {
+                    Some(id) => id,
+                    None => client.fetch_user("me")?.id,
+                };
+                let mut cards = Vec::new();
+                for board in boards {
+                    let details = client.fetch_board_details(&board)?;
+                    for (list_id, list_name) in &details.lists {
+                        for card in client.fetch_list_cards(list_id, std::slice::from_ref(&me))? {
+                            cards.push((board.clone(), list_name.clone(), card));
+                        }
+                    }
+                }
+                Ok((me, cards))
+            },
+            |app, res| {
+                app.my_cards_loading = false;
+                match res {
+                    Ok((me, cards)) => {
+                        app.my_user_id = Some(me);
+                        app.my_cards = cards;
+                        app.my_cards_index = app.my_cards_index.min(app.my_cards.len().saturating_sub(1));
+                    }
+                    Err(e) => app.error_message = Some(e.to_string()),
+                }
+            },
+        );
+    }
+
+    pub fn open_selected_my_card(&mut self) {
+        if let Some((_, _, card)) = self.my_cards.get(self.my_cards_index) {
+            self.open_card(card.id.clone());
+        }
+    }
+
+    pub fn begin_edit_description(&mut self) {
+        if self.view_card.is_some() {
+            self.editor_request = Some(EditorTarget::Description);
+        }
+    }
+
+    // Start a comment straight in $EDITOR, for anything longer than a line
+    pub fn begin_long_comment(&mut self) {
+        self.begin_new_comment();
+        self.editor_request = Some(EditorTarget::Comment);
+    }
+
+    pub fn editor_text(&self, target: EditorTarget) -> String {
+        match target {
+            EditorTarget::Description => self.view_card.as_ref().and_then(|c| c.description.clone()).unwrap_or_default(),
+            EditorTarget::Comment => self.input_comment.clone(),
+        }
+    }
+
+    // Result of an editor session; None when the editor failed or was aborted
+    pub fn finish_editor(&mut self, target: EditorTarget, text: Option<String>) {
+        let Some(text) = text else {
+            self.error_message = Some("Editor exited without saving; nothing changed".to_string());
+            return;
+        };
+        // Editors add a final newline; the rest of the Markdown goes back untouched
+        let text = text.trim_end_matches(['\n', '\r']).to_string();
+        match target {
+            EditorTarget::Description => {
+                if text == self.editor_text(target).trim_end_matches(['\n', '\r']) {
+                    self.error_message = Some("Description unchanged".to_string());
+                    return;
+                }
+                let Some(card_id) = self.view_card.as_ref().map(|c| c.id.clone()) else { return; };
+                self.card_action(Some("Description updated"), move |client| {
+                    client.update_card_description(&card_id, Some(&text))
+                });
+            }
+            EditorTarget::Comment => {
+                self.input_comment = text;
+                let res = match self.input_mode {
+                    InputMode::EditingComment => self.submit_edit_comment(),
+                    _ => self.submit_comment(),
+                };
+                if let Err(e) = res {
+                    self.error_message = Some(e);
+                }
+            }
+        }
+    }
+
+    pub fn begin_new_comment(&mut self) {
+        self.input_comment.clear();
+        self.input_mode = InputMode::CreatingComment;
+        self.error_message = None;
+    }
+
+    pub fn begin_reply_to_last_comment(&mut self) {
+        self.input_comment.clear();
+        if let Some(last) = self.view_comments.last() {
+            if let Some(name) = &last.user_name {
+                self.input_comment = format!("@{} ", name);
+            } else {
+                let preview = last.text.lines().take(1).next().unwrap_or("");
+                if !preview.is_empty() {
+                    self.input_comment = format!("> {}\n", preview);
+                }
+            }
+        }
+        self.input_mode = InputMode::CreatingComment;
+        self.error_message = None;
+    }
+
+    pub fn begin_edit_last_comment(&mut self) {
+        if let Some(last) = self.view_comments.last() {
+            self.input_comment = last.text.clone();
+            self.input_mode = InputMode::EditingComment;
+            self.error_message = None;
+        }
+    }
+
+    pub fn submit_edit_comment(&mut self) -> Result<(), String> {
+        let Some(last) = self.view_comments.last() else { return Err("No comments to edit".into()); };
+        let cid = last.id.clone();
+        let text = self.input_comment.trim().to_string();
+        if text.is_empty() { return Err("Comment cannot be empty.".into()); }
+        self.card_action(Some("Comment updated"), move |client| client.update_comment(&cid, &text));
+        self.input_comment.clear();
+        self.input_mode = InputMode::ViewingCard;
+        Ok(())
+    }
+
+    pub fn delete_last_comment(&mut self) {
+        let Some(last) = self.view_comments.last() else { return; };
+        let cid = last.id.clone();
+        self.card_action(Some("Comment deleted"), move |client| client.delete_comment(&cid));
+    }
+
+    pub fn submit_comment(&mut self) -> Result<(), String> {
+        let text = self.input_comment.trim().to_string();
+        if text.is_empty() {
+            return Err("Comment cannot be empty.".to_string());
+        }
+        let card_id = match self.view_card.as_ref() {
+            Some(c) => c.id.clone(),
+            None => return Err("No card open".to_string()),
+        };
+        self.card_action(Some("Comment added"), move |client| {
+            let _cid = client.create_comment(&card_id, &text)?;
+            Ok(())
+        });
+        self.input_comment.clear();
+        self.input_mode = InputMode::ViewingCard;
+        Ok(())
+    }
+
+    pub fn begin_add_attachment(&mut self) {
+        self.input_attachment_url.clear();
+        self.input_mode = InputMode::CreatingAttachment;
+        self.error_message = None;
+    }
+
+    pub fn submit_attachment(&mut self) -> Result<(), String> {
+        let url = self.input_attachment_url.trim().to_string();
+        if url.is_empty() { return Err("Attachment URL cannot be empty.".into()); }
+        let card_id = match self.view_card.as_ref() { Some(c) => c.id.clone(), None => return Err("No card open".into()) };
+        let name = url.clone();
+        self.card_action(Some("Attachment added"), move |client| {
+            let _id = client.create_link_attachment(&card_id, &url, &name)?;
+            Ok(())
+        });
+        self.input_attachment_url.clear();
+        self.input_mode = InputMode::ViewingCard;
+        Ok(())
+    }
+
+    pub fn delete_last_attachment(&mut self) {
+        let Some(vc) = self.view_card.as_ref() else { return; };
+        let Some(last) = vc.attachments_full.last() else { return; };
+        let id = last.id.clone();
+        self.card_action(Some("Attachment deleted"), move |client| client.delete_attachment(&id));
+    }
+
+    pub fn begin_upload_file_attachment(&mut self) {
+        self.input_file_path.clear();
+        self.input_mode = InputMode::UploadingFileAttachment;
+        self.error_message = None;
+    }
+
+    pub fn submit_file_attachment(&mut self) -> Result<(), String> {
+        let path = self.input_file_path.trim().to_string();
+        if path.is_empty() { return Err("File path cannot be empty.".into()); }
+        let card_id = match self.view_card.as_ref() {
+            Some(c) => c.id.clone(),
+            None => return Err("No card open".into()),
+        };
+        self.card_action(Some("File attachment added"), move |client| {
+            let _id = client.create_file_attachment(&card_id, &path, None)?;
+            Ok(())
+        });
+        self.input_file_path.clear();
+        self.input_mode = InputMode::ViewingCard;
+        self.error_message = Some("Uploading…".into());
+        Ok(())
+    }
+
+    pub fn begin_rename_last_attachment(&mut self) {
+        if let Some(vc) = self.view_card.as_ref() {
+            if let Some(last) = vc.attachments_full.last() {
+                self.input_attachment_name = last.name.clone();
+                self.input_mode = InputMode::RenamingAttachment;
+                self.error_message = None;
+                return;
+            }
+        }
+        self.error_message = Some("No attachments to rename".into());
+    }
+
+    pub fn submit_rename_attachment(&mut self) -> Result<(), String> {
+        let name = self.input_attachment_name.trim().to_string();
+        if name.is_empty() { return Err("Attachment name cannot be empty.".into()); }
+        let att_id = match self.view_card.as_ref() {
+            Some(vc) => {
+                let last = vc.attachments_full.last().ok_or("No attachments on this card")?;
+                last.id.clone()
+            }
+            None => return Err("No card open".into()),
+        };
+        self.card_action(Some("Attachment renamed"), move |client| client.update_attachment_name(&att_id, &name));
+        self.input_attachment_name.clear();
+        self.input_mode = InputMode::ViewingCard;
+        Ok(())
+    }
+
+    pub fn begin_duplicate_card(&mut self) {
+        if let Some(vc) = self.view_card.as_ref() {
+            self.input_duplicate_name = format!("{} (copy)", vc.name);
+            self.input_mode = InputMode::DuplicatingCard;
+            self.error_message = None;
+        } else {
+            self.error_message = Some("No card open".into());
         }
+    }
 
-        // clear inputs after adding
-        self.input_description.clear();
-        self.input_due_date.clear();
-        self.error_message = None;
-
+    pub fn submit_duplicate_card(&mut self) -> Result<(), String> {
+        let name = self.input_duplicate_name.trim().to_string();
+        if name.is_empty() { return Err("Duplicate name cannot be empty.".into()); }
+        let card_id = match self.view_card.as_ref() {
+            Some(c) => c.id.clone(),
+            None => return Err("No card open".into()),
+        };
+        // Optional: refresh current list or card view; we just refresh the open card
+        self.card_action(Some("Card duplicated"), move |client| {
+            let _new_id = client.duplicate_card(&card_id, 65536, &name)?;
+            Ok(())
+        });
+        self.input_duplicate_name.clear();
+        self.input_mode = InputMode::ViewingCard;
         Ok(())
     }
 
-    pub fn delete_todo(&mut self) {
-        let Some(idx) = self.selected_index_in_all() else { return; };
-        let card_id = self.todos[idx].planka_card_id.clone();
-        if let Some(ref cid) = card_id {
-            if let Ok(client) = self.ensure_planka_client() {
-                if let Err(e) = client.delete_card(cid) {
-                    self.error_message = Some(format!("Planka delete failed: {}", e));
-                    self.enqueue_op(PendingOp {
-                        kind: PendingOpKind::Delete,
-                        project: self.current_project.clone(),
-                        card_id: Some(cid.clone()),
-                        list_id: None,
-                        name: None,
-                        due: None,
-                        ts: Local::now().timestamp(),
-                    });
+    pub fn mark_card_notifications_read(&mut self) {
+        let card_id = match self.view_card.as_ref() {
+            Some(c) => c.id.clone(),
+            None => { self.error_message = Some("No card open".into()); return; }
+        };
+        let id = card_id.clone();
+        self.spawn_job(
+            move |client| client.read_card_notifications(&id),
+            move |app, res| match res {
+                Ok(()) => {
+                    for n in app.notifications.iter_mut().filter(|n| n.card_id.as_deref() == Some(card_id.as_str())) {
+                        n.is_read = true;
+                    }
+                    app.error_message = Some("Card notifications marked as read".into());
                 }
-            } else if self.error_message.is_none() {
-                // ensure_planka_client sets error_message on failure
-            }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
+    }
+
+    // Card view tabs: 0 Comments, 1 General, 2 Custom Fields, 3 Activity, 4 Commands
+    pub fn select_card_tab(&mut self, tab: usize) {
+        self.view_card_tab = tab;
+        if tab == 3 && self.card_actions.is_empty() && !self.card_actions_end {
+            self.load_actions(false, false);
         }
-        self.todos.remove(idx);
-        if self.selected > 0 {
-            self.selected -= 1;
+    }
+
+    // The Activity tab scrolled; fetch the next page once its end is within `visible` rows
+    pub fn card_activity_scrolled(&mut self, visible: usize) {
+        if self.view_scroll as usize + visible >= self.card_actions.len() {
+            self.load_actions(false, true);
         }
     }
 
-    pub fn mark_done(&mut self) {
-        let Some(idx) = self.selected_index_in_all() else { return; };
-        // Read needed values without holding a mutable borrow of self
-        let (was_done, card_id_opt) = {
-            let todo = &self.todos[idx];
-            (todo.done, todo.planka_card_id.clone())
-        };
-        let new_done = !was_done;
+    pub fn open_board_activity(&mut self) {
+        if !self.project_names.contains_key(&self.current_project) {
+            self.error_message = Some("This project has no Planka board".to_string());
+            return;
+        }
+        self.input_mode = InputMode::ViewingBoardActivity;
+        self.board_action_index = 0;
+        self.error_message = None;
+        self.load_actions(true, false);
+    }
 
-        // If changing done-state, attempt to move the card on Planka
-        if new_done || (!new_done && was_done) {
-            if let Ok(client) = self.ensure_planka_client() {
-                let lists = if let Some(l) = self.planka_lists_by_board.get(&self.current_project).cloned() {
-                    l
-                } else {
-                    match client.resolve_lists(&self.current_project) {
-                        Ok(l) => {
-                            self.planka_lists_by_board.insert(self.current_project.clone(), l.clone());
-                            self.planka_lists = Some(l.clone());
-                            l
-                        }
-                        Err(e) => {
-                            self.error_message = Some(e);
-                            return;
-                        }
-                    }
-                };
-                if let Some(ref card_id) = card_id_opt {
-                    let target = if new_done { &lists.done_list_id } else { &lists.todo_list_id };
-                    if let Err(e) = client.move_card(card_id, target) {
-                        self.error_message = Some(format!("Planka move to Done failed: {}", e));
-                        if let Some(ref card_id) = card_id_opt {
-                            self.enqueue_op(PendingOp {
-                                kind: PendingOpKind::Move,
-                                project: self.current_project.clone(),
-                                card_id: Some(card_id.clone()),
-                                list_id: Some(target.clone()),
-                                name: None,
-                                due: None,
-                                ts: Local::now().timestamp(),
-                            });
-                        }
-                        if let Some(todo) = self.todos.get_mut(idx) {
-                            todo.sync_dirty = true;
-                        }
-                    } else {
-                        if let Some(todo) = self.todos.get_mut(idx) {
-                            todo.planka_list_id = Some(target.clone());
-                        }
-                    }
-                }
-            }
+    pub fn board_activity_step(&mut self, step: isize) {
+        let last = self.board_actions.len().saturating_sub(1);
+        self.board_action_index = self.board_action_index.saturating_add_signed(step).min(last);
+        // Keep a few entries of headroom so the next page is there before the end
+        if self.board_action_index + 5 >= self.board_actions.len() {
+            self.load_actions(true, true);
         }
+    }
 
-        if let Some(todo) = self.todos.get_mut(idx) {
-            todo.done = new_done;
+    pub fn open_selected_board_action(&mut self) {
+        match self.board_actions.get(self.board_action_index).and_then(|a| a.card_id.clone()) {
+            Some(cid) => self.open_card(cid),
+            None => self.error_message = Some("This entry has no card".to_string()),
         }
     }
 
-    pub fn mark_doing(&mut self) {
-        let Some(idx) = self.selected_index_in_all() else { return; };
-        // Read needed values without holding a mutable borrow of self
-        let (card_id_opt, _was_done, current_list_id) = {
-            let t = &self.todos[idx];
-            (t.planka_card_id.clone(), t.done, t.planka_list_id.clone())
+    pub fn user_name(&self, id: &str) -> Option<&str> {
+        self.user_names.get(id).map(|s| s.as_str()).or_else(|| self.member(id).map(|u| u.name.as_str()))
+    }
+
+    // Load the newest page of the board's (or the open card's) activity, or
+    // with `older` the page before the last entry loaded
+    pub fn load_actions(&mut self, board: bool, older: bool) {
+        let (target, loaded, end, loading) = if board {
+            (Some(self.current_project.clone()), &self.board_actions, self.board_actions_end, &self.board_actions_loading)
+        } else {
+            (self.view_card.as_ref().map(|c| c.id.clone()), &self.card_actions, self.card_actions_end, &self.card_actions_loading)
         };
-        // Doing is only for not-done items
-        let target_is_doing: bool;
-        let lists = match self.ensure_planka_client() {
-            Ok(client) => {
-                if let Some(l) = self.planka_lists_by_board.get(&self.current_project).cloned() {
-                    l
+        let Some(target) = target else { return; };
+        if loading.as_ref() == Some(&target) || (older && end) {
+            return;
+        }
+        let before = if older { loaded.last().map(|a| a.id.clone()) } else { None };
+        let mut known: HashSet<String> = self.user_names.keys().cloned().collect();
+        known.extend(self.board_members.values().flatten().map(|u| u.id.clone()));
+        if board {
+            self.board_actions_loading = Some(target.clone());
+        } else {
+            self.card_actions_loading = Some(target.clone());
+        }
+        let id = target.clone();
+        self.spawn_job(
+            move |client| {
+                let page = if board {
+                    client.fetch_board_actions(&id, before.as_deref())?
                 } else {
-                    match client.resolve_lists(&self.current_project) {
-                        Ok(l) => {
-                            self.planka_lists_by_board.insert(self.current_project.clone(), l.clone());
-                            self.planka_lists = Some(l.clone());
-                            l
-                        }
-                        Err(e) => {
-                            self.error_message = Some(e);
-                            return;
-                        }
-                    }
+                    client.fetch_card_actions(&id, before.as_deref())?
+                };
+                // Listing users needs admin rights; without them authors who
+                // are not board members stay unnamed
+                let names: HashMap<String, String> = if page.iter().filter_map(|a| a.user_id.as_ref()).any(|u| !known.contains(u)) {
+                    client.fetch_users().map(|us| us.into_iter().map(|u| (u.id, u.name)).collect()).unwrap_or_default()
+                } else {
+                    HashMap::new()
+                };
+                Ok((page, names))
+            },
+            move |app, res| {
+                let loading = if board { &mut app.board_actions_loading } else { &mut app.card_actions_loading };
+                if loading.as_ref() == Some(&target) {
+                    *loading = None;
                 }
-            }
-            Err(e) => {
-                self.error_message = Some(e);
-                return;
-            }
-        };
-        // Decide toggle target based on current list
-        target_is_doing = current_list_id.as_deref() != Some(lists.doing_list_id.as_str());
-        let target_list = if target_is_doing { &lists.doing_list_id } else { &lists.todo_list_id };
-        // Move remote if we have a card id
-        if let Some(ref cid) = card_id_opt {
-            if let Ok(client) = self.ensure_planka_client() {
-                if let Err(e) = client.move_card(cid, target_list) {
-                    self.error_message = Some(format!("Planka move to Doing failed: {}", e));
-                    // Queue a move only if we have a card id
-                    self.enqueue_op(PendingOp {
-                        kind: PendingOpKind::Move,
-                        project: self.current_project.clone(),
-                        card_id: Some(cid.clone()),
-                        list_id: Some(target_list.clone()),
-                        name: None,
-                        due: None,
-                        ts: Local::now().timestamp(),
-                    });
-                    if let Some(t) = self.todos.get_mut(idx) {
-                        t.sync_dirty = true;
+                let (page, names) = match res {
+                    Ok(r) => r,
+                    Err(e) => {
+                        app.error_message = Some(e.to_string());
+                        return;
                     }
+                };
+                app.user_names.extend(names);
+                // Drop pages for a board or card that is no longer shown
+                let (actions, end) = if board && app.current_project == target {
+                    (&mut app.board_actions, &mut app.board_actions_end)
+                } else if !board && app.view_card.as_ref().map(|c| c.id.as_str()) == Some(target.as_str()) {
+                    (&mut app.card_actions, &mut app.card_actions_end)
                 } else {
-                    if let Some(t) = self.todos.get_mut(idx) {
-                        t.planka_list_id = Some(target_list.clone());
-                    }
+                    return;
+                };
+                if !older {
+                    actions.clear();
                 }
-            }
-        } else {
-            // No remote id: reflect locally; background queue cannot move without id
-            if let Some(t) = self.todos.get_mut(idx) {
-                t.planka_list_id = Some(target_list.clone());
-                t.sync_dirty = true;
-            }
-        }
-        // Ensure not done when marking Doing; clearing done state locally if set
-        if let Some(t) = self.todos.get_mut(idx) {
-            t.done = false;
-        }
+                *end = page.is_empty();
+                actions.extend(page);
+            },
+        );
     }
 
-    pub fn next_project(&mut self) {
-        if self.projects.is_empty() {
-            self.projects = default_projects();
+    pub fn unread_notifications(&self) -> usize {
+        self.notifications.iter().filter(|n| !n.is_read).count()
+    }
+
+    // Replace the inbox; entries already read here stay listed until the next reload
+    fn set_notifications(&mut self, mut list: Vec<PlankaNotification>) {
+        list.sort_by(|a, b| b.created.cmp(&a.created));
+        self.notifications = list;
+        self.notification_index = self.notification_index.min(self.notifications.len().saturating_sub(1));
+    }
+
+    pub fn open_notifications(&mut self) {
+        if self.planka_config.is_none() {
+            self.error_message = Some("Log in to Planka first (L)".to_string());
+            return;
         }
-        if let Some(pos) = self.projects.iter().position(|p| p == &self.current_project) {
-            let next = (pos + 1) % self.projects.len();
-            self.current_project = self.projects[next].clone();
-        } else {
-            self.current_project = self.projects[0].clone();
+        self.input_mode = InputMode::ViewingNotifications;
+        self.notification_index = 0;
+        self.error_message = None;
+        self.refresh_notifications();
+    }
+
+    pub fn refresh_notifications(&mut self) {
+        self.spawn_job(
+            |client| client.fetch_notifications(),
+            |app, res| match res {
+                Ok(list) => app.set_notifications(list),
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
+    }
+
+    // Open the card a notification is about; reading it there marks it read
+    pub fn open_selected_notification(&mut self) {
+        let Some(n) = self.notifications.get(self.notification_index) else { return; };
+        let Some(cid) = n.card_id.clone() else {
+            self.error_message = Some("This notification has no card".to_string());
+            return;
+        };
+        if !n.is_read {
+            self.toggle_selected_notification_read();
         }
-        if self.planka_lists_by_board.get(&self.current_project).is_none() {
-            if let Ok(client) = self.ensure_planka_client() {
-                if let Ok(lists) = client.resolve_lists(&self.current_project) {
-                    self.planka_lists_by_board
-                        .insert(self.current_project.clone(), lists.clone());
-                    self.planka_lists = Some(lists);
+        self.open_card(cid);
+    }
+
+    // Mark the selected notification read, or unread again
+    pub fn toggle_selected_notification_read(&mut self) {
+        let Some(n) = self.notifications.get_mut(self.notification_index) else { return; };
+        let (id, read) = (n.id.clone(), !n.is_read);
+        // Shown at once; put back if the server refuses
+        n.is_read = read;
+        let nid = id.clone();
+        self.spawn_job(
+            move |client| client.update_notification(&nid, read),
+            move |app, res| {
+                if let Err(e) = res {
+                    if let Some(n) = app.notifications.iter_mut().find(|n| n.id == id) {
+                        n.is_read = !read;
+                    }
+                    app.error_message = Some(e.to_string());
                 }
-            }
-        }
+            },
+        );
     }
-    pub fn prev_project(&mut self) {
-        if self.projects.is_empty() {
-            self.projects = default_projects();
-        }
-        if let Some(pos) = self.projects.iter().position(|p| p == &self.current_project) {
-            let prev = (pos + self.projects.len() - 1) % self.projects.len();
-            self.current_project = self.projects[prev].clone();
-        } else {
-            self.current_project = self.projects[0].clone();
-        }
-        if self.planka_lists_by_board.get(&self.current_project).is_none() {
-            if let Ok(client) = self.ensure_planka_client() {
-                if let Ok(lists) = client.resolve_lists(&self.current_project) {
-                    self.planka_lists_by_board
-                        .insert(self.current_project.clone(), lists.clone());
-                    self.planka_lists = Some(lists);
+
+    pub fn mark_all_notifications_read(&mut self) {
+        self.spawn_job(
+            |client| client.read_all_notifications(),
+            |app, res| match res {
+                Ok(()) => {
+                    for n in app.notifications.iter_mut() {
+                        n.is_read = true;
+                    }
+                    app.error_message = Some("All notifications marked as read".into());
                 }
-            }
-        }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
     }
-    pub fn set_current_project<S: Into<String>>(&mut self, name: S) {
-        let name = name.into().trim().to_string();
-        if name.is_empty() {
+
+    pub fn open_users(&mut self) {
+        if self.planka_config.is_none() {
+            self.error_message = Some("Log in to Planka first (L)".to_string());
             return;
         }
-        if !self.projects.iter().any(|p| p == &name) {
-            self.projects.push(name.clone());
-        }
-        self.current_project = name;
-        if self.planka_lists_by_board.get(&self.current_project).is_none() {
-            if let Ok(client) = self.ensure_planka_client() {
-                if let Ok(lists) = client.resolve_lists(&self.current_project) {
-                    self.planka_lists_by_board
-                        .insert(self.current_project.clone(), lists.clone());
-                    self.planka_lists = Some(lists);
+        self.input_mode = InputMode::ManagingUsers;
+        self.user_index = 0;
+        self.user_confirm = None;
+        self.error_message = None;
+        self.refresh_users();
+    }
+
+    // Who we are is needed too, so the own account can't be locked out
+    pub fn refresh_users(&mut self) {
+        let me = self.my_user_id.clone();
+        self.spawn_job(
+            move |client| {
+                let me = match me {
+                    Some(id) => id,
+                    None => client.fetch_user("me")?.id,
+                };
+                Ok((me, client.fetch_users()?))
+            },
+            |app, res| match res {
+                Ok((me, users)) => {
+                    app.my_user_id = Some(me);
+                    app.set_users(users);
                 }
-            }
-        }
+                Err(PlankaError::Server { status: 403, .. }) => {
+                    app.error_message = Some("Only Planka admins can manage users".to_string());
+                }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
     }
-    pub fn refresh_projects_from_todos(&mut self) {
-        let mut uniq: Vec<String> = self
-            .todos
-            .iter()
-            .map(|t| t.project.clone())
-            .filter(|p| !p.is_empty())
-            .collect();
-        uniq.sort();
-        uniq.dedup();
-        if uniq.is_empty() {
-            uniq = default_projects();
-        }
-        self.projects = uniq;
-        if !self.projects.iter().any(|p| p == &self.current_project) {
-            self.current_project = self.projects[0].clone();
-        }
+
+    fn set_users(&mut self, mut users: Vec<PlankaUser>) {
+        users.sort_by_key(|u| u.name.to_lowercase());
+        self.users = users;
+        self.user_index = self.user_index.min(self.users.len().saturating_sub(1));
     }
 
-    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
-        let file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(path)
-            .map_err(|e| format!("Failed to open file: {}", e))?;
+    pub fn ask_next_user_role(&mut self) {
+        let Some(u) = self.users.get(self.user_index) else { return; };
+        let i = planka::USER_ROLES.iter().position(|(r, _)| *r == u.role).map_or(0, |i| i + 1);
+        let role = planka::USER_ROLES[i % planka::USER_ROLES.len()].0.to_string();
+        self.ask_user_change(UserChange::Role(role));
+    }
 
-        let writer = BufWriter::new(file);
+    pub fn ask_toggle_user_deactivated(&mut self) {
+        let Some(u) = self.users.get(self.user_index) else { return; };
+        self.ask_user_change(UserChange::Deactivate(!u.is_deactivated));
+    }
 
-        serde_json::to_writer_pretty(writer, self)
-            .map_err(|e| format!("Failed to write JSON!: {}", e))
+    pub fn ask_delete_user(&mut self) {
+        self.ask_user_change(UserChange::Delete);
     }
 
-    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Self {
-        let file = File::open(&path);
-        if let Ok(file) = file {
-            let reader = BufReader::new(file);
-            {
-                let mut app: App =
-                    serde_json::from_reader(reader).unwrap_or_else(|_| App::new());
-                app.refresh_projects_from_todos();
-                // Re-load Planka config each run (it’s not persisted in todos.json)
-                app.planka_config = planka::load_config();
-                app
-            }
-        } else {
-            App::new()
+    fn ask_user_change(&mut self, change: UserChange) {
+        let Some(u) = self.users.get(self.user_index) else { return; };
+        if self.my_user_id.as_deref() == Some(u.id.as_str()) {
+            self.error_message = Some("That is your own account; another admin has to change it".to_string());
+            return;
         }
+        self.user_confirm = Some((u.id.clone(), change));
+        self.error_message = None;
     }
 
-    pub fn open_selected_card(&mut self) {
-        let Some(idx) = self.selected_index_in_all() else { return; };
-        let cid = match self.todos[idx].planka_card_id.as_deref() {
-            Some(c) => c.to_string(),
-            None => {
-                self.error_message = Some("This task is not linked to a Planka card yet".to_string());
-                return;
-            }
+    // Question shown while a change waits for y
+    pub fn user_confirm_prompt(&self) -> Option<String> {
+        let (id, change) = self.user_confirm.as_ref()?;
+        let u = self.users.iter().find(|u| u.id == *id)?;
+        let who = match u.email {
+            Some(ref e) => format!("{} ({})", u.name, e),
+            None => u.name.clone(),
         };
-        match self.ensure_planka_client() {
-            Ok(client) => match client.fetch_card_details(&cid) {
-                Ok(details) => {
-                    self.view_card = Some(details);
-                    match client.fetch_comments(&cid) {
-                        Ok(comments) => self.view_comments = comments,
-                        Err(e) => { self.view_comments = Vec::new(); self.error_message = Some(e); }
-                    }
-                    self.view_scroll = 0;
-                    self.input_mode = InputMode::ViewingCard;
-                    self.view_card_tab = 0;
-                    self.error_message = None;
+        let question = match change {
+            UserChange::Role(r) => format!("Change the role of {} to {}?", who, planka::user_role_name(r)),
+            UserChange::Deactivate(true) => format!("Deactivate {}? They can't log in until reactivated.", who),
+            UserChange::Deactivate(false) => format!("Reactivate {}?", who),
+            UserChange::Delete => format!("Delete {} for good?", who),
+        };
+        Some(format!("{} y confirms, any other key cancels", question))
+    }
+
+    pub fn confirm_user_change(&mut self) {
+        let Some((id, change)) = self.user_confirm.take() else { return; };
+        let uid = id.clone();
+        let job_change = change.clone();
+        self.spawn_job(
+            move |client| match job_change {
+                UserChange::Role(ref role) => client.update_user(&uid, Some(role), None, None),
+                UserChange::Deactivate(d) => client.update_user(&uid, None, None, Some(d)),
+                UserChange::Delete => client.delete_user(&uid),
+            },
+            move |app, res| {
+                if let Err(e) = res {
+                    app.error_message = Some(e.to_string());
+                    return;
                 }
-                Err(e) => self.error_message = Some(e),
+                let Some(pos) = app.users.iter().position(|u| u.id == id) else { return; };
+                app.error_message = Some(match change {
+                    UserChange::Role(role) => {
+                        let msg = format!("Role of {} changed to {}", app.users[pos].name, planka::user_role_name(&role));
+                        app.users[pos].role = role;
+                        msg
+                    }
+                    UserChange::Deactivate(d) => {
+                        app.users[pos].is_deactivated = d;
+                        format!("{} {}", app.users[pos].name, if d { "deactivated" } else { "reactivated" })
+                    }
+                    UserChange::Delete => {
+                        let u = app.users.remove(pos);
+                        app.user_index = app.user_index.min(app.users.len().saturating_sub(1));
+                        format!("{} deleted", u.name)
+                    }
+                });
             },
-            Err(e) => self.error_message = Some(e),
-        }
+        );
     }
 
-    pub fn close_view(&mut self) {
-        self.view_card = None;
-        self.view_scroll = 0;
-        self.input_mode = InputMode::Normal;
+    pub fn begin_create_user(&mut self) {
+        self.user_form.clear();
+        self.input_user.clear();
+        self.user_confirm = None;
+        self.error_message = None;
+        self.input_mode = InputMode::CreatingUser;
     }
 
-    fn refresh_open_card_details(&mut self) {
-        let card_id = match self.view_card.as_ref() { Some(c) => c.id.clone(), None => return };
-        match self.ensure_planka_client() {
-            Ok(client) => {
-                if let Ok(details) = client.fetch_card_details(&card_id) {
-                    self.view_card = Some(details);
-                }
-                if let Ok(comments) = client.fetch_comments(&card_id) {
-                    self.view_comments = comments;
-                }
-            }
-            Err(e) => self.error_message = Some(e),
-        }
+    // Field the new-user form asks for next
+    pub fn user_form_field(&self) -> &'static str {
+        NEW_USER_FIELDS[self.user_form.len().min(NEW_USER_FIELDS.len() - 1)]
     }
 
-    pub fn begin_new_comment(&mut self) {
-        self.input_comment.clear();
-        self.input_mode = InputMode::CreatingComment;
+    pub fn cancel_create_user(&mut self) {
+        self.user_form.clear();
+        self.input_user.clear();
+        self.input_mode = InputMode::ManagingUsers;
+    }
+
+    // Take one field; after the last, create the account as a board user
+    pub fn submit_user_form(&mut self) -> Result<(), String> {
         self.error_message = None;
+        let field = self.user_form_field();
+        let value = std::mem::take(&mut self.input_user);
+        // Passwords are taken as typed
+        let value = if field == "Password" { value } else { value.trim().to_string() };
+        if value.is_empty() && self.user_form.len() + 1 < NEW_USER_FIELDS.len() {
+            return Err(format!("{} cannot be empty.", field));
+        }
+        self.user_form.push(value);
+        if self.user_form.len() < NEW_USER_FIELDS.len() {
+            return Ok(());
+        }
+        let mut form = std::mem::take(&mut self.user_form).into_iter();
+        let (email, password, name, username) = (
+            form.next().unwrap_or_default(),
+            form.next().unwrap_or_default(),
+            form.next().unwrap_or_default(),
+            form.next().unwrap_or_default(),
+        );
+        self.input_mode = InputMode::ManagingUsers;
+        self.error_message = Some("Creating user…".to_string());
+        self.spawn_job(
+            move |client| {
+                let username = Some(username.as_str()).filter(|u| !u.is_empty());
+                let id = client.create_user(&email, &password, "boardUser", &name, username)?;
+                Ok((id, client.fetch_users()?))
+            },
+            |app, res| match res {
+                Ok((id, users)) => {
+                    app.set_users(users);
+                    if let Some(i) = app.users.iter().position(|u| u.id == id) {
+                        app.user_index = i;
+                    }
+                    app.error_message = Some("User created as a board user; R changes the role".to_string());
+                }
+                Err(e) => app.error_message = Some(format!("Creating the user failed: {}", e)),
+            },
+        );
+        Ok(())
     }
 
-    pub fn begin_reply_to_last_comment(&mut self) {
-        self.input_comment.clear();
-        if let Some(last) = self.view_comments.last() {
-            if let Some(name) = &last.user_name {
-                self.input_comment = format!("@{} ", name);
-            } else {
-                let preview = last.text.lines().take(1).next().unwrap_or("");
-                if !preview.is_empty() {
-                    self.input_comment = format!("> {}\n", preview);
-                }
-            }
+    pub fn open_account(&mut self) {
+        if self.planka_config.is_none() {
+            self.error_message = Some("Log in to Planka first (L)".to_string());
+            return;
         }
-        self.input_mode = InputMode::CreatingComment;
+        self.input_mode = InputMode::EditingAccount;
+        self.account_index = 0;
         self.error_message = None;
+        self.spawn_job(
+            |client| client.fetch_user("me"),
+            |app, res| match res {
+                Ok(me) => {
+                    app.my_user_id = Some(me.id.clone());
+                    app.account = Some(me);
+                }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
     }
 
-    pub fn begin_edit_last_comment(&mut self) {
-        if let Some(last) = self.view_comments.last() {
-            self.input_comment = last.text.clone();
-            self.input_mode = InputMode::EditingComment;
-            self.error_message = None;
-        }
+    pub fn account_field(&self) -> AccountField {
+        AccountField::ALL[self.account_index.min(AccountField::ALL.len() - 1)]
     }
 
-    pub fn submit_edit_comment(&mut self) -> Result<(), String> {
-        let Some(last) = self.view_comments.last() else { return Err("No comments to edit".into()); };
-        let cid = last.id.clone();
-        let text = self.input_comment.trim().to_string();
-        if text.is_empty() { return Err("Comment cannot be empty.".into()); }
-        let client = self.ensure_planka_client()?;
-        client.update_comment(&cid, &text)?;
-        self.input_comment.clear();
-        self.input_mode = InputMode::ViewingCard;
-        self.refresh_open_card_details();
-        self.error_message = Some("Comment updated".into());
-        Ok(())
+    // Question the open form asks next
+    pub fn account_prompt(&self) -> &'static str {
+        let prompts = self.account_field().prompts();
+        prompts[self.account_form.len().min(prompts.len() - 1)]
     }
 
-    pub fn delete_last_comment(&mut self) {
-        let Some(last) = self.view_comments.last() else { return; };
-        let cid = last.id.clone();
-        match self.ensure_planka_client() {
-            Ok(client) => {
-                if let Err(e) = client.delete_comment(&cid) {
-                    self.error_message = Some(e);
-                } else {
-                    self.refresh_open_card_details();
-                    self.error_message = Some("Comment deleted".into());
-                }
-            }
-            Err(e) => self.error_message = Some(e),
+    pub fn begin_account_change(&mut self) {
+        if self.account.is_none() {
+            return;
         }
+        let field = self.account_field();
+        let sso = self.planka_config.as_ref().is_some_and(|c| c.sso);
+        if sso && field != AccountField::Avatar {
+            self.error_message = Some("Single sign-on accounts change this at the identity provider".to_string());
+            return;
+        }
+        self.account_form.clear();
+        self.input_account.clear();
+        self.error_message = None;
+        self.input_mode = InputMode::ChangingAccount;
     }
 
-    pub fn submit_comment(&mut self) -> Result<(), String> {
-        let text = self.input_comment.trim().to_string();
-        if text.is_empty() {
-            return Err("Comment cannot be empty.".to_string());
+    pub fn cancel_account_change(&mut self) {
+        self.account_form.clear();
+        self.input_account.clear();
+        self.input_mode = InputMode::EditingAccount;
+    }
+
+    // Take one answer; after the last, send the change
+    pub fn submit_account_form(&mut self) -> Result<(), String> {
+        self.error_message = None;
+        let field = self.account_field();
+        let prompt = self.account_prompt();
+        let value = std::mem::take(&mut self.input_account);
+        let value = if is_secret_prompt(prompt) { value } else { value.trim().to_string() };
+        if value.is_empty() && !(field == AccountField::Username && self.account_form.is_empty()) {
+            return Err(format!("{} cannot be empty.", prompt));
         }
-        let card_id = match self.view_card.as_ref() {
-            Some(c) => c.id.clone(),
-            None => return Err("No card open".to_string()),
-        };
-        let client = self.ensure_planka_client()?;
-        let _cid = client.create_comment(&card_id, &text)?;
-        match client.fetch_comments(&card_id) {
-            Ok(comments) => self.view_comments = comments,
-            Err(e) => self.error_message = Some(e),
+        if field == AccountField::Password && self.account_form.len() == 1 && self.account_form[0] != value {
+            self.account_form.clear();
+            return Err("The passwords don't match; enter the new one again".to_string());
+        }
+        self.account_form.push(value);
+        if self.account_form.len() < field.prompts().len() {
+            return Ok(());
+        }
+        let Some(me) = self.account.clone() else { return Ok(()); };
+        let mut form = std::mem::take(&mut self.account_form);
+        self.input_mode = InputMode::EditingAccount;
+        self.error_message = Some("Saving…".to_string());
+        let current = if field == AccountField::Avatar { String::new() } else { form.pop().unwrap_or_default() };
+        let value = form.swap_remove(0);
+        match field {
+            AccountField::Password => {
+                let password = value.clone();
+                self.spawn_job(
+                    move |client| client.update_user_password(&me.id, &password, Some(&current)),
+                    move |app, res| match res {
+                        Ok(()) => app.password_changed(value),
+                        Err(e) => app.error_message = Some(format!("Changing the password failed: {}", e)),
+                    },
+                );
+            }
+            AccountField::Email => {
+                let email = value.clone();
+                self.spawn_job(
+                    move |client| client.update_user_email(&me.id, &email, Some(&current)).map(|_| current),
+                    move |app, res| match res {
+                        Ok(current) => {
+                            app.follow_login_name(me.email.as_deref(), value.clone(), &current);
+                            if let Some(a) = app.account.as_mut() {
+                                a.email = Some(value);
+                            }
+                            app.error_message = Some("Email changed".to_string());
+                        }
+                        Err(e) => app.error_message = Some(format!("Changing the email failed: {}", e)),
+                    },
+                );
+            }
+            AccountField::Username => {
+                let username = Some(value.clone()).filter(|u| !u.is_empty());
+                self.spawn_job(
+                    move |client| client.update_user_username(&me.id, username.as_deref(), Some(&current)).map(|_| current),
+                    move |app, res| match res {
+                        Ok(current) => {
+                            // Without a username left, the email is what we log in with
+                            let login = if value.is_empty() { me.email.clone().unwrap_or_default() } else { value.clone() };
+                            app.follow_login_name(me.username.as_deref(), login, &current);
+                            if let Some(a) = app.account.as_mut() {
+                                a.username = Some(value).filter(|u| !u.is_empty());
+                            }
+                            app.error_message = Some("Username changed".to_string());
+                        }
+                        Err(e) => app.error_message = Some(format!("Changing the username failed: {}", e)),
+                    },
+                );
+            }
+            AccountField::Avatar => {
+                self.spawn_job(
+                    move |client| client.update_user_avatar(&me.id, &value),
+                    |app, res| {
+                        app.error_message = Some(match res {
+                            Ok(()) => "Avatar updated".to_string(),
+                            Err(e) => format!("Uploading the avatar failed: {}", e),
+                        });
+                    },
+                );
+            }
         }
-        self.input_comment.clear();
-        self.input_mode = InputMode::ViewingCard;
-        self.error_message = Some("Comment added".to_string());
         Ok(())
     }
 
-    pub fn begin_add_attachment(&mut self) {
-        self.input_attachment_url.clear();
-        self.input_mode = InputMode::CreatingAttachment;
-        self.error_message = None;
+    // Keep the new password where the old one was, so re-logins keep working
+    fn password_changed(&mut self, password: String) {
+        let Some(cfg) = self.planka_config.as_mut() else { return; };
+        cfg.password = password;
+        let stored = secrets::store_password(cfg, &cfg.password);
+        let _ = planka::save_config(cfg);
+        self.error_message = Some(match stored {
+            Ok(()) => "Password changed".to_string(),
+            Err(e) => format!("Password changed, but the new one wasn't saved: {}", e),
+        });
     }
 
-    pub fn submit_attachment(&mut self) -> Result<(), String> {
-        let url = self.input_attachment_url.trim().to_string();
-        if url.is_empty() { return Err("Attachment URL cannot be empty.".into()); }
-        let card_id = match self.view_card.as_ref() { Some(c) => c.id.clone(), None => return Err("No card open".into()) };
-        let client = self.ensure_planka_client()?;
-        let name = url.clone();
-        let _id = client.create_link_attachment(&card_id, &url, &name)?;
-        self.input_attachment_url.clear();
-        self.input_mode = InputMode::ViewingCard;
-        self.refresh_open_card_details();
-        self.error_message = Some("Attachment added".into());
-        Ok(())
+    // The email or username changed; follow it when it is the name we log in with
+    fn follow_login_name(&mut self, old: Option<&str>, new: String, password: &str) {
+        let Some(cfg) = self.planka_config.as_mut() else { return; };
+        if new.is_empty() || !old.is_some_and(|o| o.eq_ignore_ascii_case(&cfg.email_or_username)) {
+            return;
+        }
+        let previous = cfg.clone();
+        cfg.email_or_username = new;
+        // The keyring files the password under the login name; move it over
+        if cfg.secret_store == secrets::SecretStore::Keyring
+            && previous.email_or_username != cfg.email_or_username
+            && secrets::store_password(cfg, password).is_ok()
+        {
+            let _ = secrets::forget_password(&previous);
+        }
+        let _ = planka::save_config(cfg);
     }
 
-    pub fn delete_last_attachment(&mut self) {
-        let Some(vc) = self.view_card.as_ref() else { return; };
-        let Some(last) = vc.attachments_full.last() else { return; };
-        let id = last.id.clone();
-        match self.ensure_planka_client() {
-            Ok(client) => {
-                if let Err(e) = client.delete_attachment(&id) {
-                    self.error_message = Some(e);
-                } else {
-                    self.refresh_open_card_details();
-                    self.error_message = Some("Attachment deleted".into());
-                }
-            }
-            Err(e) => self.error_message = Some(e),
+    pub fn open_board_members(&mut self) {
+        if self.planka_config.is_none() {
+            self.error_message = Some("Log in to Planka first (L).".to_string());
+            return;
+        }
+        if !self.planka_boards.iter().any(|b| b.id == self.current_project) {
+            self.error_message = Some("The current project is not a Planka board.".to_string());
+            return;
         }
+        self.input_mode = InputMode::ManagingBoardMembers;
+        self.memberships.clear();
+        self.membership_index = 0;
+        self.membership_confirm = None;
+        self.error_message = None;
+        self.refresh_board_members();
     }
 
-    pub fn begin_upload_file_attachment(&mut self) {
-        self.input_file_path.clear();
-        self.input_mode = InputMode::UploadingFileAttachment;
-        self.error_message = None;
+    pub fn refresh_board_members(&mut self) {
+        let board = self.current_project.clone();
+        let me = self.my_user_id.clone();
+        self.spawn_job(
+            {
+                let board = board.clone();
+                move |client| {
+                    let me = match me {
+                        Some(id) => id,
+                        None => client.fetch_user("me")?.id,
+                    };
+                    Ok((me, client.fetch_board_details(&board)?))
+                }
+            },
+            move |app, res| match res {
+                Ok((me, details)) => {
+                    app.my_user_id = Some(me);
+                    let mut rows: Vec<(PlankaBoardMembership, PlankaUser)> = details
+                        .memberships
+                        .into_iter()
+                        .map(|m| {
+                            let user = details.members.iter().find(|u| u.id == m.user_id).cloned().unwrap_or_else(|| PlankaUser {
+                                id: m.user_id.clone(),
+                                role: String::new(),
+                                name: m.user_id.clone(),
+                                username: None,
+                                email: None,
+                                is_deactivated: false,
+                            });
+                            (m, user)
+                        })
+                        .collect();
+                    rows.sort_by_key(|(_, u)| u.name.to_lowercase());
+                    app.memberships = rows;
+                    app.membership_index = app.membership_index.min(app.memberships.len().saturating_sub(1));
+                    // Keep the member picker and initials in step
+                    app.board_members.insert(board, details.members);
+                }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
     }
 
-    pub fn submit_file_attachment(&mut self) -> Result<(), String> {
-        let path = self.input_file_path.trim().to_string();
-        if path.is_empty() { return Err("File path cannot be empty.".into()); }
-        let card_id = match self.view_card.as_ref() {
-            Some(c) => c.id.clone(),
-            None => return Err("No card open".into()),
-        };
-        let client = self.ensure_planka_client()?;
-        let _id = client.create_file_attachment(&card_id, &path, None)?;
-        self.input_file_path.clear();
-        self.input_mode = InputMode::ViewingCard;
-        self.refresh_open_card_details();
-        self.error_message = Some("File attachment added".into());
-        Ok(())
+    // Editor <-> viewer; a new viewer starts without comment rights
+    pub fn toggle_membership_role(&mut self) {
+        let Some((m, _)) = self.memberships.get(self.membership_index) else { return; };
+        let (id, old_role, old_comment) = (m.id.clone(), m.role.clone(), m.can_comment);
+        let (role, can_comment) = if old_role == "editor" { ("viewer", Some(false)) } else { ("editor", None) };
+        self.update_membership(id, Some(role), can_comment, (old_role, old_comment));
     }
 
-    pub fn begin_rename_last_attachment(&mut self) {
-        if let Some(vc) = self.view_card.as_ref() {
-            if let Some(last) = vc.attachments_full.last() {
-                self.input_attachment_name = last.name.clone();
-                self.input_mode = InputMode::RenamingAttachment;
-                self.error_message = None;
-                return;
-            }
+    pub fn toggle_membership_comment(&mut self) {
+        let Some((m, _)) = self.memberships.get(self.membership_index) else { return; };
+        if m.role == "editor" {
+            self.error_message = Some("Editors can always comment; make them a viewer first (R)".to_string());
+            return;
         }
-        self.error_message = Some("No attachments to rename".into());
+        let (id, role, old_comment) = (m.id.clone(), m.role.clone(), m.can_comment);
+        self.update_membership(id, None, Some(!m.may_comment()), (role, old_comment));
     }
 
-    pub fn submit_rename_attachment(&mut self) -> Result<(), String> {
-        let name = self.input_attachment_name.trim().to_string();
-        if name.is_empty() { return Err("Attachment name cannot be empty.".into()); }
-        let att_id = match self.view_card.as_ref() {
-            Some(vc) => {
-                let last = vc.attachments_full.last().ok_or("No attachments on this card")?;
-                last.id.clone()
+    // Shown at once; put back (to `old`) if the server refuses
+    fn update_membership(&mut self, id: String, role: Option<&'static str>, can_comment: Option<bool>, old: (String, Option<bool>)) {
+        if let Some((m, _)) = self.memberships.iter_mut().find(|(m, _)| m.id == id) {
+            if let Some(r) = role {
+                m.role = r.to_string();
             }
-            None => return Err("No card open".into()),
-        };
-        let client = self.ensure_planka_client()?;
-        client.update_attachment_name(&att_id, &name)?;
-        self.input_attachment_name.clear();
-        self.input_mode = InputMode::ViewingCard;
-        self.refresh_open_card_details();
-        self.error_message = Some("Attachment renamed".into());
-        Ok(())
+            m.can_comment = can_comment;
+        }
+        self.error_message = None;
+        let mid = id.clone();
+        self.spawn_job(
+            move |client| client.update_board_membership(&mid, role, can_comment),
+            move |app, res| {
+                if let Err(e) = res {
+                    if let Some((m, _)) = app.memberships.iter_mut().find(|(m, _)| m.id == id) {
+                        (m.role, m.can_comment) = old;
+                    }
+                    app.error_message = Some(e.to_string());
+                }
+            },
+        );
     }
 
-    pub fn begin_duplicate_card(&mut self) {
-        if let Some(vc) = self.view_card.as_ref() {
-            self.input_duplicate_name = format!("{} (copy)", vc.name);
-            self.input_mode = InputMode::DuplicatingCard;
+    pub fn ask_remove_membership(&mut self) {
+        if let Some((m, _)) = self.memberships.get(self.membership_index) {
+            self.membership_confirm = Some(m.id.clone());
             self.error_message = None;
-        } else {
-            self.error_message = Some("No card open".into());
         }
     }
 
-    pub fn submit_duplicate_card(&mut self) -> Result<(), String> {
-        let name = self.input_duplicate_name.trim().to_string();
-        if name.is_empty() { return Err("Duplicate name cannot be empty.".into()); }
-        let card_id = match self.view_card.as_ref() {
-            Some(c) => c.id.clone(),
-            None => return Err("No card open".into()),
-        };
-        let client = self.ensure_planka_client()?;
-        let _new_id = client.duplicate_card(&card_id, 65536, &name)?;
-        self.input_duplicate_name.clear();
-        self.input_mode = InputMode::ViewingCard;
-        // Optional: refresh current list or card view; we just refresh the open card
-        self.refresh_open_card_details();
-        self.error_message = Some("Card duplicated".into());
-        Ok(())
+    pub fn membership_confirm_prompt(&self) -> Option<String> {
+        let id = self.membership_confirm.as_ref()?;
+        let (_, u) = self.memberships.iter().find(|(m, _)| m.id == *id)?;
+        let you = self.my_user_id.as_deref() == Some(u.id.as_str());
+        let board = self.project_name(&self.current_project);
+        Some(if you {
+            format!("Leave {}? You lose access to it. y confirms, any other key cancels", board)
+        } else {
+            format!("Remove {} from {}? y confirms, any other key cancels", u.name, board)
+        })
     }
 
-    pub fn mark_card_notifications_read(&mut self) {
-        let card_id = match self.view_card.as_ref() {
-            Some(c) => c.id.clone(),
-            None => { self.error_message = Some("No card open".into()); return; }
-        };
-        match self.ensure_planka_client() {
-            Ok(client) => {
-                if let Err(e) = client.read_card_notifications(&card_id) {
-                    self.error_message = Some(e);
-                } else {
-                    self.error_message = Some("Card notifications marked as read".into());
+    pub fn confirm_remove_membership(&mut self) {
+        let Some(id) = self.membership_confirm.take() else { return; };
+        let mid = id.clone();
+        self.spawn_job(
+            move |client| client.delete_board_membership(&mid),
+            move |app, res| match res {
+                Ok(()) => {
+                    if let Some(pos) = app.memberships.iter().position(|(m, _)| m.id == id) {
+                        let (_, u) = app.memberships.remove(pos);
+                        app.error_message = Some(format!("{} removed from the board", u.name));
+                    }
+                    app.membership_index = app.membership_index.min(app.memberships.len().saturating_sub(1));
+                    app.refresh_board_members();
                 }
-            }
-            Err(e) => self.error_message = Some(e),
-        }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
+    }
+
+    // Pick from the instance's active users who aren't on the board yet
+    pub fn open_invite(&mut self) {
+        self.input_mode = InputMode::InvitingBoardMember;
+        self.invitable.clear();
+        self.invite_index = 0;
+        self.error_message = None;
+        self.spawn_job(
+            |client| client.fetch_users(),
+            |app, res| match res {
+                Ok(mut users) => {
+                    users.retain(|u| !u.is_deactivated && !app.memberships.iter().any(|(m, _)| m.user_id == u.id));
+                    users.sort_by_key(|u| u.name.to_lowercase());
+                    app.invitable = users;
+                }
+                Err(PlankaError::Server { status: 403, .. }) => {
+                    app.error_message = Some("Only admins and project owners can list users to invite".to_string());
+                }
+                Err(e) => app.error_message = Some(e.to_string()),
+            },
+        );
+    }
+
+    pub fn invite_selected(&mut self, role: &'static str) {
+        let Some(user) = self.invitable.get(self.invite_index).cloned() else { return; };
+        let board = self.current_project.clone();
+        self.input_mode = InputMode::ManagingBoardMembers;
+        self.error_message = Some(format!("Inviting {}…", user.name));
+        self.spawn_job(
+            move |client| client.create_board_membership(&board, &user.id, role, None),
+            move |app, res| match res {
+                Ok(_) => {
+                    app.error_message = Some(format!("{} joined the board as {}", user.name, role));
+                    app.refresh_board_members();
+                }
+                Err(e) => app.error_message = Some(format!("Inviting {} failed: {}", user.name, e)),
+            },
+        );
     }
 
     pub fn begin_create_card_cfg(&mut self) {
@@ -1696,12 +4605,12 @@ impl App {
             Some(c) => c.id.clone(),
             None => return Err("No card open".into()),
         };
-        let client = self.ensure_planka_client()?;
-        let _gid = client.create_card_custom_field_group(&card_id, 65536, Some(&name), None)?;
+        self.card_action(Some("Card custom field group created"), move |client| {
+            let _gid = client.create_card_custom_field_group(&card_id, 65536, Some(&name), None)?;
+            Ok(())
+        });
         self.input_cfg_group_name.clear();
         self.input_mode = InputMode::ViewingCard;
-        self.refresh_open_card_details();
-        self.error_message = Some("Card custom field group created".into());
         Ok(())
     }
     pub fn begin_create_board_cfg(&mut self) {
@@ -1716,12 +4625,12 @@ impl App {
             Some(b) => b,
             None => return Err("Board id not available".into()),
         };
-        let client = self.ensure_planka_client()?;
-        let _gid = client.create_board_custom_field_group(&board_id, 65536, Some(&name), None)?;
+        self.card_action(Some("Board custom field group created"), move |client| {
+            let _gid = client.create_board_custom_field_group(&board_id, 65536, Some(&name), None)?;
+            Ok(())
+        });
         self.input_cfg_group_name.clear();
         self.input_mode = InputMode::ViewingCard;
-        self.refresh_open_card_details();
-        self.error_message = Some("Board custom field group created".into());
         Ok(())
     }
     pub fn begin_create_custom_field(&mut self) {
@@ -1737,12 +4646,12 @@ impl App {
         let name = self.input_custom_field_name.trim().to_string();
         if name.is_empty() { return Err("Field name cannot be empty.".into()); }
         let gid = self.selected_group_id().ok_or("No group selected")?;
-        let client = self.ensure_planka_client()?;
-        let _fid = client.create_custom_field_in_group(&gid, 65536, &name, Some(true))?;
+        self.card_action(Some("Custom field created"), move |client| {
+            let _fid = client.create_custom_field_in_group(&gid, 65536, &name, Some(true))?;
+            Ok(())
+        });
         self.input_custom_field_name.clear();
         self.input_mode = InputMode::ViewingCard;
-        self.refresh_open_card_details();
-        self.error_message = Some("Custom field created".into());
         Ok(())
     }
     pub fn begin_edit_custom_field_value(&mut self) {
@@ -1770,12 +4679,11 @@ impl App {
             (Some(c), Some(t)) => (c.id.clone(), t),
             _ => return Err("No card/group/field selected".into()),
         };
-        let client = self.ensure_planka_client()?;
-        client.update_custom_field_value(&card_id, &gid, &fid, &content)?;
+        self.card_action(Some("Field value updated"), move |client| {
+            client.update_custom_field_value(&card_id, &gid, &fid, &content)
+        });
         self.input_custom_field_value.clear();
         self.input_mode = InputMode::ViewingCard;
-        self.refresh_open_card_details();
-        self.error_message = Some("Field value updated".into());
         Ok(())
     }
     pub fn delete_last_custom_field_value(&mut self) {
@@ -1783,17 +4691,9 @@ impl App {
             (Some(c), Some(t)) => (c.id.clone(), t),
             _ => { self.error_message = Some("No card/group/field selected".into()); return; }
         };
-        match self.ensure_planka_client() {
-            Ok(client) => {
-                if let Err(e) = client.delete_custom_field_value(&card_id, &gid, &fid) {
-                    self.error_message = Some(e);
-                } else {
-                    self.refresh_open_card_details();
-                    self.error_message = Some("Field value deleted".into());
-                }
-            }
-            Err(e) => self.error_message = Some(e),
-        }
+        self.card_action(Some("Field value deleted"), move |client| {
+            client.delete_custom_field_value(&card_id, &gid, &fid)
+        });
     }
     pub fn begin_rename_custom_field_group(&mut self) {
         if let Some(vc) = self.view_card.as_ref() {
@@ -1810,12 +4710,9 @@ impl App {
         let name = self.input_cfg_group_name.trim().to_string();
         if name.is_empty() { return Err("Group name cannot be empty.".into()); }
         let gid = self.selected_group_id().ok_or("No group selected")?;
-        let client = self.ensure_planka_client()?;
-        client.update_custom_field_group(&gid, None, Some(&name))?;
+        self.card_action(Some("Group renamed"), move |client| client.update_custom_field_group(&gid, None, Some(&name)));
         self.input_cfg_group_name.clear();
         self.input_mode = InputMode::ViewingCard;
-        self.refresh_open_card_details();
-        self.error_message = Some("Group renamed".into());
         Ok(())
     }
     pub fn delete_selected_custom_field_group(&mut self) {
@@ -1823,18 +4720,8 @@ impl App {
             Some(g) => g,
             None => { self.error_message = Some("No group selected".into()); return; }
         };
-        match self.ensure_planka_client() {
-            Ok(client) => {
-                if let Err(e) = client.delete_custom_field_group(&gid) {
-                    self.error_message = Some(e);
-                } else {
-                    self.refresh_open_card_details();
-                    self.selected_custom_group_index = 0;
-                    self.error_message = Some("Group deleted".into());
-                }
-            }
-            Err(e) => self.error_message = Some(e),
-        }
+        self.selected_custom_group_index = 0;
+        self.card_action(Some("Group deleted"), move |client| client.delete_custom_field_group(&gid));
     }
 
     pub fn begin_add_checklist_item(&mut self) {
@@ -1850,17 +4737,17 @@ impl App {
             Some(c) => (c.id.clone(), c.task_lists.clone()),
             None => return Err("No card open".into()),
         };
-        let client = self.ensure_planka_client()?;
-        let task_list_id = if let Some((id, _)) = lists.first() {
-            id.clone()
-        } else {
-            client.create_task_list(&card_id, "Checklist")?
-        };
-        let _tid = client.create_task(&task_list_id, &name)?;
+        self.card_action(Some("Checklist item added"), move |client| {
+            let task_list_id = if let Some((id, _)) = lists.first() {
+                id.clone()
+            } else {
+                client.create_task_list(&card_id, "Checklist")?
+            };
+            let _tid = client.create_task(&task_list_id, &name)?;
+            Ok(())
+        });
         self.input_checklist.clear();
         self.input_mode = InputMode::ViewingCard;
-        self.refresh_open_card_details();
-        self.error_message = Some("Checklist item added".into());
         Ok(())
     }
 
@@ -1869,32 +4756,182 @@ impl App {
         let Some(last) = vc.tasks_full.last() else { return; };
         let id = last.id.clone();
         let new_state = !last.is_completed;
-        match self.ensure_planka_client() {
-            Ok(client) => {
-                if let Err(e) = client.update_task(&id, None, Some(new_state)) {
-                    self.error_message = Some(e);
-                } else {
-                    self.refresh_open_card_details();
-                }
-            }
-            Err(e) => self.error_message = Some(e),
-        }
+        self.card_action(None, move |client| client.update_task(&id, None, Some(new_state)));
     }
 
     pub fn delete_last_task(&mut self) {
         let Some(vc) = self.view_card.as_ref() else { return; };
         let Some(last) = vc.tasks_full.last() else { return; };
         let id = last.id.clone();
-        match self.ensure_planka_client() {
-            Ok(client) => {
-                if let Err(e) = client.delete_task(&id) {
-                    self.error_message = Some(e);
-                } else {
-                    self.refresh_open_card_details();
-                    self.error_message = Some("Checklist item deleted".into());
-                }
-            }
-            Err(e) => self.error_message = Some(e),
+        self.card_action(Some("Checklist item deleted"), move |client| client.delete_task(&id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_backoff_doubles_from_base_up_to_cap() {
+        let mut retry = RetryState::default();
+        let delays: Vec<i64> = (0..MAX_OP_ATTEMPTS - 1)
+            .map(|_| {
+                retry.fail("timeout".to_string(), 1000);
+                retry.next_at - 1000
+            })
+            .collect();
+        assert_eq!(delays, vec![5, 10, 20, 40, 80, 160, 320, 640, 1280]);
+        assert_eq!(retry.last_error.as_deref(), Some("timeout"));
+
+        let mut retry = RetryState { attempts: 40, ..Default::default() };
+        retry.fail("timeout".to_string(), 0);
+        assert_eq!(retry.next_at, OP_BACKOFF_MAX_SECS);
+    }
+
+    #[test]
+    fn retry_dead_letters_at_max_attempts() {
+        let mut retry = RetryState::default();
+        for _ in 1..MAX_OP_ATTEMPTS {
+            assert!(!retry.fail("down".to_string(), 0));
+        }
+        assert!(retry.fail("down".to_string(), 0));
+        assert_eq!(retry.attempts, MAX_OP_ATTEMPTS);
+    }
+
+    fn op(id: &str, card: Option<&str>, local: Option<&str>) -> PendingOp {
+        PendingOp {
+            id: id.to_string(),
+            kind: PendingOpKind::Move,
+            project: "b1".to_string(),
+            local_id: local.map(|s| s.to_string()),
+            card_id: card.map(|s| s.to_string()),
+            list_id: None,
+            name: None,
+            due: None,
+            label_id: None,
+            user_id: None,
+            ts: 0,
+            retry: RetryState::default(),
         }
     }
+
+    fn op_ids(ops: Vec<&PendingOp>) -> Vec<&str> {
+        ops.into_iter().map(|o| o.id.as_str()).collect()
+    }
+
+    #[test]
+    fn only_the_oldest_op_of_a_card_is_sent() {
+        let ops = vec![op("a1", Some("c1"), Some("t1")), op("b1", Some("c2"), Some("t2")), op("a2", Some("c1"), Some("t1"))];
+        assert_eq!(op_ids(sendable_ops(&ops, &HashSet::new(), 0)), vec!["a1", "b1"]);
+        // A create and the ops queued behind it share the todo's local id
+        let ops = vec![op("new", None, Some("t3")), op("move", None, Some("t3"))];
+        assert_eq!(op_ids(sendable_ops(&ops, &HashSet::new(), 0)), vec!["new"]);
+    }
+
+    #[test]
+    fn ops_wait_behind_a_failed_or_in_flight_op_of_their_card() {
+        let mut ops = vec![op("a1", Some("c1"), Some("t1")), op("a2", Some("c1"), Some("t1")), op("b1", Some("c2"), None)];
+        ops[0].retry.next_at = 100;
+        assert_eq!(op_ids(sendable_ops(&ops, &HashSet::new(), 0)), vec!["b1"]);
+        assert_eq!(op_ids(sendable_ops(&ops, &HashSet::new(), 100)), vec!["a1", "b1"]);
+        // Nothing new for a card while one of its ops is on the wire, wherever it sits in the queue
+        let in_flight: HashSet<String> = ["a2".to_string()].into();
+        assert_eq!(op_ids(sendable_ops(&ops, &in_flight, 100)), vec!["b1"]);
+    }
+
+    #[test]
+    fn a_revived_op_goes_back_ahead_of_newer_ops_for_its_card() {
+        let ops = vec![op("b1", Some("c2"), None), op("a2", Some("c1"), Some("t1")), op("a3", Some("c1"), Some("t1"))];
+        assert_eq!(requeue_position(&ops, &op("a1", Some("c1"), Some("t1"))), 1);
+        assert_eq!(requeue_position(&ops, &op("z1", Some("c9"), None)), 3);
+    }
+
+    #[test]
+    fn http_errors_are_classified_by_status() {
+        assert!(matches!(PlankaError::http("Fetch card", 401u16, "no"), PlankaError::Unauthorized(_)));
+        assert!(matches!(PlankaError::http("Fetch card", 404u16, "gone"), PlankaError::NotFound(_)));
+        assert!(matches!(PlankaError::http("Fetch card", 200u16, "<!doctype html>"), PlankaError::HtmlSpaResponse(_)));
+        assert!(matches!(
+            PlankaError::http("Create card", 422u16, "invalid"),
+            PlankaError::Server { status: 422, ref op, .. } if op == "Create card"
+        ));
+    }
+
+    #[test]
+    fn http_403_with_a_pending_token_asks_for_the_terms() {
+        let body = r#"{"code":"E_FORBIDDEN","pendingToken":"pt1","termsType":"extended"}"#;
+        assert!(matches!(
+            PlankaError::http("Log in", 403u16, body),
+            PlankaError::TermsRequired { ref pending_token, ref terms_type } if pending_token == "pt1" && terms_type == "extended"
+        ));
+        // Any other 403 is an ordinary refusal
+        assert!(matches!(PlankaError::http("Delete card", 403u16, r#"{"code":"E_FORBIDDEN"}"#), PlankaError::Server { status: 403, .. }));
+        assert!(matches!(PlankaError::http("Delete card", 403u16, "nope"), PlankaError::Server { status: 403, .. }));
+    }
+
+    #[test]
+    fn only_network_and_server_side_errors_are_retried() {
+        for status in [500u16, 502, 503, 429, 408] {
+            assert!(PlankaError::http("Move card", status, "").is_transient(), "{}", status);
+        }
+        for status in [400u16, 401, 403, 404, 409, 422, 200] {
+            assert!(!PlankaError::http("Move card", status, "").is_transient(), "{}", status);
+        }
+        assert!(PlankaError::Network("timed out".to_string()).is_transient());
+        assert!(!PlankaError::Parse("bad json".to_string()).is_transient());
+    }
+
+    #[test]
+    fn http_error_messages_name_the_call() {
+        assert_eq!(PlankaError::http("Create comment", 400u16, "bad").to_string(), "Create comment failed: HTTP 400 - bad");
+        assert_eq!(PlankaError::http("Fetch card", 404u16, "gone").to_string(), "Fetch card failed: HTTP 404 - gone");
+    }
+
+    #[test]
+    fn merge3_takes_the_side_that_changed() {
+        let (base, changed) = ("a".to_string(), "b".to_string());
+        assert!(matches!(merge3(&base, &base, &changed), Merge::Take(ref v) if *v == changed));
+        assert!(matches!(merge3(&base, &changed, &base), Merge::Keep));
+        assert!(matches!(merge3(&base, &base, &base), Merge::Keep));
+    }
+
+    #[test]
+    fn merge3_agrees_when_both_sides_made_the_same_change() {
+        let (base, changed) = (Some("2026-01-01".to_string()), Some("2026-02-01".to_string()));
+        assert!(matches!(merge3(&base, &changed, &changed), Merge::Keep));
+    }
+
+    #[test]
+    fn merge3_conflicts_when_both_sides_differ() {
+        let base = "a".to_string();
+        assert!(matches!(merge3(&base, &"local".to_string(), &"remote".to_string()), Merge::Conflict));
+    }
+
+    fn ids(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn merge_set_keeps_additions_and_removals_from_both_sides() {
+        let base = ids(&["bug", "ui"]);
+        // Local dropped "ui" and added "docs"; remote added "urgent"
+        let local = ids(&["bug", "docs"]);
+        let remote = ids(&["bug", "ui", "urgent"]);
+        assert_eq!(merge_set(&base, &local, &remote, |_| true), ids(&["bug", "urgent", "docs"]));
+    }
+
+    #[test]
+    fn merge_set_drops_local_additions_that_no_longer_exist() {
+        // A member added offline who has since left the board
+        let base = ids(&["u1"]);
+        let local = ids(&["u1", "u2"]);
+        let remote = ids(&["u1"]);
+        assert_eq!(merge_set(&base, &local, &remote, |id| id != "u2"), ids(&["u1"]));
+    }
+
+    #[test]
+    fn merge_set_follows_a_remote_removal() {
+        let base = ids(&["u1", "u2"]);
+        assert_eq!(merge_set(&base, &base, &ids(&["u2"]), |_| true), ids(&["u2"]));
+    }
 }
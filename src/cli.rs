@@ -0,0 +1,238 @@
+// cli.rs
+use crate::app::{App, get_data_file_path};
+use crate::todo::Todo;
+
+const USAGE: &str = "Usage:
+  planky                                   start the TUI
+  planky add <text> [--due <when>] [--project <name>]
+  planky list [--project <name>] [--json]
+  planky done <id> [--project <name>]
+  planky advance <id> [--project <name>]   (alias: doing)
+  planky retreat <id> [--project <name>]
+  planky sync
+
+Any command, and the TUI, accepts --profile <name> to use a separate
+server profile (own login, todos and queue).";
+
+struct Args {
+    positional: Vec<String>,
+    due: Option<String>,
+    project: Option<String>,
+    json: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut out = Args { positional: Vec::new(), due: None, project: None, json: false };
+    let mut it = args.iter();
+    while let Some(a) = it.next() {
+        match a.as_str() {
+            "--due" => {
+                out.due = Some(it.next().cloned().ok_or("--due needs a value")?);
+            }
+            "--project" | "-p" => {
+                out.project = Some(it.next().cloned().ok_or("--project needs a value")?);
+            }
+            "--json" => out.json = true,
+            s if s.starts_with("--") => return Err(format!("Unknown option: {}", s)),
+            _ => out.positional.push(a.clone()),
+        }
+    }
+    Ok(out)
+}
+
+// Remove `--profile <name>` from anywhere in the arguments
+pub fn take_profile(args: &mut Vec<String>) -> Result<Option<String>, String> {
+    let Some(i) = args.iter().position(|a| a == "--profile") else { return Ok(None); };
+    if i + 1 >= args.len() {
+        return Err("--profile needs a value".to_string());
+    }
+    let name = args.remove(i + 1);
+    args.remove(i);
+    if !crate::profile::is_valid_name(&name) {
+        return Err(format!("Invalid profile name: {}", name));
+    }
+    Ok(Some(name))
+}
+
+const COMMANDS: &[&str] = &["add", "list", "done", "doing", "advance", "retreat", "sync"];
+
+// True if the arguments name a subcommand (the TUI should not start). Any
+// word counts, so a mistyped command gets an error instead of the TUI.
+pub fn is_subcommand(args: &[String]) -> bool {
+    args.first().is_some_and(|a| !a.starts_with('-') || a == "--help" || a == "-h")
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let cmd = args.first().map(|s| s.as_str()).unwrap_or("help");
+    if matches!(cmd, "help" | "--help" | "-h") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    if !COMMANDS.contains(&cmd) {
+        return Err(format!("Unknown command: {}\n\n{}", cmd, USAGE));
+    }
+    let opts = parse_args(&args[1..])?;
+
+    let data_path = get_data_file_path();
+    let mut app = App::load_from_file(&data_path);
+    if let Some(ref p) = opts.project {
+        // Boards are needed to resolve "Project/Board" names; switching then resolves the lists
+        app.ensure_boards();
+        app.wait_for_jobs();
+        app.set_current_project(p.as_str());
+    } else {
+        app.ensure_lists();
+    }
+    // Network work runs on the app's worker; there is no event loop to drain it here
+    app.wait_for_jobs();
+
+    match cmd {
+        "add" => {
+            if opts.positional.is_empty() {
+                return Err("add: missing description".to_string());
+            }
+            app.input_description = opts.positional.join(" ");
+            app.input_due_date = opts.due.clone().unwrap_or_default();
+            app.add_todo()?;
+            app.wait_for_jobs();
+            let id = app.todos.last().map(short_id).unwrap_or_default();
+            println!("Added to {} as {}: {}", app.project_name(&app.current_project), id, opts.positional.join(" "));
+        }
+        "list" => {
+            print_list(&app, opts.json)?;
+            return Ok(());
+        }
+        "done" | "doing" | "advance" | "retreat" => {
+            let id = opts
+                .positional
+                .first()
+                .ok_or_else(|| format!("{}: missing <id>", cmd))?;
+            let n = find_task(&app, id).map_err(|e| format!("{}: {}", cmd, e))?;
+            app.selected = n;
+            let idx = app.visible_indices()[n];
+            let (stage, stages) = app.stage_of(&app.todos[idx]);
+            // Without a middle stage "doing" would jump straight to done
+            if cmd == "doing" && stages < 3 {
+                return Err(format!("doing: {} has no Doing stage; use done", app.project_name(&app.current_project)));
+            }
+            match cmd {
+                // Unlike d in the TUI this doesn't toggle, so scripts can repeat it
+                "done" if stage + 1 == stages => {
+                    println!("{} already done", app.todos[idx].description);
+                    return Ok(());
+                }
+                "done" => app.mark_done(),
+                "retreat" => app.retreat(),
+                _ => app.advance(),
+            }
+            app.wait_for_jobs();
+            if let Some(msg) = app.error_message.take() {
+                eprintln!("{}", msg);
+            }
+            let t = &app.todos[idx];
+            println!("{} -> {}", t.description, status_of(&app, t));
+        }
+        "sync" => {
+            if app.planka_config.is_none() {
+                return Err("sync: no Planka login set up; start planky and log in with L first".to_string());
+            }
+            app.sync_all_projects_from_planka();
+            app.process_pending_ops_tick();
+            app.wait_for_jobs();
+            if let Some(msg) = app.error_message.take() {
+                println!("{}", msg);
+            }
+            if app.pending_ops_len() > 0 {
+                println!("{} pending operation(s) still queued", app.pending_ops_len());
+            }
+        }
+        _ => unreachable!("checked against COMMANDS"),
+    }
+
+    app.save_to_file(&data_path)
+}
+
+// Task ids are a prefix of the todo's local id, which survives syncs and
+// reordering; `list` shows this many characters of it
+const SHORT_ID_LEN: usize = 8;
+// Shorter prefixes would too easily pick a task by accident
+const MIN_ID_LEN: usize = 4;
+
+fn short_id(t: &Todo) -> &str {
+    t.local_id.get(..SHORT_ID_LEN).unwrap_or(&t.local_id)
+}
+
+// Position in `visible_indices` of the task a command names, by local id prefix or Planka card id
+fn find_task(app: &App, id: &str) -> Result<usize, String> {
+    let matches: Vec<usize> = app
+        .visible_indices()
+        .iter()
+        .enumerate()
+        .filter(|&(_, &i)| {
+            let t = &app.todos[i];
+            (id.len() >= MIN_ID_LEN && t.local_id.starts_with(id)) || t.planka_card_id.as_deref() == Some(id)
+        })
+        .map(|(n, _)| n)
+        .collect();
+    match matches[..] {
+        [n] => Ok(n),
+        [] if id.len() < MIN_ID_LEN => Err(format!("id '{}' is too short; give at least {} characters", id, MIN_ID_LEN)),
+        [] => Err(format!("no task with id '{}' in {}", id, app.project_name(&app.current_project))),
+        _ => Err(format!("id '{}' matches {} tasks; give more of it", id, matches.len())),
+    }
+}
+
+fn status_of(app: &App, t: &Todo) -> &'static str {
+    let (stage, stages) = app.stage_of(t);
+    if t.done || stage + 1 == stages {
+        "done"
+    } else if stage > 0 {
+        "doing"
+    } else {
+        "todo"
+    }
+}
+
+fn print_list(app: &App, json: bool) -> Result<(), String> {
+    let indices = app.visible_indices();
+    if json {
+        let items: Vec<serde_json::Value> = indices
+            .iter()
+            .map(|&i| {
+                let t = &app.todos[i];
+                let labels: Vec<&str> = t.labels.iter().filter_map(|id| app.label(&t.project, id)).map(|l| l.name.as_str()).collect();
+                let members: Vec<&str> = t.members.iter().filter_map(|id| app.member(id)).map(|u| u.name.as_str()).collect();
+                serde_json::json!({
+                    "id": t.local_id,
+                    "description": t.description,
+                    "due_date": t.due_date,
+                    "created_date": t.created_date,
+                    "status": status_of(app, t),
+                    "stage": app.stage_name(t),
+                    "project": app.project_name(&t.project),
+                    "board_id": t.planka_board_id,
+                    "labels": labels,
+                    "members": members,
+                    "planka_card_id": t.planka_card_id,
+                })
+            })
+            .collect();
+        let out = serde_json::to_string_pretty(&items)
+            .map_err(|e| format!("Failed to write JSON!: {}", e))?;
+        println!("{}", out);
+    } else {
+        for &i in indices.iter() {
+            let t = &app.todos[i];
+            let mark = match status_of(app, t) {
+                "done" => "[d]",
+                "doing" => "[w]",
+                _ => "[ ]",
+            };
+            match t.due_date {
+                Some(ref d) => println!("{} {} {} (due {})", short_id(t), mark, t.description, d),
+                None => println!("{} {} {}", short_id(t), mark, t.description),
+            }
+        }
+    }
+    Ok(())
+}
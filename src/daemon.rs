@@ -1,6 +1,8 @@
 //daemon.rs
 use crate::app::{App, get_data_file_path};
+use crate::planka::{self, PlankaClient};
 use chrono::Local;
+use std::collections::HashSet;
 use std::{thread, time::Duration};
 
 #[cfg(target_os = "linux")]
@@ -13,43 +15,59 @@ use notifica::notify;
 use mac_notification_sys::*;
 
 pub fn start_daemon() -> Result<(), Box<dyn std::error::Error>> {
+    // Planka notifications already announced; None until the first check,
+    // which only records what is unread (the inbox in the TUI shows those)
+    let mut seen: Option<HashSet<String>> = None;
     loop {
         let data_path = get_data_file_path();
         let app = App::load_from_file(&data_path);
         let today = Local::now().format("%Y-%m-%d").to_string();
 
         for todo in &app.todos {
-            if !todo.done {
-                if let Some(due) = &todo.due_date {
-                    if due == &today {
-                        #[cfg(target_os = "linux")]
-                        Notification::new()
-                            .summary("Todo Due today!")
-                            .body(&format!(
-                                "\"{}\" is due today! Don't forget!",
-                                todo.description
-                            ))
-                            .show()?;
-                        #[cfg(target_os = "windows")]
-                        {
-                            notify(
-                                "Planky",
-                                &format!("\"{}\" is due today! Don't forget!", todo.description),
-                            );
-                        }
-                        #[cfg(target_os = "macos")]
-                        {
-                            send_notification(
-                                "Planky",
-                                None,
-                                &format!("\"{}\" is due today! Don't forget!", todo.description),
-                                None,
-                            )?;
-                        }
-                    }
-                }
+            if !todo.done
+                && !todo.archived
+                && let Some(due) = &todo.due_date
+                && due == &today
+            {
+                desktop_notify(
+                    "Todo Due today!",
+                    &format!("\"{}\" is due today! Don't forget!", todo.description),
+                )?;
             }
         }
+        check_planka_notifications(&mut seen)?;
         thread::sleep(Duration::from_secs(60));
     }
 }
+
+// Raise a desktop notification for every unread Planka notification not seen before
+fn check_planka_notifications(seen: &mut Option<HashSet<String>>) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(cfg) = planka::load_config() else { return Ok(()); };
+    let Ok((client, _)) = PlankaClient::from_config(cfg) else { return Ok(()); };
+    // Offline or logged out: try again next round
+    let Ok(list) = client.fetch_notifications() else { return Ok(()); };
+    let first = seen.is_none();
+    let seen = seen.get_or_insert_with(HashSet::new);
+    for n in list.iter().filter(|n| !n.is_read) {
+        if seen.insert(n.id.clone()) && !first {
+            desktop_notify("Planka", &n.summary())?;
+        }
+    }
+    Ok(())
+}
+
+fn desktop_notify(summary: &str, body: &str) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(target_os = "linux")]
+    Notification::new().summary(summary).body(body).show()?;
+    #[cfg(target_os = "windows")]
+    {
+        let _ = summary;
+        notify("Planky", body);
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let _ = summary;
+        send_notification("Planky", None, body, None)?;
+    }
+    Ok(())
+}
@@ -1,10 +1,17 @@
 // main.rs
 
 mod app;
+mod cli;
 mod daemon;
 mod todo;
 mod tui;
 mod planka;
+mod oidc;
+mod profile;
+mod realtime;
+mod secrets;
+mod webhook;
+mod worker;
 
 use crate::app::{App, get_data_file_path};
 use crossterm::{
@@ -15,6 +22,24 @@ use ratatui::{Terminal, backend::CrosstermBackend};
 use std::io::{self};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Non-interactive subcommands run without the daemon or the terminal UI
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::take_profile(&mut args) {
+        Ok(Some(name)) => profile::set_active(&name),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+    if cli::is_subcommand(&args) {
+        if let Err(e) = cli::run(&args) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     std::thread::spawn(|| {
         if let Err(e) = daemon::start_daemon() {
             eprintln!("Daemon error: {}", e);
@@ -28,10 +53,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let data_path = get_data_file_path();
-
     // Load app state or start fresh
-    let mut app = App::load_from_file(&data_path);
+    let mut app = App::load_from_file(get_data_file_path());
     app.start_background_sync();
 
     // Run your TUI event loop (this should block until exit)
@@ -42,8 +65,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
-    // Save app state on exit
-    if let Err(e) = app.save_to_file(&data_path) {
+    // Save app state on exit (the profile may have been switched meanwhile)
+    if let Err(e) = app.save_to_file(get_data_file_path()) {
         eprintln!("Failed to save todos: {}", e);
     }
 
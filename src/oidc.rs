@@ -0,0 +1,136 @@
+// oidc.rs
+//
+// Single sign-on through Planka's OpenID Connect setup. `/api/config` hands
+// out a ready authorization URL; we add a state and nonce, open it in the
+// browser, and the user pastes back the URL it lands on
+// (`<planka>/oidc-callback?code=…`). Planka then exchanges the code
+// (together with our nonce) for an access token. The redirect URI stays
+// Planka's own: the provider only redeems a code for the URI it was issued to.
+use reqwest::Url;
+use std::process::{Command, Stdio};
+
+// A sign-in waiting for the URL the browser ends on
+pub struct SsoLogin {
+    pub state: String,
+    pub nonce: String,
+    pub url: String,
+}
+
+impl SsoLogin {
+    pub fn start(authorization_url: &str) -> Result<Self, String> {
+        let state = crate::todo::new_id();
+        let nonce = crate::todo::new_id();
+        let url = authorization_url_for(authorization_url, &state, &nonce)?;
+        Ok(Self { state, nonce, url })
+    }
+}
+
+// Planka's URL with our state and nonce in place of any it carries
+fn authorization_url_for(base: &str, state: &str, nonce: &str) -> Result<String, String> {
+    let mut url = Url::parse(base).map_err(|e| format!("Invalid SSO authorization URL: {}", e))?;
+    let keep: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| !matches!(k.as_ref(), "state" | "nonce"))
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(keep)
+        .append_pair("state", state)
+        .append_pair("nonce", nonce);
+    Ok(url.to_string())
+}
+
+struct Redirect {
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+}
+
+// Accepts a full URL (pasted from the browser) or a request target ("/oidc-callback?...").
+// The parameters come in the query or, with Planka's default `response_mode=fragment`, after the #.
+fn parse_redirect(s: &str) -> Option<Redirect> {
+    let s = s.trim();
+    let url = Url::parse(s).ok().or_else(|| Url::parse("http://localhost").ok()?.join(s).ok())?;
+    // The fragment is form-encoded like a query; read it as one
+    let fragment = Url::parse(&format!("http://localhost/?{}", url.fragment().unwrap_or_default())).ok()?;
+    let mut r = Redirect { code: None, state: None, error: None };
+    for (k, v) in url.query_pairs().chain(fragment.query_pairs()) {
+        match k.as_ref() {
+            "code" => r.code = Some(v.into_owned()),
+            "state" => r.state = Some(v.into_owned()),
+            "error_description" => r.error = Some(v.into_owned()),
+            "error" => {
+                r.error.get_or_insert(v.into_owned());
+            }
+            _ => {}
+        }
+    }
+    Some(r)
+}
+
+// Authorization code from the URL the browser was redirected to
+pub fn code_from_redirect(s: &str, state: &str) -> Result<String, String> {
+    let r = parse_redirect(s).ok_or("That is not a URL")?;
+    if r.state.as_deref() != Some(state) {
+        return Err("That URL doesn't belong to this sign-in (state mismatch)".to_string());
+    }
+    if let Some(e) = r.error {
+        return Err(format!("Sign-in refused: {}", e));
+    }
+    r.code.ok_or_else(|| "No authorization code in that URL".to_string())
+}
+
+pub fn open_browser(url: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut cmd = Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        // `start` would split the URL at every &
+        let mut c = Command::new("rundll32");
+        c.arg("url.dll,FileProtocolHandler");
+        c
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut cmd = Command::new("xdg-open");
+    cmd.arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Couldn't open a browser: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTH: &str = "https://idp.example/auth?client_id=planka&redirect_uri=https%3A%2F%2Fplanka.example%2Foidc-callback&response_mode=fragment&state=theirs";
+
+    #[test]
+    fn keeps_plankas_redirect_uri_and_sets_our_state_and_nonce() {
+        let url = Url::parse(&authorization_url_for(AUTH, "s1", "n1").unwrap()).unwrap();
+        let pairs: Vec<(String, String)> = url.query_pairs().map(|(k, v)| (k.into_owned(), v.into_owned())).collect();
+        let get = |key: &str| pairs.iter().filter(|(k, _)| k == key).map(|(_, v)| v.as_str()).collect::<Vec<_>>();
+        assert_eq!(get("redirect_uri"), vec!["https://planka.example/oidc-callback"]);
+        assert_eq!(get("client_id"), vec!["planka"]);
+        assert_eq!(get("state"), vec!["s1"]);
+        assert_eq!(get("nonce"), vec!["n1"]);
+    }
+
+    #[test]
+    fn reads_the_code_from_the_query_or_the_fragment() {
+        assert_eq!(code_from_redirect("https://planka.example/oidc-callback?code=abc&state=s1", "s1"), Ok("abc".to_string()));
+        assert_eq!(code_from_redirect("https://planka.example/oidc-callback#code=a%2Fb&state=s1", "s1"), Ok("a/b".to_string()));
+    }
+
+    #[test]
+    fn rejects_a_foreign_state_or_a_refusal() {
+        assert!(code_from_redirect("https://planka.example/oidc-callback?code=abc&state=other", "s1").is_err());
+        assert_eq!(
+            code_from_redirect("https://planka.example/oidc-callback#error=access_denied&error_description=Nope&state=s1", "s1"),
+            Err("Sign-in refused: Nope".to_string())
+        );
+    }
+}
@@ -1,16 +1,21 @@
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder, Response};
 use reqwest::blocking::multipart::Form;
-use reqwest::header::CONTENT_TYPE;
+use reqwest::cookie::{CookieStore, Jar};
+use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value, Map};
-use std::fs::{create_dir_all, File};
-#[cfg(debug_assertions)]
-use std::fs::OpenOptions;
+use std::fs::File;
 use std::io::{BufReader, BufWriter};
 #[cfg(debug_assertions)]
 use std::io::Write as IoWrite;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use crate::profile;
+use crate::secrets::{self, SecretStore};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 #[cfg(debug_assertions)]
 fn log_http_request(method: &str, url: &str, headers: &[(&str, &str)], body: Option<&str>) {
@@ -70,22 +75,12 @@ static INIT_LOG_ONCE: std::sync::Once = std::sync::Once::new();
 
 #[cfg(debug_assertions)]
 fn log_file_path() -> PathBuf {
-    let base = std::env::var_os("XDG_CONFIG_HOME")
-        .map(PathBuf::from)
-        .unwrap_or_else(|| {
-            std::env::var_os("HOME")
-                .map(PathBuf::from)
-                .map(|p| p.join(".config"))
-                .unwrap_or_else(|| PathBuf::from("."))
-        });
-    let dir = base.join("Planky");
-    create_dir_all(&dir).ok();
-    dir.join("planka_debug.log")
+    profile::base_dir().join("planka_debug.log")
 }
 
 #[cfg(debug_assertions)]
 fn log_to_file_line(s: &str) {
-    if let Ok(mut f) = OpenOptions::new().create(true).append(true).open(log_file_path()) {
+    if let Ok(mut f) = secrets::open_private(&log_file_path(), true) {
         let _ = writeln!(f, "{}", s);
     }
 }
@@ -111,26 +106,61 @@ fn init_log_notice() {
 pub struct PlankaConfig {
     pub server_url: String,
     pub email_or_username: String,
+    // Only written to disk when `secret_store` is Config; see secrets.rs
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub password: String,
     pub token: Option<String>,
+    #[serde(default)]
+    pub secret_store: SecretStore,
+    // Board id -> workflow chosen in the TUI; boards without one are guessed from list names
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub workflows: HashMap<String, Vec<WorkflowStage>>,
+    // Logged in through single sign-on (oidc.rs): there is no password to renew the token with
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sso: bool,
+    // Local receiver for Planka webhooks; see webhook.rs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<WebhookConfig>,
+    // Pre-issued API key, sent as X-Api-Key in place of a token; nothing to log in with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    // Log in with `withHttpOnlyToken`: Planka then only honours the token together
+    // with its session cookie, kept in `http_only_token`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub with_http_only_token: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_only_token: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    // Address the receiver listens on
+    pub listen: String,
+    // URL Planka posts to; defaults to http://<listen>/ (set it when Planka can't reach that, e.g. from Docker)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    // Planka sends it back as a Bearer token with every event
+    pub access_token: String,
+    // Id of the registered webhook; None while switched off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+impl WebhookConfig {
+    pub fn target_url(&self) -> String {
+        self.url.clone().unwrap_or_else(|| format!("http://{}/", self.listen))
+    }
 }
 
 pub fn config_path() -> PathBuf {
-    let base = std::env::var_os("XDG_CONFIG_HOME")
-        .map(PathBuf::from)
-        .unwrap_or_else(|| {
-            std::env::var_os("HOME")
-                .map(PathBuf::from)
-                .map(|p| p.join(".config"))
-                .unwrap_or_else(|| PathBuf::from("."))
-        });
-    let dir = base.join("Planky");
-    create_dir_all(&dir).ok();
-    dir.join("planka.json")
+    profile::path("planka.json")
 }
 
 pub fn load_config() -> Option<PlankaConfig> {
-    let path = config_path();
+    load_config_from(&config_path())
+}
+
+pub fn load_config_from(path: &Path) -> Option<PlankaConfig> {
     let file = File::open(path).ok()?;
     let reader = BufReader::new(file);
     serde_json::from_reader(reader).ok()
@@ -138,9 +168,13 @@ pub fn load_config() -> Option<PlankaConfig> {
 
 pub fn save_config(cfg: &PlankaConfig) -> Result<(), String> {
     let path = config_path();
-    let file = File::create(path).map_err(|e| format!("Open planka config failed: {}", e))?;
+    let file = secrets::open_private(&path, false).map_err(|e| format!("Open planka config failed: {}", e))?;
     let writer = BufWriter::new(file);
-    serde_json::to_writer_pretty(writer, cfg)
+    let mut on_disk = cfg.clone();
+    if on_disk.secret_store != SecretStore::Config {
+        on_disk.password.clear();
+    }
+    serde_json::to_writer_pretty(writer, &on_disk)
         .map_err(|e| format!("Write planka config failed: {}", e))
 }
 
@@ -159,54 +193,266 @@ struct CardDetailsItem {
     due_date: Option<String>,
 }
 
+#[derive(Clone, Debug)]
+pub enum PlankaError {
+    // 401: token expired or revoked, or bad credentials on login
+    Unauthorized(String),
+    NotFound(String),
+    // Request never got a response (DNS, connect, TLS, timeout, reading the body)
+    Network(String),
+    // Any other error status; `op` names the call, e.g. "Create comment"
+    Server { op: String, status: u16, body: String },
+    Parse(String),
+    // 2xx with the web app's index.html: usually a wrong server URL or a reverse proxy rewrite
+    HtmlSpaResponse(String),
+    // Local problems: missing server URL, unreadable upload file, HTTP client setup
+    Config(String),
+    // 403 on login: the user must accept the terms ("general" or "extended")
+    // before the pending token can be traded for an access token
+    TermsRequired { pending_token: String, terms_type: String },
+}
+
+impl PlankaError {
+    // Classify a failed (or HTML) response of the `op` call by status code
+    pub fn http(op: &str, status: impl Into<u16>, body: impl Into<String>) -> Self {
+        let status = status.into();
+        let body = body.into();
+        if status == 403
+            && let Ok(v) = serde_json::from_str::<Value>(&body)
+            && let Some(pending) = v.get("pendingToken").and_then(|x| x.as_str())
+        {
+            return PlankaError::TermsRequired {
+                pending_token: pending.to_string(),
+                terms_type: v.get("termsType").and_then(|x| x.as_str()).unwrap_or("general").to_string(),
+            };
+        }
+        match status {
+            401 => PlankaError::Unauthorized(format!("{} failed: HTTP 401 - {}", op, body)),
+            404 => PlankaError::NotFound(format!("{} failed: HTTP 404 - {}", op, body)),
+            200..=299 => PlankaError::HtmlSpaResponse(body),
+            _ => PlankaError::Server { op: op.to_string(), status, body },
+        }
+    }
+
+    // Worth retrying later without user intervention
+    pub fn is_transient(&self) -> bool {
+        match self {
+            PlankaError::Network(_) => true,
+            PlankaError::Server { status, .. } => *status >= 500 || *status == 429 || *status == 408,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for PlankaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlankaError::Unauthorized(msg)
+            | PlankaError::NotFound(msg)
+            | PlankaError::Network(msg)
+            | PlankaError::Parse(msg)
+            | PlankaError::Config(msg) => write!(f, "{}", msg),
+            PlankaError::Server { op, status, body } => write!(f, "{} failed: HTTP {} - {}", op, status, body),
+            PlankaError::HtmlSpaResponse(_) => {
+                write!(f, "Server returned an HTML page instead of JSON (check the Planka server URL)")
+            }
+            PlankaError::TermsRequired { .. } => write!(f, "The terms of service must be accepted first"),
+        }
+    }
+}
+
+impl From<PlankaError> for String {
+    fn from(e: PlankaError) -> Self {
+        e.to_string()
+    }
+}
+
 pub struct PlankaClient {
     pub base_url: String,
     pub client: Client,
-    pub token: String,
+    token: Mutex<String>,
+    // Shared with `login`, so the session cookie of an HTTP-only login lands here
+    cookies: Arc<Jar>,
+    // Saved credentials, used to log in again when the token is rejected
+    cfg: PlankaConfig,
+    profile: String,
+    login_required: AtomicBool,
+}
+
+// Authenticated requests go through `PlankaClient::execute` so an expired
+// token is renewed in one place
+trait SendAuthed {
+    fn send_authed(self, client: &PlankaClient) -> reqwest::Result<Response>;
+}
+
+impl SendAuthed for RequestBuilder {
+    fn send_authed(self, client: &PlankaClient) -> reqwest::Result<Response> {
+        client.execute(self)
+    }
 }
 
 impl PlankaClient {
-    pub fn from_config(mut cfg: PlankaConfig) -> Result<(Self, PlankaConfig), String> {
+    pub fn from_config(mut cfg: PlankaConfig) -> Result<(Self, PlankaConfig), PlankaError> {
         #[cfg(debug_assertions)]
         init_log_notice();
         #[cfg(debug_assertions)]
         log_debug("PlankaClient::from_config called");
         if cfg.server_url.trim().is_empty() {
-            return Err("Planka server URL is empty".into());
+            return Err(PlankaError::Config("Planka server URL is empty".into()));
         }
-        if cfg.token.is_none() {
+        let cookies = cookie_jar(&cfg);
+        // An API key or a pre-issued token makes the login unnecessary
+        if cfg.token.is_none() && cfg.api_key.is_none() {
             #[cfg(debug_assertions)]
             log_debug("No existing token in config; attempting login");
-            let token = login(&cfg.server_url, &cfg.email_or_username, &cfg.password)?;
+            let password = secrets::password(&cfg)
+                .ok_or_else(|| PlankaError::Unauthorized("No saved Planka password".to_string()))?;
+            let token = login(&cfg, &password, &cookies)?;
             cfg.token = Some(token);
+            cfg.http_only_token = http_only_cookie(&cookies, &cfg.server_url);
             #[cfg(debug_assertions)]
             log_debug("Login successful; token stored in config");
             let _ = save_config(&cfg);
         }
         #[cfg(debug_assertions)]
-        if cfg.token.is_some() {
+        if cfg.api_key.is_some() {
+            log_debug("Using API key from config");
+        } else if cfg.token.is_some() {
             log_debug("Using existing token from config");
         }
-        let token = cfg.token.clone().unwrap();
-        let client = Client::builder()
-            .cookie_store(true)
-            .build()
-            .map_err(|e| format!("HTTP client build failed: {}", e))?;
+        let token = cfg.token.clone().unwrap_or_default();
+        let client = http_client(&cookies)?;
         Ok((
             Self {
                 base_url: cfg.server_url.clone(),
                 client,
-                token,
+                token: Mutex::new(token),
+                cookies,
+                cfg: cfg.clone(),
+                profile: profile::active(),
+                login_required: AtomicBool::new(false),
             },
             cfg,
         ))
     }
 
+    // Client without a login, for the endpoints used before one exists
+    // (server config, token exchange)
+    pub fn anonymous(server_url: &str) -> Result<Self, PlankaError> {
+        if server_url.trim().is_empty() {
+            return Err(PlankaError::Config("Planka server URL is empty".into()));
+        }
+        let cookies = Arc::new(Jar::default());
+        let client = http_client(&cookies)?;
+        let cfg = PlankaConfig { server_url: server_url.to_string(), ..Default::default() };
+        Ok(Self {
+            base_url: cfg.server_url.clone(),
+            client,
+            token: Mutex::new(String::new()),
+            cookies,
+            cfg,
+            profile: profile::active(),
+            login_required: AtomicBool::new(false),
+        })
+    }
+
+    pub fn token(&self) -> String {
+        self.token.lock().unwrap().clone()
+    }
+
+    // Session cookie of an HTTP-only login, if the server set one
+    pub fn http_only_token(&self) -> Option<String> {
+        http_only_cookie(&self.cookies, &self.base_url)
+    }
+
+    // Config with the current token and session cookie, which differ from the
+    // ones passed to `from_config` after a re-login
+    pub fn config(&self) -> PlankaConfig {
+        PlankaConfig {
+            token: Some(self.token()).filter(|t| !t.is_empty()),
+            http_only_token: self.http_only_token(),
+            ..self.cfg.clone()
+        }
+    }
+
+    // Credentials for connections that don't go through `execute` (the realtime socket)
+    pub fn auth_headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = match self.cfg.api_key {
+            Some(ref key) => vec![(API_KEY_HEADER, key.clone())],
+            None => vec![("Authorization", self.auth_header())],
+        };
+        if let Some(cookie) = self.http_only_token() {
+            headers.push(("Cookie", format!("{}={}", HTTP_ONLY_COOKIE, cookie)));
+        }
+        headers
+    }
+
+    // The token was rejected and no stored password could renew it
+    pub fn login_required(&self) -> bool {
+        self.login_required.load(Ordering::Relaxed)
+    }
+
     fn auth_header(&self) -> String {
-        format!("Bearer {}", self.token)
+        format!("Bearer {}", self.token())
+    }
+
+    // Send a request; on 401 log in again with the saved credentials and retry once
+    fn execute(&self, rb: RequestBuilder) -> reqwest::Result<Response> {
+        let mut req = rb.build()?;
+        // With an API key, authenticated requests carry it instead of the Bearer
+        // header; there is no token to renew
+        if let Some(ref key) = self.cfg.api_key
+            && req.headers_mut().remove(AUTHORIZATION).is_some()
+        {
+            if let Ok(v) = HeaderValue::from_str(key) {
+                req.headers_mut().insert(API_KEY_HEADER, v);
+            }
+            return self.client.execute(req);
+        }
+        // Bodies that can't be cloned (file uploads) and anonymous requests are not retried
+        let retry = if req.headers().contains_key(AUTHORIZATION) { req.try_clone() } else { None };
+        let resp = self.client.execute(req)?;
+        if resp.status().as_u16() != 401 {
+            return Ok(resp);
+        }
+        let Some(mut req) = retry else { return Ok(resp); };
+        let Some(auth) = self.relogin().and_then(|t| format!("Bearer {}", t).parse().ok()) else {
+            return Ok(resp);
+        };
+        req.headers_mut().insert(AUTHORIZATION, auth);
+        self.client.execute(req)
+    }
+
+    fn relogin(&self) -> Option<String> {
+        if self.login_required() {
+            return None;
+        }
+        let Some(password) = secrets::password(&self.cfg) else {
+            self.login_required.store(true, Ordering::Relaxed);
+            return None;
+        };
+        #[cfg(debug_assertions)]
+        log_debug("Token rejected (401); logging in again with saved credentials");
+        match login(&self.cfg, &password, &self.cookies) {
+            Ok(token) => {
+                *self.token.lock().unwrap() = token.clone();
+                // The app may have switched profiles while this request ran
+                if profile::active() == self.profile {
+                    let _ = save_config(&self.config());
+                }
+                Some(token)
+            }
+            Err(e) => {
+                // Wrong password (or new terms) now; a network error may pass, so only give up on those
+                if matches!(e, PlankaError::Unauthorized(_) | PlankaError::TermsRequired { .. }) {
+                    self.login_required.store(true, Ordering::Relaxed);
+                }
+                None
+            }
+        }
     }
 
-    pub fn fetch_boards(&self) -> Result<Vec<PlankaBoard>, String> {
+    pub fn fetch_boards(&self) -> Result<Vec<PlankaBoard>, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let auth = self.auth_header();
         // 1) Get all projects
@@ -224,19 +470,19 @@ impl PlankaClient {
             .header("Authorization", auth.clone())
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send()
-            .map_err(|e| format!("GET {} failed: {}", projects_url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", projects_url, e)))?;
         let status = resp.status();
         let text = resp
             .text()
-            .map_err(|e| format!("read {} failed: {}", projects_url, e))?;
+            .map_err(|e| PlankaError::Network(format!("read {} failed: {}", projects_url, e)))?;
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() {
-            return Err(format!("List projects failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("List projects", status, text));
         }
         let v: Value =
-            serde_json::from_str(&text).map_err(|e| format!("parse projects failed: {}", e))?;
+            serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse projects failed: {}", e)))?;
         let mut proj_names: HashMap<String, String> = HashMap::new();
         let mut project_ids: Vec<String> = Vec::new();
         if let Some(arr) = v.as_array() {
@@ -312,10 +558,10 @@ impl PlankaClient {
                 .header("Authorization", auth.clone())
                 .header("Accept", "application/json")
                 .header("X-Requested-With", "XMLHttpRequest")
-                .send()
-                .map_err(|e| format!("GET {} failed: {}", url, e))?;
+                .send_authed(self)
+                .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
             let status = resp.status();
-            let text = resp.text().map_err(|e| format!("read {} failed: {}", url, e))?;
+            let text = resp.text().map_err(|e| PlankaError::Network(format!("read {} failed: {}", url, e)))?;
             #[cfg(debug_assertions)]
             log_http_response(status.as_u16(), &text);
             if !status.is_success() {
@@ -326,7 +572,7 @@ impl PlankaClient {
                 continue;
             }
             let v: Value = serde_json::from_str(&text)
-                .map_err(|e| format!("parse boards failed: {}", e))?;
+                .map_err(|e| PlankaError::Parse(format!("parse boards failed: {}", e)))?;
             if let Some(arr) = v.as_array() {
                 for b in arr {
                     if let (Some(id), Some(name)) = (
@@ -374,14 +620,36 @@ impl PlankaClient {
         Ok(boards)
     }
 
-    pub fn resolve_lists(&self, board_name: &str) -> Result<PlankaLists, String> {
+    pub fn resolve_lists(&self, board_id: &str) -> Result<PlankaLists, PlankaError> {
+        let lists = self.fetch_board_lists(board_id)?;
+        if let Some(flow) = self.cfg.workflows.get(board_id) {
+            let stages: Vec<WorkflowStage> = flow
+                .iter()
+                .filter(|s| lists.iter().any(|(id, _)| *id == s.list_id))
+                .cloned()
+                .collect();
+            if stages.len() < 2 {
+                return Err(PlankaError::Config(
+                    "The board's workflow refers to lists that no longer exist; edit it under Tab → Workflow".to_string(),
+                ));
+            }
+            return Ok(PlankaLists { board_id: board_id.to_string(), stages });
+        }
+        let stages = guess_workflow(&lists).ok_or_else(|| {
+            PlankaError::NotFound("Board needs at least two lists; set its workflow under Tab → Workflow".to_string())
+        })?;
+        Ok(PlankaLists { board_id: board_id.to_string(), stages })
+    }
+
+    // Active lists of a board in board order, as (id, name)
+    pub fn fetch_board_lists(&self, board_id: &str) -> Result<Vec<(String, String)>, PlankaError> {
         let boards = self.fetch_boards()?;
         let board = boards
             .into_iter()
-            .find(|b| b.name.eq_ignore_ascii_case(board_name))
-            .ok_or_else(|| format!("Board '{}' not found on Planka", board_name))?;
+            .find(|b| b.id == board_id)
+            .ok_or_else(|| PlankaError::NotFound(format!("Board {} not found on Planka", board_id)))?;
         let base = self.base_url.trim_end_matches('/');
-        let mut lists: Vec<(String, String)> = Vec::new(); // (id, name)
+        let mut lists: Vec<(f64, String, String)> = Vec::new(); // (position, id, name)
 
         // Attempt 1: GET /api/boards/{id}?include=lists
         {
@@ -404,10 +672,10 @@ impl PlankaClient {
                 .header("Authorization", auth.clone())
                 .header("Accept", "application/json")
                 .header("X-Requested-With", "XMLHttpRequest")
-                .send()
-                .map_err(|e| format!("GET {} failed: {}", url, e))?;
+                .send_authed(self)
+                .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
             let status = resp.status();
-            let text = resp.text().map_err(|e| format!("read {} failed: {}", url, e))?;
+            let text = resp.text().map_err(|e| PlankaError::Network(format!("read {} failed: {}", url, e)))?;
             #[cfg(debug_assertions)]
             log_http_response(status.as_u16(), &text);
             if status.is_success() && !text.trim_start().starts_with('<') {
@@ -423,8 +691,9 @@ impl PlankaClient {
                                 l.get("name")
                                     .and_then(|x| x.as_str())
                                     .or_else(|| l.get("title").and_then(|x| x.as_str())),
-                            ) {
-                                lists.push((id.to_string(), name.to_string()));
+                            ) && is_active_list(l)
+                            {
+                                lists.push((position_of(l), id.to_string(), name.to_string()));
                             }
                         }
                     }
@@ -454,10 +723,10 @@ impl PlankaClient {
                     .header("Authorization", auth.clone())
                     .header("Accept", "application/json")
                     .header("X-Requested-With", "XMLHttpRequest")
-                    .send()
-                    .map_err(|e| format!("GET {} failed: {}", url, e))?;
+                    .send_authed(self)
+                    .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
                 let status = resp.status();
-                let text = resp.text().map_err(|e| format!("read {} failed: {}", url, e))?;
+                let text = resp.text().map_err(|e| PlankaError::Network(format!("read {} failed: {}", url, e)))?;
                 #[cfg(debug_assertions)]
                 log_http_response(status.as_u16(), &text);
                 if status.is_success() && !text.trim_start().starts_with('<') {
@@ -481,8 +750,9 @@ impl PlankaClient {
                                     l.get("name")
                                         .and_then(|x| x.as_str())
                                         .or_else(|| l.get("title").and_then(|x| x.as_str())),
-                                ) {
-                                    lists.push((id.to_string(), name.to_string()));
+                                ) && is_active_list(l)
+                                {
+                                    lists.push((position_of(l), id.to_string(), name.to_string()));
                                 }
                             }
                         }
@@ -492,35 +762,14 @@ impl PlankaClient {
         }
 
         if lists.is_empty() {
-            return Err("No lists found for board".into());
-        }
-
-        // Match names to todo/doing/done (case- and space-insensitive)
-        let mut todo_id: Option<String> = None;
-        let mut doing_id: Option<String> = None;
-        let mut done_id: Option<String> = None;
-        for (id, name) in &lists {
-            let n = name.to_lowercase().replace(' ', "");
-            if n.contains("todo") || n.contains("to-do") || n.contains("to_do") || n.contains("to.do") {
-                if todo_id.is_none() { todo_id = Some(id.clone()); }
-            } else if n.contains("doing") || n.contains("inprogress") || n.contains("in-progress") || n.contains("in_progress") {
-                if doing_id.is_none() { doing_id = Some(id.clone()); }
-            } else if n.contains("done") || n.contains("completed") || n.contains("complete") {
-                if done_id.is_none() { done_id = Some(id.clone()); }
-            }
+            return Err(PlankaError::NotFound("No lists found for board".into()));
         }
-        let todo = todo_id.ok_or_else(|| "Couldn't find a 'Todo' list on board".to_string())?;
-        let doing = doing_id.unwrap_or_else(|| todo.clone());
-        let done = done_id.ok_or_else(|| "Couldn't find a 'Done' list on board".to_string())?;
-        Ok(PlankaLists {
-            board_id: board.id,
-            todo_list_id: todo,
-            doing_list_id: doing,
-            done_list_id: done,
-        })
+
+        lists.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(lists.into_iter().map(|(_, id, name)| (id, name)).collect())
     }
 
-    pub fn create_project(&self, name: &str) -> Result<String, String> {
+    pub fn create_project(&self, name: &str) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/projects", base);
         let auth = self.auth_header();
@@ -543,20 +792,20 @@ impl PlankaClient {
             .header("X-Requested-With", "XMLHttpRequest")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() {
-            return Err(format!("Create project failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Create project", status, text));
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse create_project failed: {}", e))?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse create_project failed: {}", e)))?;
         v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str())
             .or_else(|| v.get("id").and_then(|x| x.as_str()))
             .map(|s| s.to_string())
-            .ok_or_else(|| "Create project response missing id".to_string())
+            .ok_or_else(|| PlankaError::Parse("Create project response missing id".to_string()))
     }
 
     pub fn create_board_with_import(
@@ -567,7 +816,7 @@ impl PlankaClient {
         import_type: Option<&str>,         // e.g. Some("trello")
         import_file: Option<&str>,         // local path to import file
         request_id: Option<&str>,
-    ) -> Result<String, String> {
+    ) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/projects/{}/boards", base, project_id);
         let auth = self.auth_header();
@@ -582,7 +831,7 @@ impl PlankaClient {
             form = form.text("importType", it.to_string());
             form = form
                 .file("importFile", path)
-                .map_err(|e| format!("Read import file failed: {}", e))?;
+                .map_err(|e| PlankaError::Config(format!("Read import file failed: {}", e)))?;
         } else if let Some(it) = import_type {
             // spec allows importType; harmless if sent without file
             form = form.text("importType", it.to_string());
@@ -594,28 +843,28 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .multipart(form)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() {
-            return Err(format!("Create board (multipart) failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Create board (multipart)", status, text));
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse create_board failed: {}", e))?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse create_board failed: {}", e)))?;
         v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str())
             .or_else(|| v.get("id").and_then(|x| x.as_str()))
             .map(|s| s.to_string())
-            .ok_or_else(|| "Create board response missing id".to_string())
+            .ok_or_else(|| PlankaError::Parse("Create board response missing id".to_string()))
     }
 
-    pub fn create_board(&self, project_id: &str, name: &str) -> Result<String, String> {
+    pub fn create_board(&self, project_id: &str, name: &str) -> Result<String, PlankaError> {
         // Default simple case (no import): spec requires multipart; we call the multipart variant.
         self.create_board_with_import(project_id, 65536, name, None, None, None)
     }
 
-    pub fn create_card(&self, list_id: &str, name: &str, due: Option<&str>) -> Result<String, String> {
+    pub fn create_card(&self, list_id: &str, name: &str, due: Option<&str>) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/lists/{}/cards", base, list_id);
         let auth = self.auth_header();
@@ -644,32 +893,37 @@ impl PlankaClient {
             .header("X-Requested-With", "XMLHttpRequest")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
-        let text = resp.text().map_err(|e| format!("read {} failed: {}", url, e))?;
+        let text = resp.text().map_err(|e| PlankaError::Network(format!("read {} failed: {}", url, e)))?;
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() {
-            return Err(format!("Create card failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Create card", status, text));
         }
         let v: Value = serde_json::from_str(&text)
-            .map_err(|e| format!("parse create_card failed: {}", e))?;
+            .map_err(|e| PlankaError::Parse(format!("parse create_card failed: {}", e)))?;
         if let Some(id) = v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str())
             .or_else(|| v.get("id").and_then(|x| x.as_str())) {
             Ok(id.to_string())
         } else {
-            Err("Create card response missing id".into())
+            Err(PlankaError::Parse("Create card response missing id".into()))
         }
     }
 
-    pub fn move_card(&self, card_id: &str, to_list_id: &str) -> Result<(), String> {
+    pub fn move_card(&self, card_id: &str, to_list_id: &str) -> Result<(), PlankaError> {
+        self.move_card_to(card_id, to_list_id, 65536.0)
+    }
+
+    // Move a card to a list at a given position (also used to reorder within a list)
+    pub fn move_card_to(&self, card_id: &str, to_list_id: &str, position: f64) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}", base, card_id);
         let auth = self.auth_header();
         let body = json!({
             "listId": to_list_id,
-            "position": 65536
+            "position": position
         });
         #[cfg(debug_assertions)]
         log_http_request(
@@ -684,19 +938,45 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() {
-            return Err(format!("Move card failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Move card", status, text));
         }
         Ok(())
     }
 
-    pub fn update_card(&self, card_id: &str, name: Option<&str>, due: Option<&str>) -> Result<(), String> {
+    // Markdown description; None (or empty) clears it
+    pub fn update_card_description(&self, card_id: &str, description: Option<&str>) -> Result<(), PlankaError> {
+        let base = self.base_url.trim_end_matches('/');
+        let url = format!("{}/api/cards/{}", base, card_id);
+        let auth = self.auth_header();
+        let body = json!({ "description": description.filter(|d| !d.is_empty()) });
+        #[cfg(debug_assertions)]
+        log_http_request("PATCH", &url, &[("Authorization", auth.as_str()), ("Accept", "application/json"), ("Content-Type", "application/json")], Some(&body.to_string()));
+        let resp = self.client
+            .patch(&url)
+            .header("Authorization", auth)
+            .header("Accept", "application/json")
+            .header(CONTENT_TYPE, "application/json")
+            .json(&body)
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
+        let status = resp.status();
+        let text = resp.text().unwrap_or_default();
+        #[cfg(debug_assertions)]
+        log_http_response(status.as_u16(), &text);
+        if !status.is_success() {
+            return Err(PlankaError::http("Update card description", status, text));
+        }
+        Ok(())
+    }
+
+    pub fn update_card(&self, card_id: &str, name: Option<&str>, due: Option<&str>) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}", base, card_id);
         let auth = self.auth_header();
@@ -730,19 +1010,19 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() {
-            return Err(format!("Update card failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Update card", status, text));
         }
         Ok(())
     }
 
-    pub fn delete_card(&self, card_id: &str) -> Result<(), String> {
+    pub fn delete_card(&self, card_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}", base, card_id);
         let auth = self.auth_header();
@@ -763,19 +1043,19 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send()
-            .map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() {
-            return Err(format!("Delete card failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Delete card", status, text));
         }
         Ok(())
     }
 
-    pub fn fetch_card_created(&self, card_id: &str) -> Result<Option<String>, String> {
+    pub fn fetch_card_created(&self, card_id: &str) -> Result<Option<String>, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}", base, card_id);
         let auth = self.auth_header();
@@ -796,8 +1076,8 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send()
-            .map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status = resp.status().as_u16();
         let body = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
@@ -806,11 +1086,48 @@ impl PlankaClient {
             return Ok(None);
         }
         let parsed: CardDetailsRes =
-            serde_json::from_str(&body).map_err(|e| format!("Parse card details failed: {}", e))?;
+            serde_json::from_str(&body).map_err(|e| PlankaError::Parse(format!("Parse card details failed: {}", e)))?;
         Ok(parsed.item.created_at)
     }
 
-    pub fn fetch_cards(&self, list_id: &str) -> Result<Vec<PlankaCard>, String> {
+    // Ok(false) only on a definite 404; other failures are errors so callers don't drop local data
+    pub fn card_exists(&self, card_id: &str) -> Result<bool, PlankaError> {
+        let base = self.base_url.trim_end_matches('/');
+        let url = format!("{}/api/cards/{}", base, card_id);
+        let auth = self.auth_header();
+        #[cfg(debug_assertions)]
+        log_http_request(
+            "GET",
+            &url,
+            &[
+                ("Authorization", auth.as_str()),
+                ("Accept", "application/json"),
+                ("X-Requested-With", "XMLHttpRequest"),
+            ],
+            None,
+        );
+        let resp = self
+            .client
+            .get(&url)
+            .header("Authorization", auth)
+            .header("Accept", "application/json")
+            .header("X-Requested-With", "XMLHttpRequest")
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
+        let status = resp.status();
+        let text = resp.text().unwrap_or_default();
+        #[cfg(debug_assertions)]
+        log_http_response(status.as_u16(), &text);
+        if status.as_u16() == 404 {
+            return Ok(false);
+        }
+        if !status.is_success() || text.trim_start().starts_with('<') {
+            return Err(PlankaError::http("Check card", status, text));
+        }
+        Ok(true)
+    }
+
+    pub fn fetch_cards(&self, list_id: &str) -> Result<Vec<PlankaCard>, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let auth = self.auth_header();
         // Try 1: /api/lists/{id}?include=cards
@@ -831,7 +1148,7 @@ impl PlankaClient {
             .header("Authorization", auth.clone())
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send();
+            .send_authed(self);
         if let Ok(r) = resp1 {
             let status = r.status();
             let text = r.text().unwrap_or_default();
@@ -844,6 +1161,8 @@ impl PlankaClient {
                         .and_then(|i| i.get("cards"))
                         .and_then(|x| x.as_array())
                     {
+                        let card_labels = card_links_of(&v, "cardLabels", "labelId");
+                        let card_members = card_links_of(&v, "cardMemberships", "userId");
                         let mut out = Vec::new();
                         for c in arr {
                             if let (Some(id), Some(name)) = (
@@ -852,7 +1171,7 @@ impl PlankaClient {
                             ) {
                                 let due = c.get("dueDate").and_then(|x| x.as_str()).map(|s| s.to_string());
                                 let created = c.get("createdAt").and_then(|x| x.as_str()).map(|s| s.to_string());
-                                out.push(PlankaCard { id: id.to_string(), name: name.to_string(), due, created });
+                                out.push(PlankaCard { id: id.to_string(), name: name.to_string(), due, created, position: position_of(c), label_ids: links_of(&card_labels, id), member_ids: links_of(&card_members, id) });
                             }
                         }
                         // Enrich created from card details if missing
@@ -887,8 +1206,8 @@ impl PlankaClient {
             .header("Authorization", auth.clone())
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send()
-            .map_err(|e| format!("GET {} failed: {}", url2, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url2, e)))?;
         let status = resp2.status();
         let text = resp2.text().unwrap_or_default();
         #[cfg(debug_assertions)]
@@ -896,7 +1215,9 @@ impl PlankaClient {
         if !status.is_success() || text.trim_start().starts_with('<') {
             return Ok(vec![]);
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse cards failed: {}", e))?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse cards failed: {}", e)))?;
+        let card_labels = card_links_of(&v, "cardLabels", "labelId");
+        let card_members = card_links_of(&v, "cardMemberships", "userId");
         let mut out = Vec::new();
         if let Some(arr) = v.as_array() {
             for c in arr {
@@ -906,7 +1227,7 @@ impl PlankaClient {
                 ) {
                     let due = c.get("dueDate").and_then(|x| x.as_str()).map(|s| s.to_string());
                     let created = c.get("createdAt").and_then(|x| x.as_str()).map(|s| s.to_string());
-                    out.push(PlankaCard { id: id.to_string(), name: name.to_string(), due, created });
+                    out.push(PlankaCard { id: id.to_string(), name: name.to_string(), due, created, position: position_of(c), label_ids: links_of(&card_labels, id), member_ids: links_of(&card_members, id) });
                 }
             }
         } else if let Some(items) = v.get("items").and_then(|x| x.as_array()) {
@@ -917,7 +1238,7 @@ impl PlankaClient {
                 ) {
                     let due = c.get("dueDate").and_then(|x| x.as_str()).map(|s| s.to_string());
                     let created = c.get("createdAt").and_then(|x| x.as_str()).map(|s| s.to_string());
-                    out.push(PlankaCard { id: id.to_string(), name: name.to_string(), due, created });
+                    out.push(PlankaCard { id: id.to_string(), name: name.to_string(), due, created, position: position_of(c), label_ids: links_of(&card_labels, id), member_ids: links_of(&card_members, id) });
                 }
             }
         }
@@ -932,7 +1253,7 @@ impl PlankaClient {
         Ok(out)
     }
 
-    pub fn fetch_card_details(&self, card_id: &str) -> Result<PlankaCardDetails, String> {
+    pub fn fetch_card_details(&self, card_id: &str) -> Result<PlankaCardDetails, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}", base, card_id);
         let auth = self.auth_header();
@@ -952,19 +1273,19 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send()
-            .map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() || text.trim_start().starts_with('<') {
-            return Err(format!("Fetch card details failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Fetch card details", status, text));
         }
         let v: Value = serde_json::from_str(&text)
-            .map_err(|e| format!("Parse card details failed: {}", e))?;
+            .map_err(|e| PlankaError::Parse(format!("Parse card details failed: {}", e)))?;
         let item = v.get("item").and_then(|x| x.as_object())
-            .ok_or_else(|| "Missing item".to_string())?;
+            .ok_or_else(|| PlankaError::Parse("Missing item".to_string()))?;
 
         let id = item.get("id").and_then(|x| x.as_str()).unwrap_or(card_id).to_string();
         let name = item.get("name").and_then(|x| x.as_str()).unwrap_or("").to_string();
@@ -980,6 +1301,8 @@ impl PlankaClient {
 
         let mut list_name: Option<String> = None;
         let mut labels: Vec<String> = Vec::new();
+        let mut label_ids: Vec<String> = Vec::new();
+        let mut member_ids: Vec<String> = Vec::new();
         let mut attachments: Vec<String> = Vec::new();
         let mut tasks: Vec<(String, bool)> = Vec::new();
         let mut attachments_full: Vec<PlankaAttachment> = Vec::new();
@@ -1016,6 +1339,7 @@ impl PlankaClient {
                     let cl_card = cl.get("cardId").and_then(|x| x.as_str());
                     if cl_card == Some(id.as_str()) {
                         if let Some(lid) = cl.get("labelId").and_then(|x| x.as_str()) {
+                            label_ids.push(lid.to_string());
                             if let Some(name) = label_by_id.get(lid) {
                                 labels.push(name.clone());
                             }
@@ -1023,6 +1347,15 @@ impl PlankaClient {
                     }
                 }
             }
+            if let Some(arr) = inc.get("cardMemberships").and_then(|x| x.as_array()) {
+                for cm in arr {
+                    if cm.get("cardId").and_then(|x| x.as_str()) == Some(id.as_str())
+                        && let Some(uid) = cm.get("userId").and_then(|x| x.as_str())
+                    {
+                        member_ids.push(uid.to_string());
+                    }
+                }
+            }
             // attachments: take names and collect full models
             if let Some(arr) = inc.get("attachments").and_then(|x| x.as_array()) {
                 for a in arr {
@@ -1118,11 +1451,11 @@ impl PlankaClient {
         }
         custom_field_groups.sort_by(|a, b| a.name.as_deref().unwrap_or("").to_lowercase().cmp(&b.name.as_deref().unwrap_or("").to_lowercase()));
         Ok(PlankaCardDetails {
-            id, name, description, due, is_due_completed, created, updated, list_name, labels, attachments, tasks,
+            id, name, description, due, is_due_completed, created, updated, list_id, list_name, labels, label_ids, member_ids, attachments, tasks,
             board_id, attachments_full, tasks_full, task_lists, custom_field_groups,
         })
     }
-    pub fn fetch_comments(&self, card_id: &str) -> Result<Vec<PlankaComment>, String> {
+    pub fn fetch_comments(&self, card_id: &str) -> Result<Vec<PlankaComment>, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/comments", base, card_id);
         let auth = self.auth_header();
@@ -1142,16 +1475,16 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send()
-            .map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() || text.trim_start().starts_with('<') {
-            return Err(format!("Fetch comments failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Fetch comments", status, text));
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("Parse comments failed: {}", e))?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("Parse comments failed: {}", e)))?;
         // Map userId -> user name
         let mut user_name_by_id: HashMap<String, String> = HashMap::new();
         if let Some(included) = v.get("included").and_then(|x| x.as_object()) {
@@ -1180,7 +1513,7 @@ impl PlankaClient {
         Ok(out)
     }
 
-    pub fn create_comment(&self, card_id: &str, text: &str) -> Result<String, String> {
+    pub fn create_comment(&self, card_id: &str, text: &str) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/comments", base, card_id);
         let auth = self.auth_header();
@@ -1204,22 +1537,22 @@ impl PlankaClient {
             .header("X-Requested-With", "XMLHttpRequest")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() {
-            return Err(format!("Create comment failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Create comment", status, text));
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("Parse create comment failed: {}", e))?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("Parse create comment failed: {}", e)))?;
         v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str())
             .map(|s| s.to_string())
-            .ok_or_else(|| "Create comment response missing id".to_string())
+            .ok_or_else(|| PlankaError::Parse("Create comment response missing id".to_string()))
     }
 
-    pub fn update_comment(&self, comment_id: &str, text: &str) -> Result<(), String> {
+    pub fn update_comment(&self, comment_id: &str, text: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/comments/{}", base, comment_id);
         let auth = self.auth_header();
@@ -1231,17 +1564,17 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update comment failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update comment", status, text)); }
         Ok(())
     }
 
-    pub fn delete_comment(&self, comment_id: &str) -> Result<(), String> {
+    pub fn delete_comment(&self, comment_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/comments/{}", base, comment_id);
         let auth = self.auth_header();
@@ -1250,17 +1583,17 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send()
-            .map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete comment failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete comment", status, text)); }
         Ok(())
     }
 
-    pub fn create_link_attachment(&self, card_id: &str, url_str: &str, name: &str) -> Result<String, String> {
+    pub fn create_link_attachment(&self, card_id: &str, url_str: &str, name: &str) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/attachments", base, card_id);
         let auth = self.auth_header();
@@ -1275,18 +1608,18 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .multipart(form)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create attachment failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("Parse create attachment failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Create attachment response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create attachment", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("Parse create attachment failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Create attachment response missing id".to_string()))
     }
 
-    pub fn delete_attachment(&self, attachment_id: &str) -> Result<(), String> {
+    pub fn delete_attachment(&self, attachment_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/attachments/{}", base, attachment_id);
         let auth = self.auth_header();
@@ -1296,17 +1629,17 @@ impl PlankaClient {
             .delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send()
-            .map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete attachment failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete attachment", status, text)); }
         Ok(())
     }
 
-    pub fn create_task_list(&self, card_id: &str, name: &str) -> Result<String, String> {
+    pub fn create_task_list(&self, card_id: &str, name: &str) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/task-lists", base, card_id);
         let auth = self.auth_header();
@@ -1318,18 +1651,18 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create task list failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("Parse create task list failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Create task list response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create task list", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("Parse create task list failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Create task list response missing id".to_string()))
     }
 
-    pub fn create_task(&self, task_list_id: &str, name: &str) -> Result<String, String> {
+    pub fn create_task(&self, task_list_id: &str, name: &str) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/task-lists/{}/tasks", base, task_list_id);
         let auth = self.auth_header();
@@ -1341,18 +1674,18 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create task failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("Parse create task failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Create task response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create task", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("Parse create task failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Create task response missing id".to_string()))
     }
 
-    pub fn update_task(&self, task_id: &str, name: Option<&str>, is_completed: Option<bool>) -> Result<(), String> {
+    pub fn update_task(&self, task_id: &str, name: Option<&str>, is_completed: Option<bool>) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/tasks/{}", base, task_id);
         let auth = self.auth_header();
@@ -1367,17 +1700,17 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update task failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update task", status, text)); }
         Ok(())
     }
 
-    pub fn delete_task(&self, task_id: &str) -> Result<(), String> {
+    pub fn delete_task(&self, task_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/tasks/{}", base, task_id);
         let auth = self.auth_header();
@@ -1386,17 +1719,17 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send()
-            .map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete task failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete task", status, text)); }
         Ok(())
     }
 
-    pub fn fetch_board_actions(&self, board_id: &str, before_id: Option<&str>) -> Result<Vec<PlankaAction>, String> {
+    pub fn fetch_board_actions(&self, board_id: &str, before_id: Option<&str>) -> Result<Vec<PlankaAction>, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = match before_id {
             Some(b) => format!("{}/api/boards/{}/actions?beforeId={}", base, board_id, b),
@@ -1409,16 +1742,16 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send()
-            .map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() || text.trim_start().starts_with('<') {
-            return Err(format!("Fetch board actions failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Fetch board actions", status, text));
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse actions failed: {}", e))?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse actions failed: {}", e)))?;
         let mut out = Vec::new();
         if let Some(items) = v.get("items").and_then(|x| x.as_array()) {
             for a in items {
@@ -1434,7 +1767,7 @@ impl PlankaClient {
         Ok(out)
     }
 
-    pub fn fetch_card_actions(&self, card_id: &str, before_id: Option<&str>) -> Result<Vec<PlankaAction>, String> {
+    pub fn fetch_card_actions(&self, card_id: &str, before_id: Option<&str>) -> Result<Vec<PlankaAction>, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = match before_id {
             Some(b) => format!("{}/api/cards/{}/actions?beforeId={}", base, card_id, b),
@@ -1447,16 +1780,16 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send()
-            .map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() || text.trim_start().starts_with('<') {
-            return Err(format!("Fetch card actions failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Fetch card actions", status, text));
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse actions failed: {}", e))?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse actions failed: {}", e)))?;
         let mut out = Vec::new();
         if let Some(items) = v.get("items").and_then(|x| x.as_array()) {
             for a in items {
@@ -1472,7 +1805,7 @@ impl PlankaClient {
         Ok(out)
     }
 
-    pub fn add_label_to_card(&self, card_id: &str, label_id: &str) -> Result<(), String> {
+    pub fn add_label_to_card(&self, card_id: &str, label_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/card-labels", base, card_id);
         let auth = self.auth_header();
@@ -1484,17 +1817,17 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Add label failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Add label", status, text)); }
         Ok(())
     }
 
-    pub fn remove_label_from_card(&self, card_id: &str, label_id: &str) -> Result<(), String> {
+    pub fn remove_label_from_card(&self, card_id: &str, label_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/card-labels/labelId:{}", base, card_id, label_id);
         let auth = self.auth_header();
@@ -1503,17 +1836,17 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send()
-            .map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Remove label failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Remove label", status, text)); }
         Ok(())
     }
 
-    pub fn add_member_to_card(&self, card_id: &str, user_id: &str) -> Result<(), String> {
+    pub fn add_member_to_card(&self, card_id: &str, user_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/card-memberships", base, card_id);
         let auth = self.auth_header();
@@ -1525,17 +1858,17 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Add member failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Add member", status, text)); }
         Ok(())
     }
 
-    pub fn remove_member_from_card(&self, card_id: &str, user_id: &str) -> Result<(), String> {
+    pub fn remove_member_from_card(&self, card_id: &str, user_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/card-memberships/userId:{}", base, card_id, user_id);
         let auth = self.auth_header();
@@ -1544,17 +1877,17 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send()
-            .map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Remove member failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Remove member", status, text)); }
         Ok(())
     }
 
-    pub fn create_project_manager(&self, project_id: &str, user_id: &str) -> Result<String, String> {
+    pub fn create_project_manager(&self, project_id: &str, user_id: &str) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/projects/{}/project-managers", base, project_id);
         let auth = self.auth_header();
@@ -1566,18 +1899,18 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create project manager failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse project manager failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create project manager", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse project manager failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn delete_project_manager(&self, id: &str) -> Result<(), String> {
+    pub fn delete_project_manager(&self, id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/project-managers/{}", base, id);
         let auth = self.auth_header();
@@ -1586,17 +1919,17 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send()
-            .map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete project manager failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete project manager", status, text)); }
         Ok(())
     }
 
-    pub fn fetch_projects(&self) -> Result<Vec<PlankaProject>, String> {
+    pub fn fetch_projects(&self) -> Result<Vec<PlankaProject>, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/projects", base);
         let auth = self.auth_header();
@@ -1606,16 +1939,16 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send()
-            .map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() || text.trim_start().starts_with('<') {
-            return Err(format!("Fetch projects failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Fetch projects", status, text));
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse projects failed: {}", e))?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse projects failed: {}", e)))?;
         let iter = v.as_array()
             .cloned()
             .or_else(|| v.get("items").and_then(|x| x.as_array()).cloned())
@@ -1632,7 +1965,7 @@ impl PlankaClient {
         Ok(out)
     }
 
-    pub fn fetch_project_details(&self, project_id: &str) -> Result<PlankaProjectDetails, String> {
+    pub fn fetch_project_details(&self, project_id: &str) -> Result<PlankaProjectDetails, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/projects/{}?include=boards", base, project_id);
         let auth = self.auth_header();
@@ -1642,17 +1975,17 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send()
-            .map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() || text.trim_start().starts_with('<') {
-            return Err(format!("Fetch project failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Fetch project", status, text));
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse project failed: {}", e))?;
-        let item = v.get("item").and_then(|x| x.as_object()).ok_or_else(|| "Missing item".to_string())?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse project failed: {}", e)))?;
+        let item = v.get("item").and_then(|x| x.as_object()).ok_or_else(|| PlankaError::Parse("Missing item".to_string()))?;
         let id = item.get("id").and_then(|x| x.as_str()).unwrap_or(project_id).to_string();
         let name = item.get("name").and_then(|x| x.as_str()).or_else(|| item.get("title").and_then(|x| x.as_str())).unwrap_or("").to_string();
         let description = item.get("description").and_then(|x| x.as_str()).map(|s| s.to_string());
@@ -1685,7 +2018,7 @@ impl PlankaClient {
         is_favorite: Option<bool>,
         background_type: Option<&str>,
         background_gradient: Option<&str>,
-    ) -> Result<(), String> {
+    ) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/projects/{}", base, project_id);
         let auth = self.auth_header();
@@ -1704,17 +2037,17 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update project failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update project", status, text)); }
         Ok(())
     }
 
-    pub fn delete_project(&self, project_id: &str) -> Result<(), String> {
+    pub fn delete_project(&self, project_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/projects/{}", base, project_id);
         let auth = self.auth_header();
@@ -1723,17 +2056,17 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send()
-            .map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete project failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete project", status, text)); }
         Ok(())
     }
 
-    pub fn fetch_task_list(&self, task_list_id: &str) -> Result<PlankaTaskListDetails, String> {
+    pub fn fetch_task_list(&self, task_list_id: &str) -> Result<PlankaTaskListDetails, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/task-lists/{}", base, task_list_id);
         let auth = self.auth_header();
@@ -1743,17 +2076,17 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send()
-            .map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
         if !status.is_success() || text.trim_start().starts_with('<') {
-            return Err(format!("Fetch task list failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Fetch task list", status, text));
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse task list failed: {}", e))?;
-        let item = v.get("item").and_then(|x| x.as_object()).ok_or_else(|| "Missing item".to_string())?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse task list failed: {}", e)))?;
+        let item = v.get("item").and_then(|x| x.as_object()).ok_or_else(|| PlankaError::Parse("Missing item".to_string()))?;
         let id = item.get("id").and_then(|x| x.as_str()).unwrap_or(task_list_id).to_string();
         let name = item.get("name").and_then(|x| x.as_str()).or_else(|| item.get("title").and_then(|x| x.as_str())).unwrap_or("").to_string();
         let mut tasks: Vec<PlankaTask> = Vec::new();
@@ -1776,7 +2109,7 @@ impl PlankaClient {
         position: Option<i64>,
         show_on_front_of_card: Option<bool>,
         hide_completed_tasks: Option<bool>,
-    ) -> Result<(), String> {
+    ) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/task-lists/{}", base, task_list_id);
         let auth = self.auth_header();
@@ -1793,17 +2126,17 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update task list failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update task list", status, text)); }
         Ok(())
     }
 
-    pub fn delete_task_list(&self, task_list_id: &str) -> Result<(), String> {
+    pub fn delete_task_list(&self, task_list_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/task-lists/{}", base, task_list_id);
         let auth = self.auth_header();
@@ -1812,13 +2145,13 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send()
-            .map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete task list failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete task list", status, text)); }
         Ok(())
     }
 
@@ -1833,7 +2166,7 @@ impl PlankaClient {
         always_display_card_creator: Option<bool>,
         expand_task_lists_by_default: Option<bool>,
         is_subscribed: Option<bool>,
-    ) -> Result<(), String> {
+    ) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/boards/{}", base, board_id);
         let auth = self.auth_header();
@@ -1854,15 +2187,15 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update board failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update board", status, text)); }
         Ok(())
     }
 
-    pub fn delete_board(&self, board_id: &str) -> Result<(), String> {
+    pub fn delete_board(&self, board_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/boards/{}", base, board_id);
         let auth = self.auth_header();
@@ -1871,18 +2204,18 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete board failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete board", status, text)); }
         Ok(())
     }
 
-    pub fn fetch_board_details(&self, board_id: &str) -> Result<PlankaBoardDetails, String> {
+    pub fn fetch_board_details(&self, board_id: &str) -> Result<PlankaBoardDetails, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         // include lists and labels explicitly
-        let url = format!("{}/api/boards/{}?include=lists,labels", base, board_id);
+        let url = format!("{}/api/boards/{}?include=lists,labels,users", base, board_id);
         let auth = self.auth_header();
         #[cfg(debug_assertions)]
         log_http_request("GET", &url, &[("Authorization", auth.as_str()), ("Accept", "application/json"), ("X-Requested-With", "XMLHttpRequest")], None);
@@ -1890,23 +2223,41 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send().map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
         if !status.is_success() || text.trim_start().starts_with('<') {
-            return Err(format!("Fetch board failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Fetch board", status, text));
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse board failed: {}", e))?;
-        let item = v.get("item").and_then(|x| x.as_object()).ok_or_else(|| "Missing item".to_string())?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse board failed: {}", e)))?;
+        let item = v.get("item").and_then(|x| x.as_object()).ok_or_else(|| PlankaError::Parse("Missing item".to_string()))?;
         let id = item.get("id").and_then(|x| x.as_str()).unwrap_or(board_id).to_string();
         let name = item.get("name").and_then(|x| x.as_str()).or_else(|| item.get("title").and_then(|x| x.as_str())).unwrap_or("").to_string();
         let project_id = item.get("projectId").and_then(|x| x.as_str()).map(|s| s.to_string());
         let mut lists = Vec::new();
         let mut labels = Vec::new();
+        let mut members = Vec::new();
+        let mut memberships = Vec::new();
         if let Some(inc) = v.get("included").and_then(|x| x.as_object()) {
+            if let Some(arr) = inc.get("boardMemberships").and_then(|x| x.as_array()) {
+                memberships = arr.iter().filter_map(parse_board_membership).collect();
+            }
+            // Users of the board: those with a board membership (all included users on older servers)
+            let member_ids: Option<Vec<&str>> = inc.get("boardMemberships").and_then(|x| x.as_array())
+                .map(|arr| arr.iter().filter_map(|m| m.get("userId").and_then(|x| x.as_str())).collect());
+            if let Some(arr) = inc.get("users").and_then(|x| x.as_array()) {
+                for u in arr {
+                    let user = parse_user(u);
+                    if member_ids.as_ref().is_none_or(|ids| ids.contains(&user.id.as_str())) {
+                        members.push(user);
+                    }
+                }
+            }
             if let Some(arr) = inc.get("lists").and_then(|x| x.as_array()) {
-                for l in arr {
+                let mut ordered: Vec<&Value> = arr.iter().filter(|l| is_active_list(l)).collect();
+                ordered.sort_by(|a, b| position_of(a).total_cmp(&position_of(b)));
+                for l in ordered {
                     if let (Some(lid), Some(nm)) = (l.get("id").and_then(|x| x.as_str()), l.get("name").and_then(|x| x.as_str()).or_else(|| l.get("title").and_then(|x| x.as_str()))) {
                         lists.push((lid.to_string(), nm.to_string()));
                     }
@@ -1914,19 +2265,18 @@ impl PlankaClient {
             }
             if let Some(arr) = inc.get("labels").and_then(|x| x.as_array()) {
                 for lab in arr {
-                    let lid = lab.get("id").and_then(|x| x.as_str());
-                    let nm = lab.get("name").and_then(|x| x.as_str()).or_else(|| lab.get("title").and_then(|x| x.as_str()));
-                    let color = lab.get("color").and_then(|x| x.as_str());
-                    if let (Some(lid), Some(nm), Some(color)) = (lid, nm, color) {
-                        labels.push((lid.to_string(), nm.to_string(), color.to_string()));
+                    if let Some(label) = parse_label(lab) {
+                        labels.push(label);
                     }
                 }
             }
         }
-        Ok(PlankaBoardDetails { id, name, project_id, lists, labels })
+        labels.sort_by(|a, b| a.position.total_cmp(&b.position));
+        members.sort_by_key(|a| a.name.to_lowercase());
+        Ok(PlankaBoardDetails { id, name, project_id, lists, labels, members, memberships })
     }
 
-    pub fn create_board_membership(&self, board_id: &str, user_id: &str, role: &str, can_comment: Option<bool>) -> Result<String, String> {
+    pub fn create_board_membership(&self, board_id: &str, user_id: &str, role: &str, can_comment: Option<bool>) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/boards/{}/board-memberships", base, board_id);
         let auth = self.auth_header();
@@ -1941,16 +2291,16 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create board membership failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse create board membership failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create board membership", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse create board membership failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn update_board_membership(&self, membership_id: &str, role: Option<&str>, can_comment: Option<bool>) -> Result<(), String> {
+    pub fn update_board_membership(&self, membership_id: &str, role: Option<&str>, can_comment: Option<bool>) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/board-memberships/{}", base, membership_id);
         let auth = self.auth_header();
@@ -1965,15 +2315,15 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update board membership failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update board membership", status, text)); }
         Ok(())
     }
 
-    pub fn delete_board_membership(&self, membership_id: &str) -> Result<(), String> {
+    pub fn delete_board_membership(&self, membership_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/board-memberships/{}", base, membership_id);
         let auth = self.auth_header();
@@ -1982,15 +2332,15 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete board membership failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete board membership", status, text)); }
         Ok(())
     }
 
-    pub fn create_label(&self, board_id: &str, color: &str, name: Option<&str>, position: Option<i64>) -> Result<String, String> {
+    pub fn create_label(&self, board_id: &str, color: &str, name: Option<&str>, position: Option<i64>) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/boards/{}/labels", base, board_id);
         let auth = self.auth_header();
@@ -2005,16 +2355,16 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create label failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse create label failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create label", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse create label failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn update_label(&self, label_id: &str, position: Option<i64>, name: Option<&str>, color: Option<&str>) -> Result<(), String> {
+    pub fn update_label(&self, label_id: &str, position: Option<i64>, name: Option<&str>, color: Option<&str>) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/labels/{}", base, label_id);
         let auth = self.auth_header();
@@ -2030,15 +2380,15 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update label failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update label", status, text)); }
         Ok(())
     }
 
-    pub fn delete_label(&self, label_id: &str) -> Result<(), String> {
+    pub fn delete_label(&self, label_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/labels/{}", base, label_id);
         let auth = self.auth_header();
@@ -2047,15 +2397,15 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete label failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete label", status, text)); }
         Ok(())
     }
 
-    pub fn clear_list(&self, list_id: &str) -> Result<(), String> {
+    pub fn clear_list(&self, list_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/lists/{}/clear", base, list_id);
         let auth = self.auth_header();
@@ -2066,15 +2416,15 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&json!({}))
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Clear list failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Clear list", status, text)); }
         Ok(())
     }
 
-    pub fn create_list(&self, board_id: &str, name: &str, list_type: Option<&str>, position: Option<i64>, color: Option<&str>) -> Result<String, String> {
+    pub fn create_list(&self, board_id: &str, name: &str, list_type: Option<&str>, position: Option<i64>, color: Option<&str>) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/boards/{}/lists", base, board_id);
         let auth = self.auth_header();
@@ -2090,16 +2440,16 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create list failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse create list failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create list", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse create list failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn fetch_list_details(&self, list_id: &str) -> Result<PlankaListDetails, String> {
+    pub fn fetch_list_details(&self, list_id: &str) -> Result<PlankaListDetails, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/lists/{}", base, list_id);
         let auth = self.auth_header();
@@ -2109,31 +2459,71 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .header("X-Requested-With", "XMLHttpRequest")
-            .send().map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
         if !status.is_success() || text.trim_start().starts_with('<') {
-            return Err(format!("Fetch list failed: HTTP {} - {}", status, text));
+            return Err(PlankaError::http("Fetch list", status, text));
         }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse list failed: {}", e))?;
-        let item = v.get("item").and_then(|x| x.as_object()).ok_or_else(|| "Missing item".to_string())?;
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse list failed: {}", e)))?;
+        let item = v.get("item").and_then(|x| x.as_object()).ok_or_else(|| PlankaError::Parse("Missing item".to_string()))?;
         let id = item.get("id").and_then(|x| x.as_str()).unwrap_or(list_id).to_string();
         let name = item.get("name").and_then(|x| x.as_str()).or_else(|| item.get("title").and_then(|x| x.as_str())).unwrap_or("").to_string();
+        let card_labels = card_links_of(&v, "cardLabels", "labelId");
+        let card_members = card_links_of(&v, "cardMemberships", "userId");
         let mut cards = Vec::new();
         if let Some(arr) = v.get("included").and_then(|i| i.get("cards")).and_then(|x| x.as_array()) {
             for c in arr {
                 if let (Some(cid), Some(nm)) = (c.get("id").and_then(|x| x.as_str()), c.get("name").and_then(|x| x.as_str())) {
                     let due = c.get("dueDate").and_then(|x| x.as_str()).map(|s| s.to_string());
                     let created = c.get("createdAt").and_then(|x| x.as_str()).map(|s| s.to_string());
-                    cards.push(PlankaCard { id: cid.to_string(), name: nm.to_string(), due, created });
+                    cards.push(PlankaCard { id: cid.to_string(), name: nm.to_string(), due, created, position: position_of(c), label_ids: links_of(&card_labels, cid), member_ids: links_of(&card_members, cid) });
                 }
             }
         }
+        cards.sort_by(|a, b| a.position.total_cmp(&b.position));
         Ok(PlankaListDetails { id, name, cards })
     }
 
-    pub fn update_list(&self, list_id: &str, board_id: Option<&str>, list_type: Option<&str>, position: Option<i64>, name: Option<&str>, color: Option<&str>) -> Result<(), String> {
+    // Cards of a list through the list-cards endpoint, optionally only those
+    // assigned to one of `filter_user_ids`
+    pub fn fetch_list_cards(&self, list_id: &str, filter_user_ids: &[String]) -> Result<Vec<PlankaCard>, PlankaError> {
+        let base = self.base_url.trim_end_matches('/');
+        let mut url = format!("{}/api/lists/{}/cards", base, list_id);
+        if !filter_user_ids.is_empty() {
+            url = format!("{}?filterUserIds={}", url, filter_user_ids.join(","));
+        }
+        let auth = self.auth_header();
+        #[cfg(debug_assertions)]
+        log_http_request("GET", &url, &[("Authorization", auth.as_str()), ("Accept", "application/json"), ("X-Requested-With", "XMLHttpRequest")], None);
+        let resp = self.client.get(&url)
+            .header("Authorization", auth)
+            .header("Accept", "application/json")
+            .header("X-Requested-With", "XMLHttpRequest")
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
+        let status = resp.status();
+        let text = resp.text().unwrap_or_default();
+        #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
+        if !status.is_success() || text.trim_start().starts_with('<') {
+            return Err(PlankaError::http("Fetch list cards", status, text));
+        }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse cards failed: {}", e)))?;
+        let card_labels = card_links_of(&v, "cardLabels", "labelId");
+        let card_members = card_links_of(&v, "cardMemberships", "userId");
+        let mut cards = Vec::new();
+        for c in v.get("items").and_then(|x| x.as_array()).into_iter().flatten() {
+            if let (Some(cid), Some(nm)) = (c.get("id").and_then(|x| x.as_str()), c.get("name").and_then(|x| x.as_str())) {
+                let due = c.get("dueDate").and_then(|x| x.as_str()).map(|s| s.to_string());
+                let created = c.get("createdAt").and_then(|x| x.as_str()).map(|s| s.to_string());
+                cards.push(PlankaCard { id: cid.to_string(), name: nm.to_string(), due, created, position: position_of(c), label_ids: links_of(&card_labels, cid), member_ids: links_of(&card_members, cid) });
+            }
+        }
+        cards.sort_by(|a, b| a.position.total_cmp(&b.position));
+        Ok(cards)
+    }
+
+    pub fn update_list(&self, list_id: &str, board_id: Option<&str>, list_type: Option<&str>, position: Option<i64>, name: Option<&str>, color: Option<&str>) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/lists/{}", base, list_id);
         let auth = self.auth_header();
@@ -2151,15 +2541,15 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update list failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update list", status, text)); }
         Ok(())
     }
 
-    pub fn delete_list(&self, list_id: &str) -> Result<(), String> {
+    pub fn delete_list(&self, list_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/lists/{}", base, list_id);
         let auth = self.auth_header();
@@ -2168,15 +2558,15 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete list failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete list", status, text)); }
         Ok(())
     }
 
-    pub fn move_list_cards(&self, source_list_id: &str, target_list_id: &str) -> Result<(), String> {
+    pub fn move_list_cards(&self, source_list_id: &str, target_list_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/lists/{}/move-cards", base, source_list_id);
         let auth = self.auth_header();
@@ -2188,15 +2578,15 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Move list cards failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Move list cards", status, text)); }
         Ok(())
     }
 
-    pub fn sort_list(&self, list_id: &str, field_name: &str, order: Option<&str>) -> Result<(), String> {
+    pub fn sort_list(&self, list_id: &str, field_name: &str, order: Option<&str>) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/lists/{}/sort", base, list_id);
         let auth = self.auth_header();
@@ -2210,22 +2600,22 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Sort list failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Sort list", status, text)); }
         Ok(())
     }
 
-    pub fn create_file_attachment(&self, card_id: &str, file_path: &str, name: Option<&str>) -> Result<String, String> {
+    pub fn create_file_attachment(&self, card_id: &str, file_path: &str, name: Option<&str>) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/attachments", base, card_id);
         let auth = self.auth_header();
         let mut form = Form::new()
             .text("type", "file".to_string());
         form = form.file("file", file_path)
-            .map_err(|e| format!("Read file failed: {}", e))?;
+            .map_err(|e| PlankaError::Config(format!("Read file failed: {}", e)))?;
         if let Some(n) = name {
             form = form.text("name", n.to_string());
         }
@@ -2236,20 +2626,20 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .multipart(form)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create file attachment failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("Parse create attachment failed: {}", e))?;
+        if !status.is_success() { return Err(PlankaError::http("Create file attachment", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("Parse create attachment failed: {}", e)))?;
         v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str())
             .map(|s| s.to_string())
-            .ok_or_else(|| "Create attachment response missing id".to_string())
+            .ok_or_else(|| PlankaError::Parse("Create attachment response missing id".to_string()))
     }
 
-    pub fn update_attachment_name(&self, attachment_id: &str, name: &str) -> Result<(), String> {
+    pub fn update_attachment_name(&self, attachment_id: &str, name: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/attachments/{}", base, attachment_id);
         let auth = self.auth_header();
@@ -2262,23 +2652,23 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send()
-            .map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update attachment failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update attachment", status, text)); }
         Ok(())
     }
 
-    pub fn upload_background_image(&self, project_id: &str, file_path: &str) -> Result<String, String> {
+    pub fn upload_background_image(&self, project_id: &str, file_path: &str) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/projects/{}/background-images", base, project_id);
         let auth = self.auth_header();
         let form = Form::new()
             .file("file", file_path)
-            .map_err(|e| format!("Read file failed: {}", e))?;
+            .map_err(|e| PlankaError::Config(format!("Read file failed: {}", e)))?;
         #[cfg(debug_assertions)]
         log_http_request("POST", &url, &[("Authorization", auth.as_str()), ("Accept", "application/json")], Some("[multipart form]"));
         let resp = self.client
@@ -2286,20 +2676,20 @@ impl PlankaClient {
             .header("Authorization", auth)
             .header("Accept", "application/json")
             .multipart(form)
-            .send()
-            .map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Upload background image failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("Parse background image failed: {}", e))?;
+        if !status.is_success() { return Err(PlankaError::http("Upload background image", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("Parse background image failed: {}", e)))?;
         v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str())
             .map(|s| s.to_string())
-            .ok_or_else(|| "Upload background image response missing id".to_string())
+            .ok_or_else(|| PlankaError::Parse("Upload background image response missing id".to_string()))
     }
 
-    pub fn delete_background_image(&self, background_image_id: &str) -> Result<(), String> {
+    pub fn delete_background_image(&self, background_image_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/background-images/{}", base, background_image_id);
         let auth = self.auth_header();
@@ -2309,17 +2699,17 @@ impl PlankaClient {
             .delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send()
-            .map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete background image failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete background image", status, text)); }
         Ok(())
     }
 
-    pub fn create_base_custom_field_group(&self, project_id: &str, name: &str) -> Result<String, String> {
+    pub fn create_base_custom_field_group(&self, project_id: &str, name: &str) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/projects/{}/base-custom-field-groups", base, project_id);
         let auth = self.auth_header();
@@ -2331,17 +2721,17 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create base custom field group failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse base custom field group failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create base custom field group", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse base custom field group failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn update_base_custom_field_group(&self, id: &str, name: Option<&str>) -> Result<(), String> {
+    pub fn update_base_custom_field_group(&self, id: &str, name: Option<&str>) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/base-custom-field-groups/{}", base, id);
         let auth = self.auth_header();
@@ -2355,16 +2745,16 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update base custom field group failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update base custom field group", status, text)); }
         Ok(())
     }
 
-    pub fn delete_base_custom_field_group(&self, id: &str) -> Result<(), String> {
+    pub fn delete_base_custom_field_group(&self, id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/base-custom-field-groups/{}", base, id);
         let auth = self.auth_header();
@@ -2373,16 +2763,16 @@ impl PlankaClient {
         let resp = self.client.delete(&url)
             .header("Authorization", auth)
             .header("Accept", "application/json")
-            .send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete base custom field group failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete base custom field group", status, text)); }
         Ok(())
     }
 
-    pub fn duplicate_card(&self, card_id: &str, position: i64, name: &str) -> Result<String, String> {
+    pub fn duplicate_card(&self, card_id: &str, position: i64, name: &str) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/duplicate", base, card_id);
         let auth = self.auth_header();
@@ -2394,18 +2784,18 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&body)
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Duplicate card failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse duplicate card failed: {}", e))?;
+        if !status.is_success() { return Err(PlankaError::http("Duplicate card", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse duplicate card failed: {}", e)))?;
         v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string())
-            .ok_or_else(|| "Duplicate card response missing id".to_string())
+            .ok_or_else(|| PlankaError::Parse("Duplicate card response missing id".to_string()))
     }
 
-    pub fn read_card_notifications(&self, card_id: &str) -> Result<(), String> {
+    pub fn read_card_notifications(&self, card_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/read-notifications", base, card_id);
         let auth = self.auth_header();
@@ -2416,16 +2806,16 @@ impl PlankaClient {
             .header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json")
             .json(&json!({}))
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status();
         let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)]
         log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Read card notifications failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Read card notifications", status, text)); }
         Ok(())
     }
 
-    pub fn create_board_custom_field_group(&self, board_id: &str, position: i64, name: Option<&str>, base_custom_field_group_id: Option<&str>) -> Result<String, String> {
+    pub fn create_board_custom_field_group(&self, board_id: &str, position: i64, name: Option<&str>, base_custom_field_group_id: Option<&str>) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/boards/{}/custom-field-groups", base, board_id);
         let auth = self.auth_header();
@@ -2438,15 +2828,15 @@ impl PlankaClient {
         let resp = self.client.post(&url)
             .header("Authorization", auth).header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json").json(&body)
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create board custom field group failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse create board cfg failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create board custom field group", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse create board cfg failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn create_card_custom_field_group(&self, card_id: &str, position: i64, name: Option<&str>, base_custom_field_group_id: Option<&str>) -> Result<String, String> {
+    pub fn create_card_custom_field_group(&self, card_id: &str, position: i64, name: Option<&str>, base_custom_field_group_id: Option<&str>) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/custom-field-groups", base, card_id);
         let auth = self.auth_header();
@@ -2459,37 +2849,37 @@ impl PlankaClient {
         let resp = self.client.post(&url)
             .header("Authorization", auth).header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json").json(&body)
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create card custom field group failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse create card cfg failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create card custom field group", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse create card cfg failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn delete_custom_field_group(&self, id: &str) -> Result<(), String> {
+    pub fn delete_custom_field_group(&self, id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/custom-field-groups/{}", base, id);
         let auth = self.auth_header();
         #[cfg(debug_assertions)] log_http_request("DELETE", &url, &[("Authorization", auth.as_str()), ("Accept", "application/json")], None);
         let resp = self.client.delete(&url).header("Authorization", auth).header("Accept", "application/json")
-            .send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete custom field group failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete custom field group", status, text)); }
         Ok(())
     }
 
-    pub fn get_custom_field_group(&self, id: &str) -> Result<PlankaCustomFieldGroupDetails, String> {
+    pub fn get_custom_field_group(&self, id: &str) -> Result<PlankaCustomFieldGroupDetails, PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/custom-field-groups/{}", base, id);
         let auth = self.auth_header();
         #[cfg(debug_assertions)] log_http_request("GET", &url, &[("Authorization", auth.as_str()), ("Accept", "application/json")], None);
         let resp = self.client.get(&url).header("Authorization", auth).header("Accept", "application/json")
-            .send().map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Get custom field group failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse cfg failed: {}", e))?;
-        let item = v.get("item").and_then(|x| x.as_object()).ok_or("Missing item")?;
+        if !status.is_success() { return Err(PlankaError::http("Get custom field group", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse cfg failed: {}", e)))?;
+        let item = v.get("item").and_then(|x| x.as_object()).ok_or_else(|| PlankaError::Parse("Missing item".to_string()))?;
         let gid = item.get("id").and_then(|x| x.as_str()).unwrap_or(id).to_string();
         let name = item.get("name").and_then(|x| x.as_str()).map(|s| s.to_string());
         let mut fields: Vec<PlankaCustomField> = Vec::new();
@@ -2513,7 +2903,7 @@ impl PlankaClient {
         Ok(PlankaCustomFieldGroupDetails { id: gid, name, fields, values_by_field })
     }
 
-    pub fn update_custom_field_group(&self, id: &str, position: Option<i64>, name: Option<&str>) -> Result<(), String> {
+    pub fn update_custom_field_group(&self, id: &str, position: Option<i64>, name: Option<&str>) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/custom-field-groups/{}", base, id);
         let auth = self.auth_header();
         let mut body = Map::new();
@@ -2524,14 +2914,14 @@ impl PlankaClient {
         let resp = self.client.patch(&url)
             .header("Authorization", auth).header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json").json(&body)
-            .send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update custom field group failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update custom field group", status, text)); }
         Ok(())
     }
 
-    pub fn update_custom_field_value(&self, card_id: &str, custom_field_group_id: &str, custom_field_id: &str, content: &str) -> Result<(), String> {
+    pub fn update_custom_field_value(&self, card_id: &str, custom_field_group_id: &str, custom_field_id: &str, content: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/custom-field-values/customFieldGroupId:{}:customFieldId:{}", base, card_id, custom_field_group_id, custom_field_id);
         let auth = self.auth_header();
@@ -2540,28 +2930,28 @@ impl PlankaClient {
         let resp = self.client.patch(&url)
             .header("Authorization", auth).header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json").json(&body)
-            .send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update custom field value failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update custom field value", status, text)); }
         Ok(())
     }
 
-    pub fn delete_custom_field_value(&self, card_id: &str, custom_field_group_id: &str, custom_field_id: &str) -> Result<(), String> {
+    pub fn delete_custom_field_value(&self, card_id: &str, custom_field_group_id: &str, custom_field_id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/cards/{}/custom-field-value/customFieldGroupId:{}:customFieldId:{}", base, card_id, custom_field_group_id, custom_field_id);
         let auth = self.auth_header();
         #[cfg(debug_assertions)] log_http_request("DELETE", &url, &[("Authorization", auth.as_str()), ("Accept", "application/json")], None);
         let resp = self.client.delete(&url)
             .header("Authorization", auth).header("Accept", "application/json")
-            .send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete custom field value failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete custom field value", status, text)); }
         Ok(())
     }
 
-    pub fn create_custom_field_in_base_group(&self, base_custom_field_group_id: &str, position: i64, name: &str, show_on_front_of_card: Option<bool>) -> Result<String, String> {
+    pub fn create_custom_field_in_base_group(&self, base_custom_field_group_id: &str, position: i64, name: &str, show_on_front_of_card: Option<bool>) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/base-custom-field-groups/{}/custom-fields", base, base_custom_field_group_id);
         let auth = self.auth_header();
@@ -2573,15 +2963,15 @@ impl PlankaClient {
         let resp = self.client.post(&url)
             .header("Authorization", auth).header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json").json(&body)
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create custom field (base group) failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse custom field failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create custom field (base group)", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse custom field failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn create_custom_field_in_group(&self, custom_field_group_id: &str, position: i64, name: &str, show_on_front_of_card: Option<bool>) -> Result<String, String> {
+    pub fn create_custom_field_in_group(&self, custom_field_group_id: &str, position: i64, name: &str, show_on_front_of_card: Option<bool>) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = format!("{}/api/custom-field-groups/{}/custom-fields", base, custom_field_group_id);
         let auth = self.auth_header();
@@ -2593,27 +2983,27 @@ impl PlankaClient {
         let resp = self.client.post(&url)
             .header("Authorization", auth).header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json").json(&body)
-            .send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Create custom field failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse custom field failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success() { return Err(PlankaError::http("Create custom field", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse custom field failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn delete_custom_field(&self, id: &str) -> Result<(), String> {
+    pub fn delete_custom_field(&self, id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/custom-fields/{}", base, id);
         let auth = self.auth_header();
         #[cfg(debug_assertions)] log_http_request("DELETE", &url, &[("Authorization", auth.as_str()), ("Accept", "application/json")], None);
         let resp = self.client.delete(&url).header("Authorization", auth).header("Accept", "application/json")
-            .send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Delete custom field failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Delete custom field", status, text)); }
         Ok(())
     }
 
-    pub fn update_custom_field(&self, id: &str, position: Option<i64>, name: Option<&str>, show_on_front_of_card: Option<bool>) -> Result<(), String> {
+    pub fn update_custom_field(&self, id: &str, position: Option<i64>, name: Option<&str>, show_on_front_of_card: Option<bool>) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/custom-fields/{}", base, id);
         let auth = self.auth_header();
         let mut body = Map::new();
@@ -2625,43 +3015,43 @@ impl PlankaClient {
         let resp = self.client.patch(&url)
             .header("Authorization", auth).header("Accept", "application/json")
             .header(CONTENT_TYPE, "application/json").json(&body)
-            .send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success() { return Err(format!("Update custom field failed: HTTP {} - {}", status, text)); }
+        if !status.is_success() { return Err(PlankaError::http("Update custom field", status, text)); }
         Ok(())
     }
 
-    pub fn accept_terms(&self, pending_token: &str, signature: &str) -> Result<String, String> {
+    pub fn accept_terms(&self, pending_token: &str, signature: &str) -> Result<String, PlankaError> {
         let url = format!("{}/api/access-tokens/accept-terms", self.base_url.trim_end_matches('/'));
         let body = json!({ "pendingToken": pending_token, "signature": signature });
         #[cfg(debug_assertions)]
         log_http_request("POST", &url, &[("Accept","application/json"),("Content-Type","application/json")], Some(&body.to_string()));
         let resp = self.client.post(&url)
             .header("Accept","application/json").header(CONTENT_TYPE,"application/json")
-            .json(&body).send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status = resp.status(); let text = resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(), &text);
-        if !status.is_success(){ return Err(format!("Accept terms failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse accept-terms failed: {}", e))?;
-        v.get("item").and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing token".to_string())
+        if !status.is_success(){ return Err(PlankaError::http("Accept terms", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse accept-terms failed: {}", e)))?;
+        v.get("item").and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing token".to_string()))
     }
 
-    pub fn logout_me(&self) -> Result<String, String> {
+    pub fn logout_me(&self) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/access-tokens/me", base);
         let auth = self.auth_header();
         #[cfg(debug_assertions)] log_http_request("DELETE",&url,&[("Authorization",auth.as_str()),("Accept","application/json")],None);
         let resp = self.client.delete(&url)
             .header("Authorization",auth).header("Accept","application/json")
-            .send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+            .send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Logout failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse logout failed: {}", e))?;
-        v.get("item").and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing item".to_string())
+        if !status.is_success(){ return Err(PlankaError::http("Logout", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse logout failed: {}", e)))?;
+        v.get("item").and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing item".to_string()))
     }
 
-    pub fn exchange_with_oidc(&self, code: &str, nonce: &str, with_http_only_token: Option<bool>) -> Result<String, String> {
+    pub fn exchange_with_oidc(&self, code: &str, nonce: &str, with_http_only_token: Option<bool>) -> Result<String, PlankaError> {
         let url = format!("{}/api/access-tokens/exchange-with-oidc", self.base_url.trim_end_matches('/'));
         let mut body = Map::new();
         body.insert("code".into(), Value::String(code.into()));
@@ -2671,39 +3061,39 @@ impl PlankaClient {
             log_http_request("POST",&url,&[("Accept","application/json"),("Content-Type","application/json")],Some(&preview.to_string())); }
         let resp = self.client.post(&url)
             .header("Accept","application/json").header(CONTENT_TYPE,"application/json")
-            .json(&body).send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("OIDC exchange failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse exchange failed: {}", e))?;
-        v.get("item").and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing token".to_string())
+        if !status.is_success(){ return Err(PlankaError::http("OIDC exchange", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse exchange failed: {}", e)))?;
+        v.get("item").and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing token".to_string()))
     }
 
-    pub fn revoke_pending_token(&self, pending_token: &str) -> Result<(), String> {
+    pub fn revoke_pending_token(&self, pending_token: &str) -> Result<(), PlankaError> {
         let url = format!("{}/api/access-tokens/revoke-pending-token", self.base_url.trim_end_matches('/'));
         let body = json!({ "pendingToken": pending_token });
         #[cfg(debug_assertions)]
         log_http_request("POST",&url,&[("Accept","application/json"),("Content-Type","application/json")],Some(&body.to_string()));
         let resp = self.client.post(&url)
             .header("Accept","application/json").header(CONTENT_TYPE,"application/json")
-            .json(&body).send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+            .json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Revoke pending token failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Revoke pending token", status, text)); }
         Ok(())
     }
 
-    pub fn fetch_server_config(&self) -> Result<PlankaServerConfig, String> {
+    pub fn fetch_server_config(&self) -> Result<PlankaServerConfig, PlankaError> {
         let url = format!("{}/api/config", self.base_url.trim_end_matches('/'));
         #[cfg(debug_assertions)] log_http_request("GET",&url,&[("Accept","application/json")],None);
         let resp = self.client.get(&url)
-            .header("Accept","application/json").send()
-            .map_err(|e| format!("GET {} failed: {}", url, e))?;
+            .header("Accept","application/json").send_authed(self)
+            .map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Fetch config failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse config failed: {}", e))?;
-        let item = v.get("item").ok_or("Missing item")?;
+        if !status.is_success(){ return Err(PlankaError::http("Fetch config", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse config failed: {}", e)))?;
+        let item = v.get("item").ok_or_else(|| PlankaError::Parse("Missing item".to_string()))?;
         let version = item.get("version").and_then(|x| x.as_str()).unwrap_or("").to_string();
         let active = item.get("activeUsersLimit").and_then(|x| x.as_i64());
         let oidc = item.get("oidc").and_then(|x| x.as_object()).map(|o| PlankaOidcConfig{
@@ -2714,42 +3104,42 @@ impl PlankaClient {
         Ok(PlankaServerConfig{ version, active_users_limit: active, oidc })
     }
 
-    pub fn create_board_notification_service(&self, board_id: &str, url_value: &str, format_value: &str) -> Result<String, String> {
+    pub fn create_board_notification_service(&self, board_id: &str, url_value: &str, format_value: &str) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/boards/{}/notification-services", base, board_id);
         let auth = self.auth_header(); let body = json!({ "url": url_value, "format": format_value });
         #[cfg(debug_assertions)] log_http_request("POST",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some(&body.to_string()));
-        let resp = self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+        let resp = self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Create board notification service failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse notification service failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success(){ return Err(PlankaError::http("Create board notification service", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse notification service failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn create_user_notification_service(&self, user_id: &str, url_value: &str, format_value: &str) -> Result<String, String> {
+    pub fn create_user_notification_service(&self, user_id: &str, url_value: &str, format_value: &str) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/users/{}/notification-services", base, user_id);
         let auth = self.auth_header(); let body = json!({ "url": url_value, "format": format_value });
         #[cfg(debug_assertions)] log_http_request("POST",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some(&body.to_string()));
-        let resp = self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+        let resp = self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Create user notification service failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse notification service failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success(){ return Err(PlankaError::http("Create user notification service", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse notification service failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn delete_notification_service(&self, id: &str) -> Result<(), String> {
+    pub fn delete_notification_service(&self, id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/notification-services/{}", base, id);
         let auth = self.auth_header();
         #[cfg(debug_assertions)] log_http_request("DELETE",&url,&[("Authorization",auth.as_str()),("Accept","application/json")],None);
-        let resp = self.client.delete(&url).header("Authorization",auth).header("Accept","application/json").send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+        let resp = self.client.delete(&url).header("Authorization",auth).header("Accept","application/json").send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Delete notification service failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Delete notification service", status, text)); }
         Ok(())
     }
 
-    pub fn update_notification_service(&self, id: &str, url_value: Option<&str>, format_value: Option<&str>) -> Result<(), String> {
+    pub fn update_notification_service(&self, id: &str, url_value: Option<&str>, format_value: Option<&str>) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/notification-services/{}", base, id);
         let auth = self.auth_header(); let mut body = Map::new();
         if let Some(u) = url_value { body.insert("url".into(), Value::String(u.into())); }
@@ -2757,92 +3147,82 @@ impl PlankaClient {
         if body.is_empty(){ return Ok(()); }
         #[cfg(debug_assertions)] { let preview = Value::Object(body.clone());
             log_http_request("PATCH",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some(&preview.to_string())); }
-        let resp = self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+        let resp = self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Update notification service failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Update notification service", status, text)); }
         Ok(())
     }
 
-    pub fn test_notification_service(&self, id: &str) -> Result<(), String> {
+    pub fn test_notification_service(&self, id: &str) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/notification-services/{}/test", base, id);
         let auth = self.auth_header();
         #[cfg(debug_assertions)] log_http_request("POST",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some("{}"));
-        let resp = self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&json!({})).send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+        let resp = self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&json!({})).send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Test notification service failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Test notification service", status, text)); }
         Ok(())
     }
 
-    pub fn fetch_notifications(&self) -> Result<Vec<PlankaNotification>, String> {
+    pub fn fetch_notifications(&self) -> Result<Vec<PlankaNotification>, PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/notifications", base);
         let auth = self.auth_header();
         #[cfg(debug_assertions)] log_http_request("GET",&url,&[("Authorization",auth.as_str()),("Accept","application/json")],None);
-        let resp = self.client.get(&url).header("Authorization",auth).header("Accept","application/json").send().map_err(|e| format!("GET {} failed: {}", url, e))?;
+        let resp = self.client.get(&url).header("Authorization",auth).header("Accept","application/json").send_authed(self).map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Fetch notifications failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse notifications failed: {}", e))?;
+        if !status.is_success(){ return Err(PlankaError::http("Fetch notifications", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse notifications failed: {}", e)))?;
+        let users = v.get("included").and_then(|i| i.get("users")).and_then(|x| x.as_array()).cloned().unwrap_or_default();
         let mut out=Vec::new();
         if let Some(items)=v.get("items").and_then(|x| x.as_array()){
             for n in items {
-                let id = n.get("id").and_then(|x| x.as_str()).unwrap_or_default().to_string();
-                let user_id = n.get("userId").and_then(|x| x.as_str()).unwrap_or_default().to_string();
-                let card_id = n.get("cardId").and_then(|x| x.as_str()).map(|s| s.to_string());
-                let r#type = n.get("type").and_then(|x| x.as_str()).unwrap_or_default().to_string();
-                let is_read = n.get("isRead").and_then(|x| x.as_bool()).unwrap_or(false);
-                let created = n.get("createdAt").and_then(|x| x.as_str()).map(|s| s.to_string());
-                let text = n.get("data").and_then(|d| d.get("text")).and_then(|x| x.as_str()).map(|s| s.to_string());
-                out.push(PlankaNotification{ id, user_id, card_id, r#type, text, is_read, created });
+                out.push(parse_notification(n, &users));
             }
         }
         Ok(out)
     }
 
-    pub fn read_all_notifications(&self) -> Result<(), String> {
+    pub fn read_all_notifications(&self) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/notifications/read-all", base);
         let auth = self.auth_header();
         #[cfg(debug_assertions)] log_http_request("POST",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some("{}"));
-        let resp = self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&json!({})).send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+        let resp = self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&json!({})).send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Read-all notifications failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Read-all notifications", status, text)); }
         Ok(())
     }
 
-    pub fn fetch_notification(&self, id: &str) -> Result<PlankaNotification, String> {
+    pub fn fetch_notification(&self, id: &str) -> Result<PlankaNotification, PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/notifications/{}", base, id);
         let auth = self.auth_header();
         #[cfg(debug_assertions)] log_http_request("GET",&url,&[("Authorization",auth.as_str()),("Accept","application/json")],None);
-        let resp = self.client.get(&url).header("Authorization",auth).header("Accept","application/json").send().map_err(|e| format!("GET {} failed: {}", url, e))?;
+        let resp = self.client.get(&url).header("Authorization",auth).header("Accept","application/json").send_authed(self).map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Fetch notification failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse notification failed: {}", e))?;
-        let n = v.get("item").ok_or("Missing item")?;
-        let nid = n.get("id").and_then(|x| x.as_str()).unwrap_or(id).to_string();
-        let user_id = n.get("userId").and_then(|x| x.as_str()).unwrap_or_default().to_string();
-        let card_id = n.get("cardId").and_then(|x| x.as_str()).map(|s| s.to_string());
-        let r#type = n.get("type").and_then(|x| x.as_str()).unwrap_or_default().to_string();
-        let is_read = n.get("isRead").and_then(|x| x.as_bool()).unwrap_or(false);
-        let created = n.get("createdAt").and_then(|x| x.as_str()).map(|s| s.to_string());
-        let textf = n.get("data").and_then(|d| d.get("text")).and_then(|x| x.as_str()).map(|s| s.to_string());
-        Ok(PlankaNotification{ id: nid, user_id, card_id, r#type, text: textf, is_read, created })
-    }
-
-    pub fn update_notification(&self, id: &str, is_read: bool) -> Result<(), String> {
+        if !status.is_success(){ return Err(PlankaError::http("Fetch notification", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse notification failed: {}", e)))?;
+        let n = v.get("item").ok_or_else(|| PlankaError::Parse("Missing item".to_string()))?;
+        let users = v.get("included").and_then(|i| i.get("users")).and_then(|x| x.as_array()).cloned().unwrap_or_default();
+        let mut out = parse_notification(n, &users);
+        if out.id.is_empty() { out.id = id.to_string(); }
+        Ok(out)
+    }
+
+    pub fn update_notification(&self, id: &str, is_read: bool) -> Result<(), PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/notifications/{}", base, id);
         let auth = self.auth_header(); let body = json!({ "isRead": is_read });
         #[cfg(debug_assertions)] log_http_request("PATCH",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some(&body.to_string()));
-        let resp = self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+        let resp = self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Update notification failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Update notification", status, text)); }
         Ok(())
     }
 
-    pub fn fetch_terms(&self, r#type: &str, language: Option<&str>) -> Result<PlankaTerms, String> {
+    pub fn fetch_terms(&self, r#type: &str, language: Option<&str>) -> Result<PlankaTerms, PlankaError> {
         let base = self.base_url.trim_end_matches('/');
         let url = if let Some(lang) = language {
             format!("{}/api/terms/{}?language={}", base, r#type, lang)
@@ -2850,12 +3230,12 @@ impl PlankaClient {
             format!("{}/api/terms/{}", base, r#type)
         };
         #[cfg(debug_assertions)] log_http_request("GET",&url,&[("Accept","application/json")],None);
-        let resp = self.client.get(&url).header("Accept","application/json").send().map_err(|e| format!("GET {} failed: {}", url, e))?;
+        let resp = self.client.get(&url).header("Accept","application/json").send_authed(self).map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Fetch terms failed: HTTP {} - {}", status, text)); }
-        let v: Value = serde_json::from_str(&text).map_err(|e| format!("parse terms failed: {}", e))?;
-        let item = v.get("item").ok_or("Missing item")?;
+        if !status.is_success(){ return Err(PlankaError::http("Fetch terms", status, text)); }
+        let v: Value = serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse terms failed: {}", e)))?;
+        let item = v.get("item").ok_or_else(|| PlankaError::Parse("Missing item".to_string()))?;
         Ok(PlankaTerms{
             r#type: item.get("type").and_then(|x| x.as_str()).unwrap_or("").to_string(),
             language: item.get("language").and_then(|x| x.as_str()).unwrap_or("").to_string(),
@@ -2864,7 +3244,7 @@ impl PlankaClient {
         })
     }
 
-    pub fn create_user(&self, email: &str, password: &str, role: &str, name: &str, username: Option<&str>) -> Result<String, String> {
+    pub fn create_user(&self, email: &str, password: &str, role: &str, name: &str, username: Option<&str>) -> Result<String, PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/users", base);
         let auth = self.auth_header(); let mut body = Map::new();
         body.insert("email".into(), Value::String(email.into()));
@@ -2873,71 +3253,59 @@ impl PlankaClient {
         body.insert("name".into(), Value::String(name.into()));
         if let Some(u)=username { body.insert("username".into(), Value::String(u.into())); }
         #[cfg(debug_assertions)] { let preview=Value::Object(body.clone()); log_http_request("POST",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some(&preview.to_string())); }
-        let resp = self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+        let resp = self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Create user failed: HTTP {} - {}", status, text)); }
-        let v: Value=serde_json::from_str(&text).map_err(|e| format!("parse create user failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success(){ return Err(PlankaError::http("Create user", status, text)); }
+        let v: Value=serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse create user failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn fetch_users(&self) -> Result<Vec<PlankaUser>, String> {
+    pub fn fetch_users(&self) -> Result<Vec<PlankaUser>, PlankaError> {
         let base = self.base_url.trim_end_matches('/'); let url = format!("{}/api/users", base);
         let auth = self.auth_header();
         #[cfg(debug_assertions)] log_http_request("GET",&url,&[("Authorization",auth.as_str()),("Accept","application/json")],None);
-        let resp = self.client.get(&url).header("Authorization",auth).header("Accept","application/json").send().map_err(|e| format!("GET {} failed: {}", url, e))?;
+        let resp = self.client.get(&url).header("Authorization",auth).header("Accept","application/json").send_authed(self).map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Fetch users failed: HTTP {} - {}", status, text)); }
-        let v: Value=serde_json::from_str(&text).map_err(|e| format!("parse users failed: {}", e))?;
+        if !status.is_success(){ return Err(PlankaError::http("Fetch users", status, text)); }
+        let v: Value=serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse users failed: {}", e)))?;
         let mut out=Vec::new();
         if let Some(items)=v.get("items").and_then(|x| x.as_array()){
             for u in items {
-                out.push(PlankaUser{
-                    id: u.get("id").and_then(|x| x.as_str()).unwrap_or_default().to_string(),
-                    role: u.get("role").and_then(|x| x.as_str()).unwrap_or_default().to_string(),
-                    name: u.get("name").and_then(|x| x.as_str()).unwrap_or_default().to_string(),
-                    username: u.get("username").and_then(|x| x.as_str()).map(|s| s.to_string()),
-                    email: u.get("email").and_then(|x| x.as_str()).map(|s| s.to_string()),
-                    is_deactivated: u.get("isDeactivated").and_then(|x| x.as_bool()).unwrap_or(false),
-                });
+                out.push(parse_user(u));
             }
         }
         Ok(out)
     }
 
-    pub fn delete_user(&self, id: &str) -> Result<(), String> {
+    pub fn delete_user(&self, id: &str) -> Result<(), PlankaError> {
         let base=self.base_url.trim_end_matches('/'); let url=format!("{}/api/users/{}", base, id);
         let auth=self.auth_header();
         #[cfg(debug_assertions)] log_http_request("DELETE",&url,&[("Authorization",auth.as_str()),("Accept","application/json")],None);
-        let resp=self.client.delete(&url).header("Authorization",auth).header("Accept","application/json").send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+        let resp=self.client.delete(&url).header("Authorization",auth).header("Accept","application/json").send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Delete user failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Delete user", status, text)); }
         Ok(())
     }
 
-    pub fn fetch_user(&self, id: &str) -> Result<PlankaUser, String> {
+    pub fn fetch_user(&self, id: &str) -> Result<PlankaUser, PlankaError> {
         let base=self.base_url.trim_end_matches('/'); let url=format!("{}/api/users/{}", base, id);
         let auth=self.auth_header();
         #[cfg(debug_assertions)] log_http_request("GET",&url,&[("Authorization",auth.as_str()),("Accept","application/json")],None);
-        let resp=self.client.get(&url).header("Authorization",auth).header("Accept","application/json").send().map_err(|e| format!("GET {} failed: {}", url, e))?;
+        let resp=self.client.get(&url).header("Authorization",auth).header("Accept","application/json").send_authed(self).map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Fetch user failed: HTTP {} - {}", status, text)); }
-        let v: Value=serde_json::from_str(&text).map_err(|e| format!("parse user failed: {}", e))?;
-        let u=v.get("item").ok_or("Missing item")?;
-        Ok(PlankaUser{
-            id: u.get("id").and_then(|x| x.as_str()).unwrap_or(id).to_string(),
-            role: u.get("role").and_then(|x| x.as_str()).unwrap_or_default().to_string(),
-            name: u.get("name").and_then(|x| x.as_str()).unwrap_or_default().to_string(),
-            username: u.get("username").and_then(|x| x.as_str()).map(|s| s.to_string()),
-            email: u.get("email").and_then(|x| x.as_str()).map(|s| s.to_string()),
-            is_deactivated: u.get("isDeactivated").and_then(|x| x.as_bool()).unwrap_or(false),
-        })
+        if !status.is_success(){ return Err(PlankaError::http("Fetch user", status, text)); }
+        let v: Value=serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse user failed: {}", e)))?;
+        let u=v.get("item").ok_or_else(|| PlankaError::Parse("Missing item".to_string()))?;
+        let mut user = parse_user(u);
+        if user.id.is_empty() { user.id = id.to_string(); }
+        Ok(user)
     }
 
-    pub fn update_user(&self, id: &str, role: Option<&str>, name: Option<&str>, is_deactivated: Option<bool>) -> Result<(), String> {
+    pub fn update_user(&self, id: &str, role: Option<&str>, name: Option<&str>, is_deactivated: Option<bool>) -> Result<(), PlankaError> {
         let base=self.base_url.trim_end_matches('/'); let url=format!("{}/api/users/{}", base, id);
         let auth=self.auth_header(); let mut body=Map::new();
         if let Some(r)=role { body.insert("role".into(), Value::String(r.into())); }
@@ -2946,64 +3314,64 @@ impl PlankaClient {
         if body.is_empty(){ return Ok(()); }
         #[cfg(debug_assertions)] { let preview=Value::Object(body.clone());
             log_http_request("PATCH",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some(&preview.to_string())); }
-        let resp=self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+        let resp=self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Update user failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Update user", status, text)); }
         Ok(())
     }
 
-    pub fn update_user_avatar(&self, id: &str, file_path: &str) -> Result<(), String> {
+    pub fn update_user_avatar(&self, id: &str, file_path: &str) -> Result<(), PlankaError> {
         let base=self.base_url.trim_end_matches('/'); let url=format!("{}/api/users/{}/avatar", base, id);
         let auth=self.auth_header();
-        let form = Form::new().file("file", file_path).map_err(|e| format!("Read file failed: {}", e))?;
+        let form = Form::new().file("file", file_path).map_err(|e| PlankaError::Config(format!("Read file failed: {}", e)))?;
         #[cfg(debug_assertions)] log_http_request("POST",&url,&[("Authorization",auth.as_str()),("Accept","application/json")],Some("[multipart form]"));
-        let resp=self.client.post(&url).header("Authorization",auth).header("Accept","application/json").multipart(form).send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+        let resp=self.client.post(&url).header("Authorization",auth).header("Accept","application/json").multipart(form).send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Update avatar failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Update avatar", status, text)); }
         Ok(())
     }
 
-    pub fn update_user_email(&self, id: &str, email: &str, current_password: Option<&str>) -> Result<(), String> {
+    pub fn update_user_email(&self, id: &str, email: &str, current_password: Option<&str>) -> Result<(), PlankaError> {
         let base=self.base_url.trim_end_matches('/'); let url=format!("{}/api/users/{}/email", base, id);
         let auth=self.auth_header(); let mut body=Map::new();
         body.insert("email".into(), Value::String(email.into()));
         if let Some(p)=current_password { body.insert("currentPassword".into(), Value::String(p.into())); }
         #[cfg(debug_assertions)] { let preview=Value::Object(body.clone());
             log_http_request("PATCH",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some(&preview.to_string())); }
-        let resp=self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+        let resp=self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Update user email failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Update user email", status, text)); }
         Ok(())
     }
 
-    pub fn update_user_password(&self, id: &str, password: &str, current_password: Option<&str>) -> Result<(), String> {
+    pub fn update_user_password(&self, id: &str, password: &str, current_password: Option<&str>) -> Result<(), PlankaError> {
         let base=self.base_url.trim_end_matches('/'); let url=format!("{}/api/users/{}/password", base, id);
         let auth=self.auth_header(); let mut body=Map::new();
         body.insert("password".into(), Value::String(password.into()));
         if let Some(p)=current_password { body.insert("currentPassword".into(), Value::String(p.into())); }
         #[cfg(debug_assertions)] { let preview=Value::Object(body.clone());
             log_http_request("PATCH",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some(&preview.to_string())); }
-        let resp=self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+        let resp=self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Update user password failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Update user password", status, text)); }
         Ok(())
     }
 
-    pub fn update_user_username(&self, id: &str, username: Option<&str>, current_password: Option<&str>) -> Result<(), String> {
+    pub fn update_user_username(&self, id: &str, username: Option<&str>, current_password: Option<&str>) -> Result<(), PlankaError> {
         let base=self.base_url.trim_end_matches('/'); let url=format!("{}/api/users/{}/username", base, id);
         let auth=self.auth_header(); let mut body=Map::new();
         if let Some(u)=username { body.insert("username".into(), Value::String(u.into())); }
         if let Some(p)=current_password { body.insert("currentPassword".into(), Value::String(p.into())); }
         #[cfg(debug_assertions)] { let preview=Value::Object(body.clone());
             log_http_request("PATCH",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some(&preview.to_string())); }
-        let resp=self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+        let resp=self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Update user username failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Update user username", status, text)); }
         Ok(())
     }
 
@@ -3014,7 +3382,7 @@ impl PlankaClient {
         access_token: Option<&str>,
         events: Option<&[&str]>,
         excluded_events: Option<&[&str]>,
-    ) -> Result<String, String> {
+    ) -> Result<String, PlankaError> {
         let base=self.base_url.trim_end_matches('/'); let url=format!("{}/api/webhooks", base);
         let auth=self.auth_header(); let mut body=Map::new();
         body.insert("name".into(), Value::String(name.into()));
@@ -3028,23 +3396,23 @@ impl PlankaClient {
         }
         #[cfg(debug_assertions)] { let preview=Value::Object(body.clone());
             log_http_request("POST",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some(&preview.to_string())); }
-        let resp=self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send().map_err(|e| format!("POST {} failed: {}", url, e))?;
+        let resp=self.client.post(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("POST {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Create webhook failed: HTTP {} - {}", status, text)); }
-        let v: Value=serde_json::from_str(&text).map_err(|e| format!("parse create webhook failed: {}", e))?;
-        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| "Response missing id".to_string())
+        if !status.is_success(){ return Err(PlankaError::http("Create webhook", status, text)); }
+        let v: Value=serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse create webhook failed: {}", e)))?;
+        v.get("item").and_then(|i| i.get("id")).and_then(|x| x.as_str()).map(|s| s.to_string()).ok_or_else(|| PlankaError::Parse("Response missing id".to_string()))
     }
 
-    pub fn fetch_webhooks(&self) -> Result<Vec<PlankaWebhook>, String> {
+    pub fn fetch_webhooks(&self) -> Result<Vec<PlankaWebhook>, PlankaError> {
         let base=self.base_url.trim_end_matches('/'); let url=format!("{}/api/webhooks", base);
         let auth=self.auth_header();
         #[cfg(debug_assertions)] log_http_request("GET",&url,&[("Authorization",auth.as_str()),("Accept","application/json")],None);
-        let resp=self.client.get(&url).header("Authorization",auth).header("Accept","application/json").send().map_err(|e| format!("GET {} failed: {}", url, e))?;
+        let resp=self.client.get(&url).header("Authorization",auth).header("Accept","application/json").send_authed(self).map_err(|e| PlankaError::Network(format!("GET {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Fetch webhooks failed: HTTP {} - {}", status, text)); }
-        let v: Value=serde_json::from_str(&text).map_err(|e| format!("parse webhooks failed: {}", e))?;
+        if !status.is_success(){ return Err(PlankaError::http("Fetch webhooks", status, text)); }
+        let v: Value=serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("parse webhooks failed: {}", e)))?;
         let mut out=Vec::new();
         if let Some(items)=v.get("items").and_then(|x| x.as_array()){
             for w in items {
@@ -3061,14 +3429,14 @@ impl PlankaClient {
         Ok(out)
     }
 
-    pub fn delete_webhook(&self, id: &str) -> Result<(), String> {
+    pub fn delete_webhook(&self, id: &str) -> Result<(), PlankaError> {
         let base=self.base_url.trim_end_matches('/'); let url=format!("{}/api/webhooks/{}", base, id);
         let auth=self.auth_header();
         #[cfg(debug_assertions)] log_http_request("DELETE",&url,&[("Authorization",auth.as_str()),("Accept","application/json")],None);
-        let resp=self.client.delete(&url).header("Authorization",auth).header("Accept","application/json").send().map_err(|e| format!("DELETE {} failed: {}", url, e))?;
+        let resp=self.client.delete(&url).header("Authorization",auth).header("Accept","application/json").send_authed(self).map_err(|e| PlankaError::Network(format!("DELETE {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Delete webhook failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Delete webhook", status, text)); }
         Ok(())
     }
 
@@ -3080,7 +3448,7 @@ impl PlankaClient {
         access_token: Option<&str>,
         events: Option<&[&str]>,
         excluded_events: Option<&[&str]>,
-    ) -> Result<(), String> {
+    ) -> Result<(), PlankaError> {
         let base=self.base_url.trim_end_matches('/'); let url=format!("{}/api/webhooks/{}", base, id);
         let auth=self.auth_header(); let mut body=Map::new();
         if let Some(v)=name { body.insert("name".into(), Value::String(v.into())); }
@@ -3095,16 +3463,56 @@ impl PlankaClient {
         if body.is_empty(){ return Ok(()); }
         #[cfg(debug_assertions)] { let preview=Value::Object(body.clone());
             log_http_request("PATCH",&url,&[("Authorization",auth.as_str()),("Accept","application/json"),("Content-Type","application/json")],Some(&preview.to_string())); }
-        let resp=self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send().map_err(|e| format!("PATCH {} failed: {}", url, e))?;
+        let resp=self.client.patch(&url).header("Authorization",auth).header("Accept","application/json").header(CONTENT_TYPE,"application/json").json(&body).send_authed(self).map_err(|e| PlankaError::Network(format!("PATCH {} failed: {}", url, e)))?;
         let status=resp.status(); let text=resp.text().unwrap_or_default();
         #[cfg(debug_assertions)] log_http_response(status.as_u16(),&text);
-        if !status.is_success(){ return Err(format!("Update webhook failed: HTTP {} - {}", status, text)); }
+        if !status.is_success(){ return Err(PlankaError::http("Update webhook", status, text)); }
         Ok(())
     }
 }
 
-// POST /api/access-tokens
-fn login(server_url: &str, email_or_username: &str, password: &str) -> Result<String, String> {
+// A stalled connection must fail the request rather than block the serial worker
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+// Generous enough for attachment uploads
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn http_client(cookies: &Arc<Jar>) -> Result<Client, PlankaError> {
+    Client::builder()
+        .cookie_provider(cookies.clone())
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| PlankaError::Config(format!("HTTP client build failed: {}", e)))
+}
+
+const API_KEY_HEADER: &str = "X-Api-Key";
+// Cookie Planka sets on a `withHttpOnlyToken` login
+const HTTP_ONLY_COOKIE: &str = "httpOnlyToken";
+
+// Cookie jar holding the saved session cookie, if any
+fn cookie_jar(cfg: &PlankaConfig) -> Arc<Jar> {
+    let jar = Arc::new(Jar::default());
+    if let (Some(cookie), Ok(url)) = (cfg.http_only_token.as_ref(), Url::parse(&cfg.server_url)) {
+        jar.add_cookie_str(&format!("{}={}; Path=/", HTTP_ONLY_COOKIE, cookie), &url);
+    }
+    jar
+}
+
+fn http_only_cookie(jar: &Jar, server_url: &str) -> Option<String> {
+    let url = Url::parse(server_url).ok()?;
+    let header = jar.cookies(&url)?;
+    header
+        .to_str()
+        .ok()?
+        .split("; ")
+        .find_map(|c| c.strip_prefix(HTTP_ONLY_COOKIE)?.strip_prefix('='))
+        .map(|s| s.to_string())
+}
+
+// POST /api/access-tokens; the session cookie of an HTTP-only login goes to `cookies`
+fn login(cfg: &PlankaConfig, password: &str, cookies: &Arc<Jar>) -> Result<String, PlankaError> {
+    let server_url = cfg.server_url.as_str();
+    let email_or_username = cfg.email_or_username.as_str();
     #[cfg(debug_assertions)]
     init_log_notice();
     #[derive(Serialize)]
@@ -3121,14 +3529,14 @@ fn login(server_url: &str, email_or_username: &str, password: &str) -> Result<St
     }
 
     let url = format!("{}/api/access-tokens", server_url.trim_end_matches('/'));
-    let client = Client::new();
+    let client = http_client(cookies)?;
     // Debug: log outgoing request (mask password)
     #[cfg(debug_assertions)]
     {
         let preview = json!({
             "emailOrUsername": email_or_username,
             "password": "***",
-            "withHttpOnlyToken": false
+            "withHttpOnlyToken": cfg.with_http_only_token
         }).to_string();
         log_http_request(
             "POST",
@@ -3144,19 +3552,19 @@ fn login(server_url: &str, email_or_username: &str, password: &str) -> Result<St
         .json(&LoginReq {
             email_or_username,
             password,
-            with_http_only_token: false,
+            with_http_only_token: cfg.with_http_only_token,
         })
         .send()
-        .map_err(|e| format!("Login request failed: {}", e))?;
+        .map_err(|e| PlankaError::Network(format!("Login request failed: {}", e)))?;
     let status = res.status();
-    let text = res.text().map_err(|e| format!("Login read failed: {}", e))?;
+    let text = res.text().map_err(|e| PlankaError::Network(format!("Login read failed: {}", e)))?;
     #[cfg(debug_assertions)]
     log_http_response(status.as_u16(), &text);
     if !status.is_success() {
-        return Err(format!("Login failed: HTTP {} - {}", status, text));
+        return Err(PlankaError::http("Login", status, text));
     }
     let body: LoginRes =
-        serde_json::from_str(&text).map_err(|e| format!("Login parse failed: {}", e))?;
+        serde_json::from_str(&text).map_err(|e| PlankaError::Parse(format!("Login parse failed: {}", e)))?;
     #[cfg(debug_assertions)]
     log_debug("Login succeeded and token parsed");
     Ok(body.item)
@@ -3170,12 +3578,163 @@ pub struct PlankaBoard {
     pub project_name: Option<String>,
 }
 
+// One step of a board's workflow: a Planka list and the label Planky shows for it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowStage {
+    pub name: String,
+    pub list_id: String,
+}
+
+// Tracked lists of a board in workflow order: new cards go to the first
+// stage, the last one means done. Always holds at least two stages.
 #[derive(Clone, Debug)]
 pub struct PlankaLists {
     pub board_id: String,
-    pub todo_list_id: String,
-    pub doing_list_id: String,
-    pub done_list_id: String,
+    pub stages: Vec<WorkflowStage>,
+}
+
+impl PlankaLists {
+    pub fn first_list_id(&self) -> &str {
+        &self.stages[0].list_id
+    }
+
+    pub fn done_list_id(&self) -> &str {
+        &self.stages[self.stages.len() - 1].list_id
+    }
+
+    pub fn contains(&self, list_id: &str) -> bool {
+        self.stages.iter().any(|s| s.list_id == list_id)
+    }
+
+    // Stage of a card; lists outside the workflow count as the first stage
+    pub fn stage_index(&self, list_id: Option<&str>, done: bool) -> usize {
+        if done {
+            return self.stages.len() - 1;
+        }
+        list_id
+            .and_then(|id| self.stages.iter().position(|s| s.list_id == id))
+            .unwrap_or(0)
+    }
+}
+
+// Planka 2 also returns the board's archive and trash lists
+fn is_active_list(l: &Value) -> bool {
+    !matches!(l.get("type").and_then(|x| x.as_str()), Some("archive") | Some("trash"))
+}
+
+// Lists and cards are ordered by a numeric position
+fn position_of(v: &Value) -> f64 {
+    v.get("position").and_then(|x| x.as_f64()).unwrap_or(0.0)
+}
+
+// Label model as sent by the API and the socket
+pub fn parse_label(v: &Value) -> Option<PlankaLabel> {
+    let id = v.get("id").and_then(|x| x.as_str())?;
+    let name = v.get("name").and_then(|x| x.as_str()).or_else(|| v.get("title").and_then(|x| x.as_str())).unwrap_or("");
+    let color = v.get("color").and_then(|x| x.as_str()).unwrap_or("dark-granite");
+    Some(PlankaLabel { id: id.to_string(), name: name.to_string(), color: color.to_string(), position: position_of(v) })
+}
+
+// card id -> linked ids from an included join table, e.g. `cardLabels`
+// (labelId) or `cardMemberships` (userId) of a list or board response
+fn card_links_of(v: &Value, table: &str, field: &str) -> HashMap<String, Vec<String>> {
+    let mut out: HashMap<String, Vec<String>> = HashMap::new();
+    let arr = v.get("included").and_then(|i| i.get(table)).and_then(|x| x.as_array());
+    for row in arr.into_iter().flatten() {
+        if let (Some(cid), Some(id)) = (row.get("cardId").and_then(|x| x.as_str()), row.get(field).and_then(|x| x.as_str())) {
+            out.entry(cid.to_string()).or_default().push(id.to_string());
+        }
+    }
+    out
+}
+
+fn links_of(links: &HashMap<String, Vec<String>>, card_id: &str) -> Vec<String> {
+    links.get(card_id).cloned().unwrap_or_default()
+}
+
+// A notification item; `users` (the response's included users) names its creator
+pub fn parse_notification(n: &Value, users: &[Value]) -> PlankaNotification {
+    let str_of = |v: Option<&Value>| v.and_then(|x| x.as_str()).map(|s| s.to_string());
+    let data = n.get("data");
+    let creator_id = n.get("creatorUserId").and_then(|x| x.as_str());
+    let creator = users
+        .iter()
+        .find(|u| creator_id.is_some() && u.get("id").and_then(|x| x.as_str()) == creator_id)
+        .and_then(|u| str_of(u.get("name")));
+    PlankaNotification {
+        id: str_of(n.get("id")).unwrap_or_default(),
+        user_id: str_of(n.get("userId")).unwrap_or_default(),
+        card_id: str_of(n.get("cardId")),
+        r#type: str_of(n.get("type")).unwrap_or_default(),
+        text: str_of(data.and_then(|d| d.get("text"))),
+        is_read: n.get("isRead").and_then(|x| x.as_bool()).unwrap_or(false),
+        created: str_of(n.get("createdAt")),
+        creator,
+        card_name: str_of(data.and_then(|d| d.get("card")).and_then(|c| c.get("name"))),
+        to_list: str_of(data.and_then(|d| d.get("toList")).and_then(|l| l.get("name"))),
+    }
+}
+
+pub fn parse_board_membership(m: &Value) -> Option<PlankaBoardMembership> {
+    Some(PlankaBoardMembership {
+        id: m.get("id")?.as_str()?.to_string(),
+        user_id: m.get("userId")?.as_str()?.to_string(),
+        role: m.get("role").and_then(|x| x.as_str()).unwrap_or("editor").to_string(),
+        can_comment: m.get("canComment").and_then(|x| x.as_bool()),
+    })
+}
+
+pub fn parse_user(u: &Value) -> PlankaUser {
+    PlankaUser {
+        id: u.get("id").and_then(|x| x.as_str()).unwrap_or_default().to_string(),
+        role: u.get("role").and_then(|x| x.as_str()).unwrap_or_default().to_string(),
+        name: u.get("name").and_then(|x| x.as_str()).unwrap_or_default().to_string(),
+        username: u.get("username").and_then(|x| x.as_str()).map(|s| s.to_string()),
+        email: u.get("email").and_then(|x| x.as_str()).map(|s| s.to_string()),
+        is_deactivated: u.get("isDeactivated").and_then(|x| x.as_bool()).unwrap_or(false),
+    }
+}
+
+// Instance-wide user roles, from most to least rights, with their display names
+pub const USER_ROLES: &[(&str, &str)] = &[("admin", "Admin"), ("projectOwner", "Project owner"), ("boardUser", "Board user")];
+
+pub fn user_role_name(role: &str) -> &str {
+    USER_ROLES.iter().find(|(r, _)| *r == role).map(|(_, n)| *n).unwrap_or(role)
+}
+
+// Label colors Planka accepts, in the order its color picker shows them
+pub const LABEL_COLORS: &[&str] = &[
+    "berry-red", "pumpkin-orange", "lagoon-blue", "pink-tulip", "light-mud",
+    "orange-peel", "bright-moss", "antique-blue", "dark-granite", "lagune-blue",
+    "sunny-grass", "morning-sky", "light-orange", "midnight-blue", "tank-green",
+    "gun-metal", "wet-moss", "red-burgundy", "light-concrete", "apricot-red",
+    "desert-sand", "navy-blue", "egg-yellow", "coral-green", "light-cocoa",
+];
+
+// Default workflow: Todo/Doing/Done matched by name (case- and
+// space-insensitive, Doing optional), else every list in board order
+pub fn guess_workflow(lists: &[(String, String)]) -> Option<Vec<WorkflowStage>> {
+    let stage = |(id, name): &(String, String)| WorkflowStage { name: name.clone(), list_id: id.clone() };
+    let mut todo: Option<WorkflowStage> = None;
+    let mut doing: Option<WorkflowStage> = None;
+    let mut done: Option<WorkflowStage> = None;
+    for l in lists {
+        let n = l.1.to_lowercase().replace(' ', "");
+        if n.contains("todo") || n.contains("to-do") || n.contains("to_do") || n.contains("to.do") {
+            if todo.is_none() { todo = Some(stage(l)); }
+        } else if n.contains("doing") || n.contains("inprogress") || n.contains("in-progress") || n.contains("in_progress") {
+            if doing.is_none() { doing = Some(stage(l)); }
+        } else if (n.contains("done") || n.contains("completed") || n.contains("complete")) && done.is_none() {
+            done = Some(stage(l));
+        }
+    }
+    if let (Some(todo), Some(done)) = (todo, done) {
+        return Some([Some(todo), doing, Some(done)].into_iter().flatten().collect());
+    }
+    if lists.len() < 2 {
+        return None;
+    }
+    Some(lists.iter().map(stage).collect())
 }
 
 #[derive(Clone, Debug)]
@@ -3184,6 +3743,19 @@ pub struct PlankaCard {
     pub name: String,
     pub due: Option<String>,
     pub created: Option<String>,
+    pub position: f64,
+    pub label_ids: Vec<String>,
+    pub member_ids: Vec<String>, // user ids
+}
+
+// A board label; Planka allows labels without a name (color only)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlankaLabel {
+    pub id: String,
+    pub name: String,
+    pub color: String,
+    #[serde(default)]
+    pub position: f64,
 }
 
 #[derive(Clone, Debug)]
@@ -3234,8 +3806,11 @@ pub struct PlankaCardDetails {
     pub is_due_completed: Option<bool>,
     pub created: Option<String>,
     pub updated: Option<String>,
+    pub list_id: Option<String>,
     pub list_name: Option<String>,
     pub labels: Vec<String>,
+    pub label_ids: Vec<String>,
+    pub member_ids: Vec<String>,
     pub attachments: Vec<String>,
     pub tasks: Vec<(String, bool)>, // (name, isCompleted)
     pub board_id: Option<String>,
@@ -3255,6 +3830,46 @@ pub struct PlankaAction {
     pub created: Option<String>,
 }
 
+impl PlankaAction {
+    // What happened, to follow the name of whoever did it ("moved “X” to Done").
+    // `with_card` names the card, for timelines that span a whole board.
+    pub fn describe(&self, with_card: bool) -> String {
+        let field = |path: &[&str]| {
+            let mut v = self.data.as_ref()?;
+            for key in path {
+                v = v.get(*key)?;
+            }
+            v.as_str().map(|s| s.to_string())
+        };
+        let name = field(&["card", "name"]).map(|n| format!("“{}”", n)).unwrap_or_else(|| "a card".to_string());
+        // "moved “X” to…" / "moved to…", "added Bob to “X”" / "added Bob to this card"
+        let (card, this, on) = if with_card {
+            (format!(" {}", name), format!(" {}", name), format!(" on {}", name))
+        } else {
+            (String::new(), " this card".to_string(), String::new())
+        };
+        let list = |key: &str| field(&[key, "name"]).unwrap_or_else(|| "?".to_string());
+        let task = || field(&["task", "name"]).unwrap_or_default();
+        match self.type_.as_str() {
+            "createCard" => format!("created{} in {}", this, list("list")),
+            "duplicateCard" => format!("duplicated{}", this),
+            "moveCard" => format!("moved{} from {} to {}", card, list("fromList"), list("toList")),
+            "commentCard" => format!("commented{}: {}", on, field(&["text"]).unwrap_or_default()),
+            "addMemberToCard" => match field(&["user", "name"]) {
+                Some(u) => format!("added {} to{}", u, this),
+                None => format!("joined{}", this),
+            },
+            "removeMemberFromCard" => match field(&["user", "name"]) {
+                Some(u) => format!("removed {} from{}", u, this),
+                None => format!("left{}", this),
+            },
+            "completeTask" => format!("completed “{}”{}", task(), on),
+            "uncompleteTask" => format!("reopened “{}”{}", task(), on),
+            other => format!("{}{}", other, card),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PlankaProject {
     pub id: String,
@@ -3285,7 +3900,23 @@ pub struct PlankaBoardDetails {
     pub name: String,
     pub project_id: Option<String>,
     pub lists: Vec<(String, String)>,   // (id, name)
-    pub labels: Vec<(String, String, String)>, // (id, name, color)
+    pub labels: Vec<PlankaLabel>,
+    pub members: Vec<PlankaUser>,
+    pub memberships: Vec<PlankaBoardMembership>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PlankaBoardMembership {
+    pub id: String,
+    pub user_id: String,
+    pub role: String,              // "editor" | "viewer"
+    pub can_comment: Option<bool>, // only set for viewers; editors can always comment
+}
+
+impl PlankaBoardMembership {
+    pub fn may_comment(&self) -> bool {
+        self.role == "editor" || self.can_comment.unwrap_or(false)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -3318,6 +3949,29 @@ pub struct PlankaNotification {
     pub text: Option<String>,
     pub is_read: bool,
     pub created: Option<String>,
+    pub creator: Option<String>,   // name of the user who caused it
+    pub card_name: Option<String>,
+    pub to_list: Option<String>,   // moveCard: the list the card went to
+}
+
+impl PlankaNotification {
+    // One line in the words of Planka's own notification list
+    pub fn summary(&self) -> String {
+        let who = self.creator.as_deref().unwrap_or("Someone");
+        let card = self.card_name.as_deref().map(|c| format!("“{}”", c)).unwrap_or_else(|| "a card".to_string());
+        let text = self.text.as_deref().map(|t| t.lines().next().unwrap_or_default()).unwrap_or_default();
+        match self.r#type.as_str() {
+            "moveCard" => match self.to_list {
+                Some(ref list) => format!("{} moved {} to {}", who, card, list),
+                None => format!("{} moved {}", who, card),
+            },
+            "commentCard" => format!("{} commented on {}: {}", who, card, text),
+            "mentionInComment" => format!("{} mentioned you on {}: {}", who, card, text),
+            "addMemberToCard" => format!("{} added you to {}", who, card),
+            other if !text.is_empty() => format!("{} ({}) on {}: {}", who, other, card, text),
+            other => format!("{} ({}) on {}", who, other, card),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -3329,7 +3983,7 @@ pub struct PlankaNotificationService {
     pub format: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PlankaUser {
     pub id: String,
     pub role: String,
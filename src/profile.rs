@@ -0,0 +1,75 @@
+// profile.rs
+//
+// Named server profiles. The default profile keeps the original layout
+// (~/.config/Planky/*.json) so existing setups carry on unchanged; a named
+// profile such as `--profile work` keeps its own planka.json, todos and op
+// queues under ~/.config/Planky/profiles/work/.
+use std::fs::create_dir_all;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+pub const DEFAULT: &str = "default";
+
+static ACTIVE: RwLock<Option<String>> = RwLock::new(None);
+
+// ~/.config/Planky (XDG_CONFIG_HOME if set)
+pub fn base_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            std::env::var_os("HOME")
+                .map(PathBuf::from)
+                .map(|p| p.join(".config"))
+                .unwrap_or_else(|| PathBuf::from("."))
+        });
+    let dir = base.join("Planky");
+    create_dir_all(&dir).ok();
+    dir
+}
+
+pub fn active() -> String {
+    ACTIVE.read().unwrap().clone().unwrap_or_else(|| DEFAULT.to_string())
+}
+
+pub fn set_active(name: &str) {
+    *ACTIVE.write().unwrap() = if name == DEFAULT { None } else { Some(name.to_string()) };
+}
+
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub fn dir_of(name: &str) -> PathBuf {
+    let base = base_dir();
+    if name == DEFAULT {
+        return base;
+    }
+    let dir = base.join("profiles").join(name);
+    create_dir_all(&dir).ok();
+    dir
+}
+
+// Directory holding the active profile's files
+pub fn dir() -> PathBuf {
+    dir_of(&active())
+}
+
+pub fn path(file: &str) -> PathBuf {
+    dir().join(file)
+}
+
+// Default first, then the named profiles alphabetically
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(base_dir().join("profiles"))
+        .map(|rd| {
+            rd.filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| e.file_name().into_string().ok())
+                .filter(|n| is_valid_name(n))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names.insert(0, DEFAULT.to_string());
+    names
+}
@@ -0,0 +1,265 @@
+// realtime.rs
+//
+// Minimal Planka socket subscriber. Planka speaks sails.io on top of
+// socket.io (engine.io v3): we open a websocket, subscribe to each board with
+// a virtual GET request and turn card events into `Delta`s.
+use crate::app::Delta;
+use crate::planka::{self, PlankaClient, PlankaLists};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+use tungstenite::client::IntoClientRequest;
+use tungstenite::handshake::client::Request;
+use tungstenite::HandshakeError;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+type Socket = WebSocket<MaybeTlsStream<TcpStream>>;
+
+fn socket_url(base_url: &str) -> String {
+    let base = base_url.trim_end_matches('/');
+    let ws = if let Some(rest) = base.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = base.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        format!("ws://{}", base)
+    };
+    format!(
+        "{}/socket.io/?__sails_io_sdk_version=1.2.1&__sails_io_sdk_platform=node&__sails_io_sdk_language=javascript&EIO=3&transport=websocket",
+        ws
+    )
+}
+
+// Open the websocket with the same connect timeout as the HTTP client, and a
+// read timeout so a server that never finishes the TLS or upgrade handshake
+// can't hold the sync thread (and its polling fallback) forever
+fn connect(req: Request) -> Result<Socket, String> {
+    let uri = req.uri();
+    let host = uri.host().ok_or("Socket URL has no host")?.trim_start_matches('[').trim_end_matches(']').to_string();
+    let port = uri.port_u16().unwrap_or(if uri.scheme_str() == Some("wss") { 443 } else { 80 });
+    let addrs = (host.as_str(), port).to_socket_addrs().map_err(|e| format!("Socket connect failed: {}", e))?;
+    let mut last_error = format!("Socket connect failed: {} has no address", host);
+    for addr in addrs {
+        match TcpStream::connect_timeout(&addr, planka::CONNECT_TIMEOUT) {
+            Ok(stream) => {
+                let _ = stream.set_read_timeout(Some(planka::CONNECT_TIMEOUT));
+                let _ = stream.set_write_timeout(Some(planka::CONNECT_TIMEOUT));
+                return match tungstenite::client_tls_with_config(req, stream, None, None) {
+                    Ok((ws, _)) => Ok(ws),
+                    // The read timeout hit mid-handshake
+                    Err(HandshakeError::Interrupted(_)) => Err("Socket handshake timed out".to_string()),
+                    Err(HandshakeError::Failure(e)) => Err(format!("Socket connect failed: {}", e)),
+                };
+            }
+            Err(e) => last_error = format!("Socket connect failed: {}", e),
+        }
+    }
+    Err(last_error)
+}
+
+fn set_read_timeout(ws: &mut Socket, dur: Duration) {
+    let _ = match ws.get_mut() {
+        MaybeTlsStream::Plain(s) => s.set_read_timeout(Some(dur)),
+        MaybeTlsStream::Rustls(s) => s.get_mut().set_read_timeout(Some(dur)),
+        _ => Ok(()),
+    };
+}
+
+fn send_text(ws: &mut Socket, s: String) -> Result<(), String> {
+    ws.send(Message::text(s))
+        .map_err(|e| format!("Socket send failed: {}", e))
+}
+
+// Blocks while the socket is healthy, forwarding card events to `tx`.
+// Returns Ok(()) when the board/list layout changed and the caller should
+// re-resolve lists and resubscribe; Err when the connection dropped or
+// `stop` was raised (the app switched profiles).
+pub fn run(client: &PlankaClient, boards: &[(String, PlankaLists)], tx: &Sender<Delta>, stop: &AtomicBool) -> Result<(), String> {
+    if boards.is_empty() {
+        return Err("No boards to subscribe to".to_string());
+    }
+    // list_id -> (project key, is done list)
+    let mut by_list: HashMap<String, (String, bool)> = HashMap::new();
+    let mut by_board: HashMap<String, String> = HashMap::new();
+    for (project, l) in boards {
+        by_board.insert(l.board_id.clone(), project.clone());
+        for stage in &l.stages {
+            by_list.insert(stage.list_id.clone(), (project.clone(), stage.list_id == l.done_list_id()));
+        }
+    }
+
+    let auth_headers = client.auth_headers();
+    let mut req = socket_url(&client.base_url)
+        .into_client_request()
+        .map_err(|e| format!("Socket URL invalid: {}", e))?;
+    for (name, value) in &auth_headers {
+        if let Ok(v) = value.parse() {
+            req.headers_mut().insert(*name, v);
+        }
+    }
+    // Virtual requests carry the same credentials as the handshake
+    let auth: serde_json::Map<String, Value> =
+        auth_headers.into_iter().map(|(k, v)| (k.to_string(), Value::String(v))).collect();
+    let mut ws = connect(req)?;
+
+    // Engine.io handshake: "0{...pingInterval...}"
+    let mut ping_interval = Duration::from_millis(25000);
+    if let Ok(Message::Text(t)) = ws.read()
+        && let Some(json) = t.as_str().strip_prefix('0')
+        && let Ok(v) = serde_json::from_str::<Value>(json)
+        && let Some(ms) = v.get("pingInterval").and_then(|x| x.as_u64())
+    {
+        ping_interval = Duration::from_millis(ms);
+    }
+    set_read_timeout(&mut ws, Duration::from_secs(1));
+
+    // Subscribe to each board through a sails virtual request
+    for (ack, (_, l)) in boards.iter().enumerate() {
+        let payload = serde_json::json!([
+            "get",
+            {
+                "method": "get",
+                "headers": auth,
+                "data": {},
+                "url": format!("/api/boards/{}?subscribe=true", l.board_id),
+            }
+        ]);
+        send_text(&mut ws, format!("42{}{}", ack, payload))?;
+    }
+    // Fetching the current user over the socket joins its room, which carries
+    // notification events. Best effort: its ack is not checked.
+    let user_ack = boards.len();
+    let payload = serde_json::json!([
+        "get",
+        { "method": "get", "headers": auth, "data": {}, "url": "/api/users/me?subscribe=true" }
+    ]);
+    send_text(&mut ws, format!("42{}{}", user_ack, payload))?;
+
+    let mut last_ping = Instant::now();
+    loop {
+        if stop.load(Ordering::Relaxed) {
+            let _ = ws.close(None);
+            return Err("Stopped".to_string());
+        }
+        if last_ping.elapsed() >= ping_interval {
+            send_text(&mut ws, "2".to_string())?;
+            last_ping = Instant::now();
+        }
+        let msg = match ws.read() {
+            Ok(m) => m,
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+            {
+                continue;
+            }
+            Err(e) => return Err(format!("Socket read failed: {}", e)),
+        };
+        let text = match msg {
+            Message::Text(t) => t.as_str().to_string(),
+            Message::Close(_) => return Err("Socket closed by server".to_string()),
+            _ => continue,
+        };
+        if text == "1" || text == "41" {
+            return Err("Socket closed by server".to_string());
+        }
+        if let Some(rest) = text.strip_prefix("43") {
+            // Ack for a subscribe request: "43<id>[{statusCode, body}]"
+            let json = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+            let id = rest[..rest.len() - json.len()].parse::<usize>().ok();
+            if id != Some(user_ack)
+                && let Ok(v) = serde_json::from_str::<Value>(json)
+            {
+                let status = v.get(0).and_then(|r| r.get("statusCode")).and_then(|x| x.as_u64()).unwrap_or(200);
+                if status >= 400 {
+                    return Err(format!("Socket subscribe failed: HTTP {}", status));
+                }
+            }
+            continue;
+        }
+        let Some(json) = text.strip_prefix("42") else { continue; };
+        let Ok(v) = serde_json::from_str::<Value>(json) else { continue; };
+        let event = v.get(0).and_then(|x| x.as_str()).unwrap_or_default();
+        let item = v.get(1).and_then(|x| x.get("item"));
+        match event {
+            "cardCreate" | "cardUpdate" => {
+                let Some(item) = item else { continue; };
+                let id = item.get("id").and_then(|x| x.as_str());
+                let name = item.get("name").and_then(|x| x.as_str());
+                let list_id = item.get("listId").and_then(|x| x.as_str());
+                if let (Some(id), Some(name), Some(list_id)) = (id, name, list_id)
+                    && let Some((project, done)) = by_list.get(list_id)
+                {
+                    let _ = tx.send(Delta::Upsert {
+                        project: project.clone(),
+                        id: id.to_string(),
+                        name: name.to_string(),
+                        due: item.get("dueDate").and_then(|x| x.as_str()).map(|s| s.to_string()),
+                        created: item.get("createdAt").and_then(|x| x.as_str()).map(|s| s.to_string()),
+                        done: *done,
+                        list_id: list_id.to_string(),
+                        labels: None,
+                        members: None,
+                    });
+                } else if let (Some(id), Some(project)) = (id, item.get("boardId").and_then(|x| x.as_str()).and_then(|b| by_board.get(b))) {
+                    // Moved out of the tracked lists (e.g. into the archive)
+                    let _ = tx.send(Delta::Archived { project: project.clone(), id: id.to_string() });
+                }
+            }
+            "cardDelete" => {
+                let Some(item) = item else { continue; };
+                let id = item.get("id").and_then(|x| x.as_str());
+                let project = item.get("boardId").and_then(|x| x.as_str()).and_then(|b| by_board.get(b));
+                if let (Some(id), Some(project)) = (id, project) {
+                    let _ = tx.send(Delta::Delete { project: project.clone(), id: id.to_string() });
+                }
+            }
+            "cardLabelCreate" | "cardLabelDelete" => {
+                let Some(item) = item else { continue; };
+                let card_id = item.get("cardId").and_then(|x| x.as_str());
+                let label_id = item.get("labelId").and_then(|x| x.as_str());
+                if let (Some(card_id), Some(label_id)) = (card_id, label_id) {
+                    let _ = tx.send(Delta::CardLabel {
+                        card_id: card_id.to_string(),
+                        label_id: label_id.to_string(),
+                        added: event == "cardLabelCreate",
+                    });
+                }
+            }
+            "cardMembershipCreate" | "cardMembershipDelete" => {
+                let Some(item) = item else { continue; };
+                let card_id = item.get("cardId").and_then(|x| x.as_str());
+                let user_id = item.get("userId").and_then(|x| x.as_str());
+                if let (Some(card_id), Some(user_id)) = (card_id, user_id) {
+                    let _ = tx.send(Delta::CardMember {
+                        card_id: card_id.to_string(),
+                        user_id: user_id.to_string(),
+                        added: event == "cardMembershipCreate",
+                    });
+                }
+            }
+            "notificationCreate" | "notificationUpdate" => {
+                let Some(item) = item else { continue; };
+                let users = v.get(1).and_then(|x| x.get("included")).and_then(|i| i.get("users")).and_then(|x| x.as_array());
+                let n = planka::parse_notification(item, users.map(|u| u.as_slice()).unwrap_or(&[]));
+                let _ = tx.send(Delta::Notification(n));
+            }
+            "labelCreate" | "labelUpdate" | "labelDelete" => {
+                let Some(item) = item else { continue; };
+                let project = item.get("boardId").and_then(|x| x.as_str()).and_then(|b| by_board.get(b));
+                if let (Some(project), Some(label)) = (project, planka::parse_label(item)) {
+                    let _ = tx.send(Delta::Label { project: project.clone(), label, deleted: event == "labelDelete" });
+                }
+            }
+            // List layout changed: let the caller re-resolve and resubscribe
+            "listCreate" | "listUpdate" | "listDelete" | "boardCreate" | "boardUpdate" | "boardDelete" => {
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+}
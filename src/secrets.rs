@@ -0,0 +1,132 @@
+// secrets.rs
+//
+// Where the Planka password lives. By default it stays in planka.json as
+// before; `secret_store` can instead keep it in the OS keyring, hand it to an
+// external helper such as `pass`, or not store it at all (token only, Planky
+// asks again when the token is rejected). Also owns the 0600 file helper used
+// for everything Planky writes to its config dir.
+use crate::planka::PlankaConfig;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const KEYRING_SERVICE: &str = "planky";
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretStore {
+    // Plaintext in planka.json
+    #[default]
+    Config,
+    // Never written anywhere; only the token is kept
+    None,
+    // Secret Service on Linux, Keychain on macOS, Credential Manager on Windows
+    Keyring,
+    // Shell commands: `get` prints the password, `store` reads it on stdin
+    Command { get: String, store: Option<String> },
+}
+
+fn keyring_entry(cfg: &PlankaConfig) -> Result<keyring::Entry, String> {
+    let user = format!("{}@{}", cfg.email_or_username, cfg.server_url.trim_end_matches('/'));
+    keyring::Entry::new(KEYRING_SERVICE, &user).map_err(|e| format!("Keyring unavailable: {}", e))
+}
+
+fn shell(cmd: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut c = Command::new("cmd");
+        c.args(["/C", cmd]);
+        c
+    }
+    #[cfg(not(windows))]
+    {
+        let mut c = Command::new("sh");
+        c.args(["-c", cmd]);
+        c
+    }
+}
+
+// Password to log in with: the in-memory one, else whatever the store holds
+pub fn password(cfg: &PlankaConfig) -> Option<String> {
+    if !cfg.password.is_empty() {
+        return Some(cfg.password.clone());
+    }
+    match cfg.secret_store {
+        SecretStore::Config | SecretStore::None => None,
+        SecretStore::Keyring => keyring_entry(cfg).ok()?.get_password().ok(),
+        SecretStore::Command { ref get, .. } => {
+            let out = shell(get).stderr(Stdio::null()).output().ok()?;
+            if !out.status.success() {
+                return None;
+            }
+            // `pass` and friends print the secret on the first line
+            let text = String::from_utf8_lossy(&out.stdout);
+            let pw = text.lines().next().unwrap_or_default().to_string();
+            if pw.is_empty() { None } else { Some(pw) }
+        }
+    }
+}
+
+// Hand a freshly entered password to the configured store
+pub fn store_password(cfg: &PlankaConfig, password: &str) -> Result<(), String> {
+    match cfg.secret_store {
+        // `save_config` writes it for Config; None keeps it in memory only
+        SecretStore::Config | SecretStore::None => Ok(()),
+        SecretStore::Keyring => keyring_entry(cfg)?
+            .set_password(password)
+            .map_err(|e| format!("Keyring store failed: {}", e)),
+        SecretStore::Command { store: None, .. } => Ok(()),
+        SecretStore::Command { store: Some(ref store), .. } => {
+            let mut child = shell(store)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| format!("Password helper failed to start: {}", e))?;
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = writeln!(stdin, "{}", password);
+            }
+            let status = child.wait().map_err(|e| format!("Password helper failed: {}", e))?;
+            if status.success() {
+                Ok(())
+            } else {
+                Err(format!("Password helper exited with {}", status))
+            }
+        }
+    }
+}
+
+// Remove the keyring entry filed under `cfg`'s login name, e.g. after the name changed
+pub fn forget_password(cfg: &PlankaConfig) -> Result<(), String> {
+    if cfg.secret_store != SecretStore::Keyring {
+        return Ok(());
+    }
+    match keyring_entry(cfg)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Keyring delete failed: {}", e)),
+    }
+}
+
+// Open a file for writing, readable by the owner only. Also tightens files
+// created by older versions, which used the default umask.
+pub fn open_private(path: &Path, append: bool) -> std::io::Result<File> {
+    let mut opts = OpenOptions::new();
+    opts.create(true);
+    if append {
+        opts.append(true);
+    } else {
+        opts.write(true).truncate(true);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        opts.mode(0o600);
+        let file = opts.open(path)?;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        Ok(file)
+    }
+    #[cfg(not(unix))]
+    opts.open(path)
+}
@@ -3,8 +3,26 @@
 use chrono::Local;
 use serde::{Deserialize, Serialize};
 fn default_project() -> String { "Inbox".to_string() }
+pub fn new_id() -> String { uuid::Uuid::new_v4().to_string() }
+
+// Last state both sides agreed on; the base for three-way merges
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SyncBase {
+    pub name: String,
+    pub due: Option<String>,
+    pub list_id: Option<String>,
+    pub done: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Todo {
+    // client-side id so queued ops can find this todo before it has a card id
+    #[serde(default = "new_id")]
+    pub local_id: String,
     pub description: String,
     pub due_date: Option<String>,
     pub created_date: String,
@@ -17,6 +35,17 @@ pub struct Todo {
     pub planka_list_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub planka_board_id: Option<String>,
+    // Planka label ids of the card (names and colors live in App::board_labels)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    // Planka user ids assigned to the card (names live in App::board_members)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<String>,
+    // tombstone: card left the tracked lists on Planka (archived/moved away)
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<SyncBase>,
     #[serde(skip)]
     pub sync_dirty: bool,
 }
@@ -24,6 +53,7 @@ pub struct Todo {
 impl Todo {
     pub fn new(description: String, due_date: Option<String>) -> Self {
         Self {
+            local_id: new_id(),
             description,
             due_date,
             created_date: Local::now().format("%Y-%m-%d").to_string(),
@@ -32,7 +62,22 @@ impl Todo {
             planka_card_id: None,
             planka_list_id: None,
             planka_board_id: None,
+            labels: Vec::new(),
+            members: Vec::new(),
+            archived: false,
+            base: None,
             sync_dirty: false,
         }
     }
+
+    pub fn snapshot(&self) -> SyncBase {
+        SyncBase {
+            name: self.description.clone(),
+            due: self.due_date.clone(),
+            list_id: self.planka_list_id.clone(),
+            done: self.done,
+            labels: self.labels.clone(),
+            members: self.members.clone(),
+        }
+    }
 }
@@ -1,6 +1,7 @@
 // tui.rs
 
-use crate::app::{App, InputMode};
+use crate::app::{is_secret_prompt, AccountField, App, EditorTarget, InputMode};
+use crate::planka::{self, PlankaLabel, PlankaUser};
 use chrono::{
     Datelike, Duration as Dur, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike,
     Weekday,
@@ -9,10 +10,10 @@ use crossterm::event::{self, Event as CEvent, KeyCode, KeyEventKind, KeyModifier
 use ratatui::{
     Terminal,
     backend::Backend,
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Line},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
 use std::{io, time::Duration};
 use std::io::Write;
@@ -27,25 +28,19 @@ where
     app.start_background_sync();
 
     // Eagerly resolve lists for the current project so [w] is shown immediately
-    if app.planka_lists_by_board.get(&app.current_project).is_none() {
-        if let Ok(client) = app.ensure_planka_client() {
-            // Ensure boards are cached so the header can show "Project - Board"
-            if app.planka_boards.is_empty() {
-                if let Ok(boards) = client.fetch_boards() {
-                    app.planka_boards = boards;
-                }
-            }
-            if let Ok(lists) = client.resolve_lists(&app.current_project) {
-                app.planka_lists_by_board
-                    .insert(app.current_project.clone(), lists.clone());
-                app.planka_lists = Some(lists);
-            }
-        }
-    }
+    app.ensure_lists();
 
     loop {
-        // process inbound updates and retry queued outbound ops
+        // A key asked for $EDITOR; run it with the terminal handed over
+        if let Some(target) = app.editor_request.take() {
+            let text = app.editor_text(target);
+            let edited = run_editor(terminal, &text)?;
+            app.finish_editor(target, edited);
+        }
+
+        // process inbound updates, finished network jobs and queued outbound ops
         app.drain_inbound();
+        app.drain_worker();
         app.process_pending_ops_tick();
         terminal.draw(|f| ui(f, app))?;
 
@@ -58,6 +53,22 @@ where
                 match app.input_mode {
                     InputMode::Normal => match key.code {
                         KeyCode::Char('q') => return Ok(()),
+                        KeyCode::Char('v') => app.toggle_board_view(),
+                        KeyCode::Char('f') => app.open_label_filter(),
+                        // Board view: Shift+arrows (or < > K J) move the card, arrows move the cursor
+                        KeyCode::Left if app.board_view && key.modifiers.contains(KeyModifiers::SHIFT) => app.kanban_move_card(-1),
+                        KeyCode::Right if app.board_view && key.modifiers.contains(KeyModifiers::SHIFT) => app.kanban_move_card(1),
+                        KeyCode::Up if app.board_view && key.modifiers.contains(KeyModifiers::SHIFT) => app.kanban_reorder(-1),
+                        KeyCode::Down if app.board_view && key.modifiers.contains(KeyModifiers::SHIFT) => app.kanban_reorder(1),
+                        KeyCode::Char('<') if app.board_view => app.kanban_move_card(-1),
+                        KeyCode::Char('>') if app.board_view => app.kanban_move_card(1),
+                        KeyCode::Char('K') if app.board_view => app.kanban_reorder(-1),
+                        KeyCode::Char('J') if app.board_view => app.kanban_reorder(1),
+                        KeyCode::Left if app.board_view => app.kanban_step_column(-1),
+                        KeyCode::Right if app.board_view => app.kanban_step_column(1),
+                        KeyCode::Up if app.board_view => app.kanban_step_row(-1),
+                        KeyCode::Down if app.board_view => app.kanban_step_row(1),
+                        KeyCode::Char('r') if app.board_view && !key.modifiers.contains(KeyModifiers::SHIFT) => app.refresh_kanban(),
                         KeyCode::Char('a') => {
                             app.input_mode = InputMode::EditingDescription;
                             app.input_description.clear();
@@ -72,9 +83,8 @@ where
                             app.delete_todo();
                         }
                         KeyCode::Char('d') => app.mark_done(),
-                        KeyCode::Char('w') => {
-                            app.mark_doing();
-                        }
+                        KeyCode::Char('w') => app.advance(),
+                        KeyCode::Char('b') => app.retreat(),
                         KeyCode::Char('?') => {
                             app.input_mode = InputMode::Searching;
                             app.search_query.clear();
@@ -93,9 +103,14 @@ where
                             app.open_selected_card();
                         }
                         KeyCode::Char('c') => {
-                            let list = filtered_todos(app);
-                            if let Some(todo) = list.get(app.selected) {
-                                if let Err(e) = copy_to_clipboard(&todo.description) {
+                            // The highlighted card in board view, else the list selection
+                            let text = if app.board_view {
+                                app.kanban_card().map(|c| c.name.clone())
+                            } else {
+                                filtered_todos(app).get(app.selected).map(|t| t.description.clone())
+                            };
+                            if let Some(text) = text {
+                                if let Err(e) = copy_to_clipboard(&text) {
                                     app.error_message = Some(format!("Copy failed: {}", e));
                                 } else {
                                     app.error_message = Some("Copied task to clipboard".to_string());
@@ -128,7 +143,7 @@ where
                         }
                         KeyCode::Char('l') => {
                             app.input_mode = InputMode::EditingProject;
-                            app.input_project = app.current_project.clone();
+                            app.input_project = app.project_name(&app.current_project);
                             app.error_message = None;
                         }
                         KeyCode::Char('S') => {
@@ -141,6 +156,264 @@ where
                             app.input_mode = InputMode::ControlCenter;
                             app.error_message = None;
                         }
+                        KeyCode::Char('C') => {
+                            app.open_conflicts();
+                        }
+                        KeyCode::Char('m') => app.open_my_cards(),
+                        KeyCode::Char('n') => app.open_notifications(),
+                        KeyCode::Char('A') => app.open_board_activity(),
+                        _ => {}
+                    },
+                    InputMode::ViewingPendingOps => match key.code {
+                        KeyCode::Esc => {
+                            app.input_mode = InputMode::Normal;
+                        }
+                        KeyCode::Down
+                            if app.ops_index + 1 < app.pending_ops.len() + app.dead_ops.len() =>
+                        {
+                            app.ops_index += 1;
+                        }
+                        KeyCode::Up => {
+                            app.ops_index = app.ops_index.saturating_sub(1);
+                        }
+                        KeyCode::Char('r') => app.retry_selected_op(),
+                        KeyCode::Char('x') => app.discard_selected_op(),
+                        _ => {}
+                    },
+                    InputMode::SwitchingProfile => match key.code {
+                        KeyCode::Esc => {
+                            app.input_mode = InputMode::ControlCenter;
+                        }
+                        KeyCode::Down if app.profile_index + 1 < app.profiles.len() => {
+                            app.profile_index += 1;
+                        }
+                        KeyCode::Up => {
+                            app.profile_index = app.profile_index.saturating_sub(1);
+                        }
+                        KeyCode::Enter => app.switch_to_selected_profile(),
+                        KeyCode::Char('n') => {
+                            app.input_profile.clear();
+                            app.input_mode = InputMode::CreatingProfile;
+                        }
+                        _ => {}
+                    },
+                    InputMode::EditingWorkflow => match key.code {
+                        KeyCode::Esc => {
+                            app.workflow_rows.clear();
+                            app.input_mode = InputMode::ControlCenter;
+                        }
+                        KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => app.move_workflow_row(1),
+                        KeyCode::Up if key.modifiers.contains(KeyModifiers::SHIFT) => app.move_workflow_row(-1),
+                        KeyCode::Char('J') => app.move_workflow_row(1),
+                        KeyCode::Char('K') => app.move_workflow_row(-1),
+                        KeyCode::Down if app.workflow_index + 1 < app.workflow_rows.len() => {
+                            app.workflow_index += 1;
+                        }
+                        KeyCode::Up => {
+                            app.workflow_index = app.workflow_index.saturating_sub(1);
+                        }
+                        KeyCode::Char(' ') => app.toggle_workflow_row(),
+                        KeyCode::Enter => { if let Err(e) = app.save_workflow(true) { app.error_message = Some(e); } }
+                        KeyCode::Char('g') => { if let Err(e) = app.save_workflow(false) { app.error_message = Some(e); } }
+                        _ => {}
+                    },
+                    InputMode::PickingLabel | InputMode::FilteringLabel => {
+                        let picking = app.input_mode == InputMode::PickingLabel;
+                        match key.code {
+                            KeyCode::Esc if picking => app.input_mode = InputMode::ViewingCard,
+                            KeyCode::Esc => app.input_mode = InputMode::Normal,
+                            KeyCode::Down if app.label_index + 1 < app.picker_labels().len() => {
+                                app.label_index += 1;
+                            }
+                            KeyCode::Up => {
+                                app.label_index = app.label_index.saturating_sub(1);
+                            }
+                            KeyCode::Char(' ') | KeyCode::Enter if picking => app.toggle_selected_label(),
+                            KeyCode::Char('n') if picking => app.begin_create_label(),
+                            KeyCode::Enter => app.apply_label_filter(),
+                            KeyCode::Char('x') if !picking => app.clear_label_filter(),
+                            _ => {}
+                        }
+                    }
+                    InputMode::PickingMember => match key.code {
+                        KeyCode::Esc => app.input_mode = InputMode::ViewingCard,
+                        KeyCode::Down if app.member_index + 1 < app.picker_members().len() => {
+                            app.member_index += 1;
+                        }
+                        KeyCode::Up => {
+                            app.member_index = app.member_index.saturating_sub(1);
+                        }
+                        KeyCode::Char(' ') | KeyCode::Enter => app.toggle_selected_member(),
+                        _ => {}
+                    },
+                    InputMode::ViewingMyCards => match key.code {
+                        KeyCode::Esc => app.input_mode = InputMode::Normal,
+                        KeyCode::Down if app.my_cards_index + 1 < app.my_cards.len() => {
+                            app.my_cards_index += 1;
+                        }
+                        KeyCode::Up => {
+                            app.my_cards_index = app.my_cards_index.saturating_sub(1);
+                        }
+                        KeyCode::Enter => app.open_selected_my_card(),
+                        KeyCode::Char('r') => app.refresh_my_cards(),
+                        _ => {}
+                    },
+                    InputMode::ViewingBoardActivity => match key.code {
+                        KeyCode::Esc => app.input_mode = InputMode::Normal,
+                        KeyCode::Down => app.board_activity_step(1),
+                        KeyCode::Up => app.board_activity_step(-1),
+                        KeyCode::PageDown => app.board_activity_step(10),
+                        KeyCode::PageUp => app.board_activity_step(-10),
+                        KeyCode::Enter => app.open_selected_board_action(),
+                        KeyCode::Char('r') => app.load_actions(true, false),
+                        _ => {}
+                    },
+                    InputMode::AcceptingTerms => match key.code {
+                        KeyCode::Down => app.scroll_terms(1),
+                        KeyCode::Up => app.scroll_terms(-1),
+                        KeyCode::PageDown => app.scroll_terms(10),
+                        KeyCode::PageUp => app.scroll_terms(-10),
+                        KeyCode::Char('a') => app.accept_terms(),
+                        KeyCode::Esc | KeyCode::Char('d') => app.decline_terms(),
+                        _ => {}
+                    },
+                    // A pending change takes only its confirmation
+                    InputMode::ManagingUsers if app.user_confirm.is_some() => match key.code {
+                        KeyCode::Char('y') => app.confirm_user_change(),
+                        _ => app.user_confirm = None,
+                    },
+                    InputMode::ManagingUsers => match key.code {
+                        KeyCode::Esc => app.input_mode = InputMode::ControlCenter,
+                        KeyCode::Down if app.user_index + 1 < app.users.len() => {
+                            app.user_index += 1;
+                        }
+                        KeyCode::Up => {
+                            app.user_index = app.user_index.saturating_sub(1);
+                        }
+                        KeyCode::Char('n') => app.begin_create_user(),
+                        KeyCode::Char('R') => app.ask_next_user_role(),
+                        KeyCode::Char('d') => app.ask_toggle_user_deactivated(),
+                        KeyCode::Char('x') => app.ask_delete_user(),
+                        KeyCode::Char('r') => app.refresh_users(),
+                        _ => {}
+                    },
+                    InputMode::CreatingUser => match key.code {
+                        KeyCode::Enter => { if let Err(e) = app.submit_user_form() { app.error_message = Some(e); } }
+                        KeyCode::Esc => app.cancel_create_user(),
+                        KeyCode::Char(c) => app.input_user.push(c),
+                        KeyCode::Backspace => { app.input_user.pop(); }
+                        _ => {}
+                    },
+                    InputMode::ManagingBoardMembers if app.membership_confirm.is_some() => match key.code {
+                        KeyCode::Char('y') => app.confirm_remove_membership(),
+                        _ => app.membership_confirm = None,
+                    },
+                    InputMode::ManagingBoardMembers => match key.code {
+                        KeyCode::Esc => app.input_mode = InputMode::ControlCenter,
+                        KeyCode::Down if app.membership_index + 1 < app.memberships.len() => {
+                            app.membership_index += 1;
+                        }
+                        KeyCode::Up => {
+                            app.membership_index = app.membership_index.saturating_sub(1);
+                        }
+                        KeyCode::Char('i') => app.open_invite(),
+                        KeyCode::Char('R') => app.toggle_membership_role(),
+                        KeyCode::Char('c') => app.toggle_membership_comment(),
+                        KeyCode::Char('x') => app.ask_remove_membership(),
+                        KeyCode::Char('r') => app.refresh_board_members(),
+                        _ => {}
+                    },
+                    InputMode::InvitingBoardMember => match key.code {
+                        KeyCode::Esc => app.input_mode = InputMode::ManagingBoardMembers,
+                        KeyCode::Down if app.invite_index + 1 < app.invitable.len() => {
+                            app.invite_index += 1;
+                        }
+                        KeyCode::Up => {
+                            app.invite_index = app.invite_index.saturating_sub(1);
+                        }
+                        KeyCode::Enter => app.invite_selected("editor"),
+                        KeyCode::Char('v') => app.invite_selected("viewer"),
+                        _ => {}
+                    },
+                    InputMode::EditingAccount => match key.code {
+                        KeyCode::Esc => app.input_mode = InputMode::ControlCenter,
+                        KeyCode::Down if app.account_index + 1 < AccountField::ALL.len() => {
+                            app.account_index += 1;
+                        }
+                        KeyCode::Up => {
+                            app.account_index = app.account_index.saturating_sub(1);
+                        }
+                        KeyCode::Enter => app.begin_account_change(),
+                        _ => {}
+                    },
+                    InputMode::ChangingAccount => match key.code {
+                        KeyCode::Enter => { if let Err(e) = app.submit_account_form() { app.error_message = Some(e); } }
+                        KeyCode::Esc => app.cancel_account_change(),
+                        KeyCode::Char(c) => app.input_account.push(c),
+                        KeyCode::Backspace => { app.input_account.pop(); }
+                        _ => {}
+                    },
+                    InputMode::ViewingNotifications => match key.code {
+                        KeyCode::Esc => app.input_mode = InputMode::Normal,
+                        KeyCode::Down if app.notification_index + 1 < app.notifications.len() => {
+                            app.notification_index += 1;
+                        }
+                        KeyCode::Up => {
+                            app.notification_index = app.notification_index.saturating_sub(1);
+                        }
+                        KeyCode::Enter => app.open_selected_notification(),
+                        KeyCode::Char(' ') => app.toggle_selected_notification_read(),
+                        KeyCode::Char('a') => app.mark_all_notifications_read(),
+                        KeyCode::Char('r') => app.refresh_notifications(),
+                        _ => {}
+                    },
+                    InputMode::CreatingLabel => match key.code {
+                        KeyCode::Enter => { if let Err(e) = app.submit_create_label() { app.error_message = Some(e); } }
+                        KeyCode::Esc => { app.input_mode = InputMode::PickingLabel; app.input_label.clear(); }
+                        KeyCode::Tab => app.cycle_label_color(1),
+                        KeyCode::BackTab => app.cycle_label_color(-1),
+                        KeyCode::Char(c) => app.input_label.push(c),
+                        KeyCode::Backspace => { app.input_label.pop(); }
+                        _ => {}
+                    },
+                    InputMode::CreatingProfile => match key.code {
+                        KeyCode::Enter => { if let Err(e) = app.submit_new_profile() { app.error_message = Some(e); } }
+                        KeyCode::Esc => { app.input_mode = InputMode::SwitchingProfile; app.input_profile.clear(); }
+                        KeyCode::Char(c) => app.input_profile.push(c),
+                        KeyCode::Backspace => { app.input_profile.pop(); }
+                        _ => {}
+                    },
+                    InputMode::ResolvingConflicts => match key.code {
+                        KeyCode::Esc => {
+                            app.input_mode = InputMode::Normal;
+                        }
+                        KeyCode::Down if app.conflict_index + 1 < app.conflicts.len() => {
+                            app.conflict_index += 1;
+                        }
+                        KeyCode::Up => {
+                            app.conflict_index = app.conflict_index.saturating_sub(1);
+                        }
+                        KeyCode::Char('l') => app.resolve_conflict_local(),
+                        KeyCode::Char('r') => app.resolve_conflict_remote(),
+                        KeyCode::Char('m') => app.begin_manual_conflict(),
+                        _ => {}
+                    },
+                    InputMode::EditingConflictValue => match key.code {
+                        KeyCode::Enter => {
+                            if let Err(e) = app.submit_manual_conflict() {
+                                app.error_message = Some(e);
+                            }
+                        }
+                        KeyCode::Esc => {
+                            app.input_conflict.clear();
+                            app.input_mode = InputMode::ResolvingConflicts;
+                        }
+                        KeyCode::Char(c) => {
+                            app.input_conflict.push(c);
+                        }
+                        KeyCode::Backspace => {
+                            app.input_conflict.pop();
+                        }
                         _ => {}
                     },
                     InputMode::EditingDescription => {
@@ -220,11 +493,7 @@ where
                         KeyCode::Enter => {
                             app.submit_planka_setup();
                         }
-                        KeyCode::Esc => {
-                            app.planka_setup = None;
-                            app.input_planka.clear();
-                            app.input_mode = InputMode::Normal;
-                        }
+                        KeyCode::Esc => app.cancel_planka_setup(),
                         KeyCode::Char(c) => {
                             app.input_planka.push(c);
                         }
@@ -288,26 +557,39 @@ where
                             app.close_view();
                         }
                         KeyCode::Tab => {
-                            app.view_card_tab = (app.view_card_tab + 1) % 4;
+                            app.select_card_tab((app.view_card_tab + 1) % 5);
                         }
                         KeyCode::BackTab => {
-                            app.view_card_tab = (app.view_card_tab + 3) % 4;
+                            app.select_card_tab((app.view_card_tab + 4) % 5);
                         }
                         KeyCode::Up => {
                             app.view_scroll = app.view_scroll.saturating_sub(1);
                         }
-                        KeyCode::Down => {
-                            app.view_scroll = app.view_scroll.saturating_add(1);
+                        KeyCode::Down | KeyCode::PageDown => {
+                            let step = if key.code == KeyCode::Down { 1 } else { 10 };
+                            app.view_scroll = app.view_scroll.saturating_add(step);
+                            if app.view_card_tab == 3 {
+                                app.card_activity_scrolled(card_activity_rows(terminal.size()?.into()));
+                            }
                         }
                         KeyCode::PageUp => {
                             app.view_scroll = app.view_scroll.saturating_sub(10);
                         }
-                        KeyCode::PageDown => {
-                            app.view_scroll = app.view_scroll.saturating_add(10);
-                        }
                         KeyCode::Char('c') => {
                             app.begin_new_comment();
                         }
+                        KeyCode::Char('C') => {
+                            app.begin_long_comment();
+                        }
+                        KeyCode::Char('E') => {
+                            app.begin_edit_description();
+                        }
+                        KeyCode::Char('l') => {
+                            app.open_label_picker();
+                        }
+                        KeyCode::Char('m') => {
+                            app.open_member_picker();
+                        }
                         KeyCode::Char('r') => {
                             app.begin_reply_to_last_comment();
                         }
@@ -365,10 +647,11 @@ where
                         KeyCode::Char('D') => { // delete selected group
                             app.delete_selected_custom_field_group();
                         }
-                        KeyCode::Char('1') if app.view_card_tab != 2 => { app.view_card_tab = 0; }
-                        KeyCode::Char('2') if app.view_card_tab != 2 => { app.view_card_tab = 1; }
-                        KeyCode::Char('3') if app.view_card_tab != 2 => { app.view_card_tab = 2; }
-                        KeyCode::Char('4') if app.view_card_tab != 2 => { app.view_card_tab = 3; }
+                        KeyCode::Char('1') if app.view_card_tab != 2 => app.select_card_tab(0),
+                        KeyCode::Char('2') if app.view_card_tab != 2 => app.select_card_tab(1),
+                        KeyCode::Char('3') if app.view_card_tab != 2 => app.select_card_tab(2),
+                        KeyCode::Char('4') if app.view_card_tab != 2 => app.select_card_tab(3),
+                        KeyCode::Char('5') if app.view_card_tab != 2 => app.select_card_tab(4),
                         KeyCode::Char(c) if app.view_card_tab == 2 && c >= '1' && c <= '9' => {
                             let idx = (c as u8 - b'1') as usize;
                             if let Some(vc) = app.view_card.as_ref() {
@@ -394,6 +677,10 @@ where
                             }
                             continue;
                         }
+                        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('e') {
+                            app.editor_request = Some(EditorTarget::Comment);
+                            continue;
+                        }
                         match key.code {
                             KeyCode::Enter => {
                                 match app.submit_comment() {
@@ -427,6 +714,10 @@ where
                             }
                             continue;
                         }
+                        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('e') {
+                            app.editor_request = Some(EditorTarget::Comment);
+                            continue;
+                        }
                         match key.code {
                             KeyCode::Enter => {
                                 match app.submit_edit_comment() {
@@ -557,8 +848,8 @@ where
                         KeyCode::Esc | KeyCode::Tab => {
                             app.input_mode = InputMode::Normal;
                         }
-                        KeyCode::Down => {
-                            if app.control_center_index < 4 { app.control_center_index += 1; }
+                        KeyCode::Down if app.control_center_index < 11 => {
+                            app.control_center_index += 1;
                         }
                         KeyCode::Up => {
                             if app.control_center_index > 0 { app.control_center_index -= 1; }
@@ -569,7 +860,14 @@ where
                                 1 => { app.begin_create_project(); }    // New project
                                 2 => { app.start_planka_setup(); app.input_mode = InputMode::EditingPlanka; }
                                 3 => { app.sync_all_projects_from_planka(); app.input_mode = InputMode::Normal; }
-                                4 => { app.input_mode = InputMode::Normal; }
+                                4 => { app.open_pending_ops(); }
+                                5 => { app.open_profiles(); }
+                                6 => { app.open_workflow(); }
+                                7 => { app.toggle_webhook(); }
+                                8 => { app.open_users(); }
+                                9 => { app.open_account(); }
+                                10 => { app.open_board_members(); }
+                                11 => { app.input_mode = InputMode::Normal; }
                                 _ => {}
                             }
                         }
@@ -606,76 +904,49 @@ where
     }
 }
 
+// Braille spinner advanced by wall clock, so it moves with the 100ms redraw
+fn spinner_frame() -> &'static str {
+    const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+    let ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    FRAMES[(ms / 100 % FRAMES.len() as u128) as usize]
+}
+
 fn filtered_todos(app: &App) -> Vec<&crate::todo::Todo> {
-    let base = app
-        .todos
-        .iter()
-        .filter(|t| t.project == app.current_project)
-        .filter(|t| {
-            if app.search_query.is_empty() {
-                true
-            } else {
-                let q = app.search_query.to_lowercase();
-                t.description.to_lowercase().contains(&q)
-                    || t.due_date
-                        .as_ref()
-                        .map(|d| d.to_lowercase().contains(&q))
-                        .unwrap_or(false)
-            }
-        });
-
-    let doing_id = app
-        .planka_lists_by_board
-        .get(&app.current_project)
-        .map(|l| l.doing_list_id.as_str());
-    let done_id = app
-        .planka_lists_by_board
-        .get(&app.current_project)
-        .map(|l| l.done_list_id.as_str());
-
-    let mut doing: Vec<&crate::todo::Todo> = Vec::new();
-    let mut todo: Vec<&crate::todo::Todo> = Vec::new();
-    let mut done: Vec<&crate::todo::Todo> = Vec::new();
-
-    for t in base {
-        let in_doing = doing_id
-            .map(|id| t.planka_list_id.as_deref() == Some(id))
-            .unwrap_or(false);
-        let in_done = done_id
-            .map(|id| t.planka_list_id.as_deref() == Some(id))
-            .unwrap_or(false);
-
-        if !t.done && in_doing {
-            doing.push(t);
-        } else if t.done || in_done {
-            done.push(t);
-        } else {
-            todo.push(t);
-        }
-    }
+    app.visible_indices().into_iter().map(|i| &app.todos[i]).collect()
+}
 
-    doing.into_iter().chain(todo).chain(done).collect()
+// Card view: top tabs, banner, tab content
+fn card_view_rows(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // tabs
+            Constraint::Length(6),  // banner
+            Constraint::Min(8),     // tab content
+        ])
+        .split(area)
+}
+
+// Entries the Activity tab shows at once: its bordered content area minus the borders
+fn card_activity_rows(area: Rect) -> usize {
+    card_view_rows(area)[2].height.saturating_sub(2) as usize
 }
 
 fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
     let size = f.area();
 
-    if matches!(app.input_mode, InputMode::ViewingCard) {
-        let area = f.area();
+    if matches!(
+        app.input_mode,
+        InputMode::ViewingCard | InputMode::PickingLabel | InputMode::CreatingLabel | InputMode::PickingMember
+    ) {
+        let rows = card_view_rows(f.area());
 
-        // Top tabs, banner, tab content
-        let rows = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(1),  // tabs
-                Constraint::Length(6),  // banner
-                Constraint::Min(8),     // tab content
-            ])
-            .split(area);
-
-        // Tabs: General | Custom Fields | Comments | Commands
+        // Tabs: Comments | General | Custom Fields | Activity | Commands
         let mut tab_spans: Vec<Span> = Vec::new();
-        let labels = ["Comments", "General", "Custom Fields", "Commands"];
+        let labels = ["Comments", "General", "Custom Fields", "Activity", "Commands"];
         for (i, label) in labels.iter().enumerate() {
             if i > 0 {
                 tab_spans.push(Span::raw(" | "));
@@ -687,7 +958,7 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
             };
             tab_spans.push(Span::styled(*label, style));
         }
-        tab_spans.push(Span::raw("   [1-4 or Tab/Shift+Tab to switch]"));
+        tab_spans.push(Span::raw("   [1-5 or Tab/Shift+Tab to switch]"));
         let tabs_line = Line::from(tab_spans);
         let tabs = Paragraph::new(tabs_line).alignment(Alignment::Left);
         f.render_widget(tabs, rows[0]);
@@ -695,7 +966,7 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
         // Banner with big title/subtitle/path
         if let Some(d) = app.view_card.as_ref() {
             let path = {
-                let board = app.current_project.clone();
+                let board = app.project_name(&app.current_project);
                 let list = d.list_name.clone().unwrap_or_else(|| "List".to_string());
                 format!("{}  •  {}", board, list)
             };
@@ -731,7 +1002,7 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
                     // Left: Meta + Custom Fields
                     let left_rows = Layout::default()
                         .direction(Direction::Vertical)
-                        .constraints([Constraint::Min(4), Constraint::Min(4)])
+                        .constraints([Constraint::Min(4), Constraint::Min(4), Constraint::Min(4)])
                         .split(cols[0]);
                     // Meta block (reuse existing meta_lines build)
                     let mut meta_lines: Vec<Line> = Vec::new();
@@ -744,11 +1015,46 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
                     if let Some(c) = d.is_due_completed { meta_lines.push(Line::from(format!("Due Completed: {}", if c { "yes" } else { "no" }))); }
                     if let Some(ref c) = d.created { meta_lines.push(Line::from(format!("Created: {}", c))); }
                     if let Some(ref u) = d.updated { meta_lines.push(Line::from(format!("Updated: {}", u))); }
-                    if !d.labels.is_empty() { meta_lines.push(Line::from(format!("Labels: {}", d.labels.join(", ")))); }
+                    if !d.label_ids.is_empty() {
+                        let board = d.board_id.as_deref().unwrap_or(app.current_project.as_str());
+                        let mut spans = vec![Span::raw("Labels:")];
+                        for (i, id) in d.label_ids.iter().enumerate() {
+                            spans.push(Span::raw(" "));
+                            match app.label(board, id) {
+                                Some(l) => spans.push(label_chip(l)),
+                                None => spans.push(Span::raw(d.labels.get(i).cloned().unwrap_or_default())),
+                            }
+                        }
+                        meta_lines.push(Line::from(spans));
+                    }
+                    if !d.member_ids.is_empty() {
+                        let mut spans = vec![Span::raw("Members:")];
+                        for id in &d.member_ids {
+                            spans.push(Span::raw(" "));
+                            match app.member(id) {
+                                Some(u) => {
+                                    spans.push(member_chip(u));
+                                    spans.push(Span::raw(format!(" {}", u.name)));
+                                }
+                                None => spans.push(Span::raw(id.clone())),
+                            }
+                        }
+                        meta_lines.push(Line::from(spans));
+                    }
                     let meta = Paragraph::new(meta_lines)
                         .block(Block::default().borders(Borders::ALL).title("| Meta |"))
                         .wrap(Wrap { trim: true });
                     f.render_widget(meta, left_rows[0]);
+                    // Description (Markdown source as stored on Planka; E edits it)
+                    let desc_lines: Vec<Line> = match d.description.as_deref() {
+                        Some(text) if !text.trim().is_empty() => text.lines().map(|l| Line::from(l.to_string())).collect(),
+                        _ => vec![Line::from(Span::styled("No description (E to write one)", Style::default().fg(Color::DarkGray)))],
+                    };
+                    let desc = Paragraph::new(desc_lines)
+                        .block(Block::default().borders(Borders::ALL).title("| Description |"))
+                        .wrap(Wrap { trim: false })
+                        .scroll((app.view_scroll, 0));
+                    f.render_widget(desc, left_rows[1]);
                     // Custom Fields block (reuse existing field_lines build)
                     let mut field_lines: Vec<Line> = Vec::new();
                     if d.custom_field_groups.is_empty() {
@@ -770,7 +1076,7 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
                     let fields = Paragraph::new(field_lines)
                         .block(Block::default().borders(Borders::ALL).title("| Custom Fields |"))
                         .wrap(Wrap { trim: true });
-                    f.render_widget(fields, left_rows[1]);
+                    f.render_widget(fields, left_rows[2]);
                     // Right: Attachments + Checklist
                     let right_rows = Layout::default()
                         .direction(Direction::Vertical)
@@ -865,14 +1171,27 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
                         .scroll((app.view_scroll, 0));
                     f.render_widget(comments, rows[2]);
                 }
-                // Commands tab: show all key bindings here
+                // Activity: the card's history, older pages load while scrolling down
                 3 => {
+                    let mut lines: Vec<Line> = app.card_actions.iter().map(|a| activity_line(app, a, false)).collect();
+                    if app.card_actions_loading.is_some() {
+                        lines.push(Line::from(Span::styled("Loading…", Style::default().fg(Color::DarkGray))));
+                    } else if lines.is_empty() {
+                        lines.push(Line::from("No activity"));
+                    }
+                    let activity = Paragraph::new(lines)
+                        .block(Block::default().borders(Borders::ALL).title("| Activity |"))
+                        .scroll((app.view_scroll, 0));
+                    f.render_widget(activity, rows[2]);
+                }
+                // Commands tab: show all key bindings here
+                4 => {
                     let help_lines = vec![
                         Line::from(vec![
                             Span::raw("[Esc] Close  "),
                             Span::raw("[↑/↓/PgUp/PgDn] Scroll  "),
-                            Span::raw("[c] Comment  [r] Reply  [e] Edit  [x] Del cmnt  "),
-                            Span::raw("[t] Add CB  [o] Toggle  [k] Del CB  "),
+                            Span::raw("[E] Edit description  [c] Comment  [C] Comment in $EDITOR  [r] Reply  [e] Edit  [x] Del cmnt  "),
+                            Span::raw("[t] Add CB  [o] Toggle  [k] Del CB  [l] Labels  [m] Members  "),
                         ]),
                         Line::from(vec![
                             Span::raw("[a] Link Attach  [F] File Attach  [R] Ren Attach  [z] Del Attach  "),
@@ -897,6 +1216,66 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
                 .block(Block::default().borders(Borders::ALL).title(" planka "));
             f.render_widget(empty, rows[1]);
         }
+        match app.input_mode {
+            InputMode::PickingMember => draw_member_picker(f, app),
+            InputMode::ViewingCard => {}
+            _ => draw_label_picker(f, app),
+        }
+        return;
+    }
+
+    if matches!(app.input_mode, InputMode::ViewingPendingOps) {
+        draw_pending_ops(f, app);
+        return;
+    }
+
+    if matches!(app.input_mode, InputMode::ViewingMyCards) {
+        draw_my_cards(f, app);
+        return;
+    }
+
+    if matches!(app.input_mode, InputMode::ViewingNotifications) {
+        draw_notifications(f, app);
+        return;
+    }
+
+    if matches!(app.input_mode, InputMode::AcceptingTerms) {
+        draw_terms(f, app);
+        return;
+    }
+
+    if matches!(app.input_mode, InputMode::ManagingUsers | InputMode::CreatingUser) {
+        draw_users(f, app);
+        return;
+    }
+
+    if matches!(app.input_mode, InputMode::EditingAccount | InputMode::ChangingAccount) {
+        draw_account(f, app);
+        return;
+    }
+
+    if matches!(app.input_mode, InputMode::ManagingBoardMembers | InputMode::InvitingBoardMember) {
+        draw_board_members(f, app);
+        return;
+    }
+
+    if matches!(app.input_mode, InputMode::ViewingBoardActivity) {
+        draw_board_activity(f, app);
+        return;
+    }
+
+    if matches!(app.input_mode, InputMode::SwitchingProfile | InputMode::CreatingProfile) {
+        draw_profiles(f, app);
+        return;
+    }
+
+    if matches!(app.input_mode, InputMode::EditingWorkflow) {
+        draw_workflow(f, app);
+        return;
+    }
+
+    if matches!(app.input_mode, InputMode::ResolvingConflicts | InputMode::EditingConflictValue) {
+        draw_conflicts(f, app);
         return;
     }
 
@@ -918,7 +1297,8 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
         f.render_widget(tabs, rows[0]);
 
         // Tools list
-        let items = ["New board", "New project", "Login/setup", "Sync all projects", "Back to tasks"];
+        let webhook = if app.webhook_enabled() { "Webhook receiver: on" } else { "Webhook receiver: off" };
+        let items = ["New board", "New project", "Login/setup", "Sync all projects", "Pending operations", "Switch profile", "Workflow", webhook, "Users", "My account", "Board members", "Back to tasks"];
         let list_items: Vec<ListItem> = items.iter().enumerate().map(|(i, label)| {
             let style = if i == app.control_center_index {
                 Style::default().fg(Color::White).bg(Color::Blue).add_modifier(Modifier::BOLD)
@@ -965,7 +1345,11 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
             | InputMode::Searching
     );
     if needs_input {
-        constraints.push(Constraint::Length(3)); // one input line only
+        if app.sso_url().is_some() {
+            constraints.push(Constraint::Length(7)); // input plus the sign-in URL
+        } else {
+            constraints.push(Constraint::Length(3)); // one input line only
+        }
     }
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -975,19 +1359,24 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
 
     let tasks_style = Style::default().fg(Color::White).bg(Color::Blue).add_modifier(Modifier::BOLD);
     let tools_style = Style::default();
-    let tabs_line = Line::from(vec![
+    let mut tabs = vec![
         Span::styled(" Tasks ", tasks_style),
         Span::raw(" "),
         Span::styled(" Tools ", tools_style),
-    ]);
+    ];
+    let active_profile = crate::profile::active();
+    if active_profile != crate::profile::DEFAULT {
+        tabs.push(Span::styled(format!("   profile: {}", active_profile), Style::default().fg(Color::Magenta)));
+    }
+    let tabs_line = Line::from(tabs);
     let tabs = Paragraph::new(tabs_line).alignment(Alignment::Left);
     f.render_widget(tabs, chunks[0]);
 
-    let board_name = &app.current_project;
+    let board_name = app.project_name(&app.current_project);
     let project_name = app
         .planka_boards
         .iter()
-        .find(|b| b.name == *board_name)
+        .find(|b| b.id == app.current_project)
         .and_then(|b| b.project_name.as_deref())
         .map(|s| s.to_string());
 
@@ -999,6 +1388,15 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
     if app.pending_ops_len() > 0 {
         title_text = format!("{}🏴‍☠️ ⇅{}", title_text, app.pending_ops_len());
     }
+    if !app.conflicts.is_empty() {
+        title_text = format!("{} ⚠{} (C)", title_text, app.conflicts.len());
+    }
+    if app.unread_notifications() > 0 {
+        title_text = format!("{} 🔔{} (n)", title_text, app.unread_notifications());
+    }
+    if app.is_busy() {
+        title_text = format!("{} {}", title_text, spinner_frame());
+    }
     let title = Paragraph::new(Line::from(Span::styled(
         title_text,
         Style::default().add_modifier(Modifier::BOLD),
@@ -1007,18 +1405,31 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
     f.render_widget(title, chunks[1]);
 
     let b = Style::default().add_modifier(Modifier::BOLD);
-    let help = Paragraph::new(vec![
+    let first_help_line = if app.board_view {
+        Line::from(vec![
+            Span::styled("←/→ ↑/↓", b), Span::raw(" select, "),
+            Span::styled("Shift+←/→", b), Span::raw(" move card, "),
+            Span::styled("Shift+↑/↓", b), Span::raw(" reorder, "),
+            Span::styled("Enter", b), Span::raw(" open, "),
+            Span::styled("r", b), Span::raw(" reload, "),
+            Span::styled("v", b), Span::raw(" task list"),
+        ])
+    } else {
         Line::from(vec![
             Span::raw("Press "),
             Span::styled("a", b), Span::raw(" add, "),
             Span::styled("e", b), Span::raw(" edit, "),
             Span::styled("Enter", b), Span::raw(" open, "),
             Span::styled("d", b), Span::raw(" done, "),
-            Span::styled("w", b), Span::raw(" doing, "),
+            Span::styled("w", b), Span::raw(" advance, "),
+            Span::styled("b", b), Span::raw(" back, "),
             Span::raw("Shift+"), Span::styled("R", b), Span::raw(" delete, "),
             Span::styled("c", b), Span::raw(" copy, "),
             Span::styled("p", b), Span::raw(" paste"),
-        ]),
+        ])
+    };
+    let help = Paragraph::new(vec![
+        first_help_line,
         Line::from(vec![
             Span::styled("?", b), Span::raw(" search, "),
             Span::styled("[", b), Span::raw(" prev, "),
@@ -1026,6 +1437,11 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
             Span::styled("l", b), Span::raw(" set project, "),
             Span::styled("S", b), Span::raw(" sync, "),
             Span::styled("L", b), Span::raw(" login, "),
+            Span::styled("v", b), Span::raw(" board, "),
+            Span::styled("f", b), Span::raw(" label filter, "),
+            Span::styled("m", b), Span::raw(" my cards, "),
+            Span::styled("n", b), Span::raw(" inbox, "),
+            Span::styled("A", b), Span::raw(" activity, "),
             Span::styled("Tab", b), Span::raw(" tools, "),
             Span::styled("q", b), Span::raw(" quit"),
         ]),
@@ -1040,12 +1456,8 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
         .iter()
         .map(|t| {
             let due_opt = t.due_date.as_ref();
-            let is_doing = !t.done
-                && app
-                    .planka_lists_by_board
-                    .get(&app.current_project)
-                    .map(|lists| t.planka_list_id.as_deref() == Some(lists.doing_list_id.as_str()))
-                    .unwrap_or(false);
+            let (stage, stages) = app.stage_of(t);
+            let is_doing = !t.done && stage > 0 && stage + 1 < stages;
 
             let status = if t.done {
                 "[d]"
@@ -1064,21 +1476,47 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
             } else {
                 Color::Yellow
             };
+            // Spinner while the row's change is being sent, ⇅ while it waits for a retry
+            let pending = match app.row_pending(&t.local_id) {
+                Some(true) => format!(" {}", spinner_frame()),
+                Some(false) => " ⇅".to_string(),
+                None => String::new(),
+            };
             // Build a single visible string, then soft-wrap to list width
-            let mut text = format!("{} {}", status, t.description);
+            let mut text = format!("{}{} {}", status, pending, t.description);
+            // With several in-progress stages, say which one
+            if is_doing && stages > 3 {
+                text = format!("{}{} {} › {}", status, pending, app.stage_name(t), t.description);
+            }
             if let Some(due) = due_opt {
                 text.push_str(&format!(" (Due: {})", due));
             }
             text.push_str(&format!(" [Created: {}]", t.created_date));
 
             let wrapped = wrap(&text, inner_width);
-            let lines: Vec<Line> = wrapped
+            let mut lines: Vec<Line> = wrapped
                 .iter()
                 .map(|w| Line::from(Span::styled(
                     w.to_string(),
                     Style::default().fg(desc_color),
                 )))
                 .collect();
+            // Label chips and member initials after the text, on a line of their own when they don't fit
+            let mut chips: Vec<Span> = t.labels.iter().filter_map(|id| app.label(&t.project, id)).map(label_chip).collect();
+            chips.extend(t.members.iter().filter_map(|id| app.member(id)).map(member_chip));
+            if !chips.is_empty() {
+                let chips_width: usize = chips.iter().map(|c| c.width() + 1).sum();
+                let last_width = lines.last().map(|l| l.width()).unwrap_or(0);
+                if lines.is_empty() || last_width + chips_width > inner_width.saturating_sub(3) {
+                    lines.push(Line::from(""));
+                }
+                if let Some(last) = lines.last_mut() {
+                    for chip in chips {
+                        last.spans.push(Span::raw(" "));
+                        last.spans.push(chip);
+                    }
+                }
+            }
             ListItem::new(lines)
         })
         .collect();
@@ -1088,8 +1526,12 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
         list_state.select(Some(app.selected.min(todos.len() - 1)));
     }
 
+    let todos_title = match app.label_filter.as_deref().and_then(|id| app.label(&app.current_project, id)) {
+        Some(l) => Line::from(vec![Span::raw("Todos · label "), label_chip(l), Span::raw(" (f to change)")]),
+        None => Line::from("Todos"),
+    };
     let todos_list = List::new(todos)
-        .block(Block::default().borders(Borders::ALL).title("Todos"))
+        .block(Block::default().borders(Borders::ALL).title(todos_title))
         .highlight_style(
             Style::default()
                 .bg(Color::Blue)
@@ -1098,7 +1540,11 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
         )
         .highlight_symbol(">> ");
 
-    f.render_stateful_widget(todos_list, chunks[3], &mut list_state);
+    if app.board_view {
+        draw_kanban(f, app, chunks[3]);
+    } else {
+        f.render_stateful_widget(todos_list, chunks[3], &mut list_state);
+    }
 
     // Optional single-line input at bottom (only when editing)
     if needs_input {
@@ -1106,11 +1552,16 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
         let caret = "|";
         if matches!(app.input_mode, InputMode::EditingPlanka) {
             let style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
-            let text = if app.input_planka.is_empty() { caret.to_string() } else { format!("{}{}", app.input_planka, caret) };
+            let mut text = if app.input_planka.is_empty() { caret.to_string() } else { format!("{}{}", app.input_planka, caret) };
+            if let Some(url) = app.sso_url() {
+                text = format!("{}\n\nSign-in page: {}", text, url);
+            }
             let title = match app.planka_setup {
                 Some(crate::app::PlankaSetupStep::Url) => "Planka URL",
+                Some(crate::app::PlankaSetupStep::Username) if app.oidc.is_some() => "Planka Username or Email (leave empty to sign in with SSO)",
                 Some(crate::app::PlankaSetupStep::Username) => "Planka Username or Email",
                 Some(crate::app::PlankaSetupStep::Password) => "Planka Password",
+                Some(crate::app::PlankaSetupStep::Sso) => "SSO: paste the URL the browser ends on after signing in (Enter alone reopens the page)",
                 _ => "Planka Setup",
             };
             let widget = Paragraph::new(text)
@@ -1171,7 +1622,7 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
             let style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
             let text = if app.input_comment.is_empty() { caret.to_string() } else { format!("{}{}", app.input_comment, caret) };
             let widget = Paragraph::new(text)
-                .block(Block::default().borders(Borders::ALL).title("Comment"))
+                .block(Block::default().borders(Borders::ALL).title("Comment (Ctrl+E: $EDITOR)"))
                 .style(style)
                 .wrap(Wrap { trim: true });
             f.render_widget(widget, chunks[last]);
@@ -1179,7 +1630,7 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
             let style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
             let text = if app.input_comment.is_empty() { caret.to_string() } else { format!("{}{}", app.input_comment, caret) };
             let widget = Paragraph::new(text)
-                .block(Block::default().borders(Borders::ALL).title("Edit Comment"))
+                .block(Block::default().borders(Borders::ALL).title("Edit Comment (Ctrl+E: $EDITOR)"))
                 .style(style)
                 .wrap(Wrap { trim: true });
             f.render_widget(widget, chunks[last]);
@@ -1284,46 +1735,1118 @@ fn ui(f: &mut ratatui::Frame<'_>, app: &App) {
         };
         f.render_widget(error, area);
     }
-}
 
-// Check if a due date string represents an overdue task
-fn is_overdue(due_date_str: &str) -> bool {
-    if due_date_str == "No due date" {
-        return false;
+    if matches!(app.input_mode, InputMode::FilteringLabel) {
+        draw_label_picker(f, app);
     }
+}
 
-    let now = Local::now();
+fn draw_pending_ops(f: &mut ratatui::Frame<'_>, app: &App) {
+    let size = f.area();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(5)])
+        .split(size);
 
-    // Try to parse as datetime first
-    if let Ok(dt) = NaiveDateTime::parse_from_str(due_date_str, "%Y-%m-%d %H:%M") {
-        if let Some(dt_local) = Local.from_local_datetime(&dt).single() {
-            return dt_local < now;
-        }
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓", b), Span::raw(" select, "),
+        Span::styled("r", b), Span::raw(" retry now, "),
+        Span::styled("x", b), Span::raw(" discard, "),
+        Span::styled("Esc", b), Span::raw(" back"),
+    ]))
+    .alignment(Alignment::Left);
+    f.render_widget(help, rows[0]);
+
+    let now = Local::now().timestamp();
+    let describe = |op: &crate::app::PendingOp| {
+        let kind = op.kind.label();
+        let target = op
+            .name
+            .clone()
+            .or_else(|| {
+                op.card_id.as_ref().and_then(|cid| {
+                    app.todos
+                        .iter()
+                        .find(|t| t.planka_card_id.as_ref() == Some(cid))
+                        .map(|t| t.description.clone())
+                })
+            })
+            .or_else(|| op.card_id.clone())
+            .unwrap_or_default();
+        format!("{:<6} {} › {}", kind, app.project_name(&op.project), target)
+    };
+    let mut items: Vec<ListItem> = Vec::new();
+    for op in &app.pending_ops {
+        let wait = op.retry.next_at - now;
+        let status = if wait > 0 {
+            format!("  [try {} · retry in {}s]", op.retry.attempts, wait)
+        } else {
+            format!("  [try {} · due]", op.retry.attempts)
+        };
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(describe(op), Style::default().fg(Color::Yellow)),
+            Span::raw(status),
+        ])));
     }
-
-    // Try to parse as date only
-    if let Ok(date) = NaiveDate::parse_from_str(due_date_str, "%Y-%m-%d") {
-        let today = now.date_naive();
-        return date < today;
+    for op in &app.dead_ops {
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(describe(op), Style::default().fg(Color::Red)),
+            Span::raw(format!("  [dead after {} tries]", op.retry.attempts)),
+        ])));
     }
-
-    false
-}
-
-pub fn parse_due_date(input: &str) -> Result<String, String> {
-    let input = input.trim().to_lowercase();
-    let now = Local::now();
-    let today = now.date_naive();
-
-    // Handle empty input
-    if input.is_empty() {
-        return Err("Please enter a due date".to_string());
+    let mut state = ratatui::widgets::ListState::default();
+    if !items.is_empty() {
+        state.select(Some(app.ops_index.min(items.len() - 1)));
     }
+    let title = format!("Pending operations ({} queued, {} failed)", app.pending_ops.len(), app.dead_ops.len());
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().bg(Color::Blue).fg(Color::White).add_modifier(Modifier::BOLD))
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(list, rows[1], &mut state);
 
-    let words: Vec<&str> = input.split_whitespace().collect();
+    let selected = app
+        .pending_ops
+        .iter()
+        .chain(app.dead_ops.iter())
+        .nth(app.ops_index);
+    let detail = match selected {
+        Some(op) => vec![
+            Line::from(vec![Span::styled("Id: ", b), Span::raw(op.id.clone())]),
+            Line::from(vec![
+                Span::styled("Queued: ", b),
+                Span::raw(
+                    chrono::DateTime::from_timestamp(op.ts, 0)
+                        .map(|d| d.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                        .unwrap_or_default(),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("Last error: ", b),
+                Span::raw(op.retry.last_error.clone().unwrap_or_else(|| "-".to_string())),
+            ]),
+        ],
+        None => vec![Line::from("Queue is empty")],
+    };
+    let detail = Paragraph::new(detail)
+        .block(Block::default().borders(Borders::ALL).title("Details"))
+        .wrap(Wrap { trim: true });
+    f.render_widget(detail, rows[2]);
 
-    let raw_date = match words.as_slice() {
-        // Immediate times
+    if let Some(ref msg) = app.error_message {
+        let error = Paragraph::new(msg.as_str())
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        let area = ratatui::layout::Rect {
+            x: size.x,
+            y: size.height.saturating_sub(1),
+            width: size.width,
+            height: 1,
+        };
+        f.render_widget(error, area);
+    }
+}
+
+fn draw_profiles(f: &mut ratatui::Frame<'_>, app: &App) {
+    let size = f.area();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(3)])
+        .split(size);
+
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓", b), Span::raw(" select, "),
+        Span::styled("Enter", b), Span::raw(" switch, "),
+        Span::styled("n", b), Span::raw(" new profile, "),
+        Span::styled("Esc", b), Span::raw(" back"),
+    ]))
+    .alignment(Alignment::Left);
+    f.render_widget(help, rows[0]);
+
+    let active = crate::profile::active();
+    let items: Vec<ListItem> = app
+        .profiles
+        .iter()
+        .map(|(name, summary)| {
+            let (mark, style) = if *name == active {
+                ("● ", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+            } else {
+                ("  ", Style::default())
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}{}", mark, name), style),
+                Span::styled(format!("  {}", summary), Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+    let mut state = ratatui::widgets::ListState::default();
+    if !items.is_empty() {
+        state.select(Some(app.profile_index.min(items.len() - 1)));
+    }
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Profiles"))
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(list, rows[1], &mut state);
+
+    if matches!(app.input_mode, InputMode::CreatingProfile) {
+        let input = Paragraph::new(format!("{}|", app.input_profile))
+            .block(Block::default().borders(Borders::ALL).title("New profile name"));
+        f.render_widget(input, rows[2]);
+    } else if let Some(ref msg) = app.error_message {
+        let error = Paragraph::new(msg.as_str())
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(error, rows[2]);
+    }
+}
+
+// The board's lists side by side; as many columns as fit, scrolled to keep the cursor visible
+fn draw_kanban(f: &mut ratatui::Frame<'_>, app: &App, area: Rect) {
+    if app.kanban.is_empty() {
+        let msg = if app.kanban_loading { "Loading board…" } else { "No board to show: the current project is not a Planka board (v for the task list)" };
+        let p = Paragraph::new(msg)
+            .block(Block::default().borders(Borders::ALL).title("Board"))
+            .alignment(Alignment::Center);
+        f.render_widget(p, area);
+        return;
+    }
+    const MIN_COLUMN_WIDTH: u16 = 24;
+    let fit = ((area.width / MIN_COLUMN_WIDTH) as usize).max(1);
+    let shown = fit.min(app.kanban.len());
+    let first = app.kanban_col.saturating_sub(shown - 1).min(app.kanban.len() - shown);
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, shown as u32); shown])
+        .split(area);
+    for (slot, ci) in (first..first + shown).enumerate() {
+        let list = &app.kanban[ci];
+        let active = ci == app.kanban_col;
+        let width = cols[slot].width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = list
+            .cards
+            .iter()
+            .map(|c| {
+                let mut text = c.name.clone();
+                if let Some(due) = c.due.as_deref().and_then(crate::app::format_planka_due) {
+                    text.push_str(&format!(" (Due: {})", due));
+                }
+                let color = match c.due.as_deref().and_then(crate::app::format_planka_due) {
+                    Some(ref d) if is_overdue(d) => Color::Red,
+                    _ => Color::Yellow,
+                };
+                let lines: Vec<Line> = wrap(&text, width.max(1))
+                    .iter()
+                    .map(|w| Line::from(Span::styled(w.to_string(), Style::default().fg(color))))
+                    .collect();
+                ListItem::new(lines)
+            })
+            .collect();
+        let mut state = ratatui::widgets::ListState::default();
+        if active && !items.is_empty() {
+            state.select(Some(app.kanban_row.min(items.len() - 1)));
+        }
+        let border = if active { Style::default().fg(Color::Cyan) } else { Style::default() };
+        let widget = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border)
+                    .title(format!("{} ({})", list.name, list.cards.len())),
+            )
+            .highlight_style(Style::default().bg(Color::Blue).fg(Color::White).add_modifier(Modifier::BOLD));
+        f.render_stateful_widget(widget, cols[slot], &mut state);
+    }
+}
+
+fn draw_workflow(f: &mut ratatui::Frame<'_>, app: &App) {
+    let size = f.area();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(3)])
+        .split(size);
+
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("Space", b), Span::raw(" use list, "),
+        Span::styled("Shift+↑/↓", b), Span::raw(" reorder, "),
+        Span::styled("Enter", b), Span::raw(" save, "),
+        Span::styled("g", b), Span::raw(" guess from names, "),
+        Span::styled("Esc", b), Span::raw(" back"),
+    ]))
+    .alignment(Alignment::Left);
+    f.render_widget(help, rows[0]);
+
+    // Stages are numbered in workflow order; the last one counts as done
+    let used = app.workflow_rows.iter().filter(|(_, on)| *on).count();
+    let mut n = 0;
+    let items: Vec<ListItem> = app
+        .workflow_rows
+        .iter()
+        .map(|(stage, on)| {
+            if !*on {
+                return ListItem::new(Line::from(Span::styled(
+                    format!("[ ]    {}", stage.name),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            n += 1;
+            let role = match n {
+                1 => "  new",
+                _ if n == used => "  done",
+                _ => "",
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("[x] {:>2}. {}", n, stage.name)),
+                Span::styled(role, Style::default().fg(Color::Green)),
+            ]))
+        })
+        .collect();
+    let mut state = ratatui::widgets::ListState::default();
+    if !items.is_empty() {
+        state.select(Some(app.workflow_index.min(items.len() - 1)));
+    }
+    let title = format!("Workflow of {}", app.project_name(&app.current_project));
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(list, rows[1], &mut state);
+
+    if let Some(ref msg) = app.error_message {
+        let error = Paragraph::new(msg.as_str())
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(error, rows[2]);
+    }
+}
+
+fn draw_my_cards(f: &mut ratatui::Frame<'_>, app: &App) {
+    let size = f.area();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(3)])
+        .split(size);
+
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓", b), Span::raw(" select, "),
+        Span::styled("Enter", b), Span::raw(" open, "),
+        Span::styled("r", b), Span::raw(" reload, "),
+        Span::styled("Esc", b), Span::raw(" back"),
+    ]))
+    .alignment(Alignment::Left);
+    f.render_widget(help, rows[0]);
+
+    let items: Vec<ListItem> = app
+        .my_cards
+        .iter()
+        .map(|(board, list, card)| {
+            let mut spans = vec![
+                Span::styled(format!("{} › {}", app.project_name(board), list), Style::default().fg(Color::DarkGray)),
+                Span::raw("  "),
+                Span::styled(card.name.clone(), Style::default().fg(Color::Yellow)),
+            ];
+            if let Some(due) = card.due.as_deref().and_then(crate::app::format_planka_due) {
+                spans.push(Span::raw(format!(" (Due: {})", due)));
+            }
+            for l in card.label_ids.iter().filter_map(|id| app.label(board, id)) {
+                spans.push(Span::raw(" "));
+                spans.push(label_chip(l));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let title = match (app.my_cards_loading, app.my_user_id.as_deref().and_then(|id| app.member(id))) {
+        (true, _) => format!("My cards {}", spinner_frame()),
+        (false, Some(me)) => format!("My cards · {} ({})", me.name, app.my_cards.len()),
+        (false, None) => format!("My cards ({})", app.my_cards.len()),
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if items.is_empty() {
+        let msg = if app.my_cards_loading { "Loading…" } else { "No cards are assigned to you" };
+        f.render_widget(Paragraph::new(msg).style(Style::default().fg(Color::DarkGray)).block(block), rows[1]);
+    } else {
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(app.my_cards_index.min(items.len() - 1)));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        f.render_stateful_widget(list, rows[1], &mut state);
+    }
+
+    if let Some(ref msg) = app.error_message {
+        let error = Paragraph::new(msg.as_str())
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(error, rows[2]);
+    }
+}
+
+// Planka timestamp as local "MM-DD HH:MM"
+fn short_time(created: Option<&str>) -> String {
+    created
+        .and_then(|c| chrono::DateTime::parse_from_rfc3339(c).ok())
+        .map(|dt| dt.with_timezone(&Local).format("%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+// "10-17 08:00  Alice moved “X” from Todo to Done"
+fn activity_line(app: &App, a: &planka::PlankaAction, with_card: bool) -> Line<'static> {
+    let when = short_time(a.created.as_deref());
+    let who = a.user_id.as_deref().and_then(|id| app.user_name(id)).unwrap_or("Someone").to_string();
+    Line::from(vec![
+        Span::styled(format!("{:<11} ", when), Style::default().fg(Color::DarkGray)),
+        Span::styled(who, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(" {}", a.describe(with_card))),
+    ])
+}
+
+fn draw_board_activity(f: &mut ratatui::Frame<'_>, app: &App) {
+    let size = f.area();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(3)])
+        .split(size);
+
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓/PgUp/PgDn", b), Span::raw(" scroll, "),
+        Span::styled("Enter", b), Span::raw(" open card, "),
+        Span::styled("r", b), Span::raw(" reload, "),
+        Span::styled("Esc", b), Span::raw(" back"),
+    ]))
+    .alignment(Alignment::Left);
+    f.render_widget(help, rows[0]);
+
+    let mut items: Vec<ListItem> = app.board_actions.iter().map(|a| ListItem::new(activity_line(app, a, true))).collect();
+    if app.board_actions_loading.is_some() {
+        items.push(ListItem::new(Span::styled("Loading…", Style::default().fg(Color::DarkGray))));
+    } else if app.board_actions_end && !items.is_empty() {
+        items.push(ListItem::new(Span::styled("— start of the board's history —", Style::default().fg(Color::DarkGray))));
+    }
+    let title = format!("Activity of {}", app.project_name(&app.current_project));
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if items.is_empty() {
+        f.render_widget(Paragraph::new("No activity").style(Style::default().fg(Color::DarkGray)).block(block), rows[1]);
+    } else {
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(app.board_action_index.min(items.len() - 1)));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        f.render_stateful_widget(list, rows[1], &mut state);
+    }
+
+    if let Some(ref msg) = app.error_message {
+        let error = Paragraph::new(msg.as_str())
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(error, rows[2]);
+    }
+}
+
+fn draw_notifications(f: &mut ratatui::Frame<'_>, app: &App) {
+    let size = f.area();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(3)])
+        .split(size);
+
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓", b), Span::raw(" select, "),
+        Span::styled("Enter", b), Span::raw(" open card, "),
+        Span::styled("Space", b), Span::raw(" read/unread, "),
+        Span::styled("a", b), Span::raw(" mark all read, "),
+        Span::styled("r", b), Span::raw(" reload, "),
+        Span::styled("Esc", b), Span::raw(" back"),
+    ]))
+    .alignment(Alignment::Left);
+    f.render_widget(help, rows[0]);
+
+    // Unread in bold yellow, read ones dimmed until the next reload drops them
+    let items: Vec<ListItem> = app
+        .notifications
+        .iter()
+        .map(|n| {
+            let when = short_time(n.created.as_deref());
+            let style = if n.is_read {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(if n.is_read { "  " } else { "● " }),
+                Span::styled(format!("{:<11} ", when), Style::default().fg(Color::DarkGray)),
+                Span::styled(n.summary(), style),
+            ]))
+        })
+        .collect();
+    let title = format!("Notifications ({} unread)", app.unread_notifications());
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if items.is_empty() {
+        let msg = if app.is_busy() { "Loading…" } else { "No notifications" };
+        f.render_widget(Paragraph::new(msg).style(Style::default().fg(Color::DarkGray)).block(block), rows[1]);
+    } else {
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(app.notification_index.min(items.len() - 1)));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        f.render_stateful_widget(list, rows[1], &mut state);
+    }
+
+    if let Some(ref msg) = app.error_message {
+        let error = Paragraph::new(msg.as_str())
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(error, rows[2]);
+    }
+}
+
+// Title of a form's input box, with the complaint about the last answer if there is one
+fn form_title(title: String, app: &App) -> Line<'static> {
+    let mut spans = vec![Span::raw(title)];
+    if let Some(ref e) = app.error_message {
+        spans.push(Span::styled(format!(" {} ", e), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)));
+    }
+    Line::from(spans)
+}
+
+fn draw_users(f: &mut ratatui::Frame<'_>, app: &App) {
+    let size = f.area();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(3)])
+        .split(size);
+
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓", b), Span::raw(" select, "),
+        Span::styled("n", b), Span::raw(" new user, "),
+        Span::styled("R", b), Span::raw(" change role, "),
+        Span::styled("d", b), Span::raw(" (de)activate, "),
+        Span::styled("x", b), Span::raw(" delete, "),
+        Span::styled("r", b), Span::raw(" reload, "),
+        Span::styled("Esc", b), Span::raw(" back"),
+    ]))
+    .alignment(Alignment::Left);
+    f.render_widget(help, rows[0]);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let items: Vec<ListItem> = app
+        .users
+        .iter()
+        .map(|u| {
+            let me = app.my_user_id.as_deref() == Some(u.id.as_str());
+            let name = if me { format!("{} (you)", u.name) } else { u.name.clone() };
+            let style = if u.is_deactivated { dim } else { Style::default() };
+            let mut spans = vec![
+                Span::styled(format!("{:<28} ", name), style.add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{:<18} ", u.username.as_deref().unwrap_or("-")), style),
+                Span::styled(format!("{:<32} ", u.email.as_deref().unwrap_or("-")), style),
+                Span::styled(planka::user_role_name(&u.role).to_string(), style.fg(Color::Cyan)),
+            ];
+            if u.is_deactivated {
+                spans.push(Span::styled("  deactivated", Style::default().fg(Color::Red)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let block = Block::default().borders(Borders::ALL).title(format!("Users ({})", app.users.len()));
+    if items.is_empty() {
+        let msg = if app.is_busy() { "Loading…" } else { "No users" };
+        f.render_widget(Paragraph::new(msg).style(dim).block(block), rows[1]);
+    } else {
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(app.user_index.min(items.len() - 1)));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        f.render_stateful_widget(list, rows[1], &mut state);
+    }
+
+    if matches!(app.input_mode, InputMode::CreatingUser) {
+        let field = app.user_form_field();
+        let shown = if field == "Password" { "*".repeat(app.input_user.chars().count()) } else { app.input_user.clone() };
+        let title = form_title(format!("New user: {} ({}/4, Esc cancels)", field, app.user_form.len() + 1), app);
+        let input = Paragraph::new(format!("{}|", shown)).block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(input, rows[2]);
+    } else if let Some(question) = app.user_confirm_prompt() {
+        let confirm = Paragraph::new(question)
+            .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(confirm, rows[2]);
+    } else if let Some(ref msg) = app.error_message {
+        let error = Paragraph::new(msg.as_str())
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(error, rows[2]);
+    }
+}
+
+fn draw_board_members(f: &mut ratatui::Frame<'_>, app: &App) {
+    let size = f.area();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(3)])
+        .split(size);
+
+    let inviting = matches!(app.input_mode, InputMode::InvitingBoardMember);
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let help = if inviting {
+        Line::from(vec![
+            Span::styled("↑/↓", b), Span::raw(" select, "),
+            Span::styled("Enter", b), Span::raw(" invite as editor, "),
+            Span::styled("v", b), Span::raw(" invite as viewer, "),
+            Span::styled("Esc", b), Span::raw(" back"),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("↑/↓", b), Span::raw(" select, "),
+            Span::styled("i", b), Span::raw(" invite, "),
+            Span::styled("R", b), Span::raw(" editor/viewer, "),
+            Span::styled("c", b), Span::raw(" comments on/off, "),
+            Span::styled("x", b), Span::raw(" remove, "),
+            Span::styled("r", b), Span::raw(" reload, "),
+            Span::styled("Esc", b), Span::raw(" back"),
+        ])
+    };
+    f.render_widget(Paragraph::new(help).alignment(Alignment::Left), rows[0]);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let board = app.project_name(&app.current_project);
+    let (items, index, title, empty): (Vec<ListItem>, usize, String, &str) = if inviting {
+        let items = app
+            .invitable
+            .iter()
+            .map(|u| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<28} ", u.name), b),
+                    Span::styled(format!("{:<18} ", u.username.as_deref().unwrap_or("-")), dim),
+                    Span::styled(u.email.clone().unwrap_or_default(), dim),
+                ]))
+            })
+            .collect();
+        (items, app.invite_index, format!("Invite to {}", board), "Everyone is on this board already")
+    } else {
+        let items = app
+            .memberships
+            .iter()
+            .map(|(m, u)| {
+                let me = app.my_user_id.as_deref() == Some(u.id.as_str());
+                let name = if me { format!("{} (you)", u.name) } else { u.name.clone() };
+                let role = if m.role == "editor" { "Editor" } else { "Viewer" };
+                let comments = if m.may_comment() {
+                    Span::raw("can comment")
+                } else {
+                    Span::styled("no comments", dim)
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<28} ", name), b),
+                    Span::styled(format!("{:<18} ", u.username.as_deref().unwrap_or("-")), dim),
+                    Span::styled(format!("{:<8} ", role), Style::default().fg(Color::Cyan)),
+                    comments,
+                ]))
+            })
+            .collect();
+        (items, app.membership_index, format!("Members of {}", board), "No members")
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if items.is_empty() {
+        let msg = if app.is_busy() { "Loading…" } else { empty };
+        f.render_widget(Paragraph::new(msg).style(dim).block(block), rows[1]);
+    } else {
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(index.min(items.len() - 1)));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        f.render_stateful_widget(list, rows[1], &mut state);
+    }
+
+    if let Some(question) = app.membership_confirm_prompt() {
+        let confirm = Paragraph::new(question)
+            .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(confirm, rows[2]);
+    } else if let Some(ref msg) = app.error_message {
+        let error = Paragraph::new(msg.as_str())
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(error, rows[2]);
+    }
+}
+
+fn draw_account(f: &mut ratatui::Frame<'_>, app: &App) {
+    let size = f.area();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(3)])
+        .split(size);
+
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓", b), Span::raw(" select, "),
+        Span::styled("Enter", b), Span::raw(" change, "),
+        Span::styled("Esc", b), Span::raw(" back"),
+    ]))
+    .alignment(Alignment::Left);
+    f.render_widget(help, rows[0]);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let block = Block::default().borders(Borders::ALL).title(match app.account {
+        Some(ref me) => format!("My account: {}", me.name),
+        None => "My account".to_string(),
+    });
+    match app.account {
+        Some(ref me) => {
+            let items: Vec<ListItem> = AccountField::ALL
+                .iter()
+                .map(|field| {
+                    let value = match field {
+                        AccountField::Password => Span::raw("••••••••"),
+                        AccountField::Email => Span::raw(me.email.clone().unwrap_or_default()),
+                        AccountField::Username => match me.username {
+                            Some(ref u) => Span::raw(u.clone()),
+                            None => Span::styled("none", dim),
+                        },
+                        AccountField::Avatar => Span::styled("upload an image", dim),
+                    };
+                    ListItem::new(Line::from(vec![Span::styled(format!("{:<10} ", field.label()), b), value]))
+                })
+                .collect();
+            let mut state = ratatui::widgets::ListState::default();
+            state.select(Some(app.account_index));
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                .highlight_symbol(">> ");
+            f.render_stateful_widget(list, rows[1], &mut state);
+        }
+        None => {
+            let msg = if app.is_busy() { "Loading…" } else { "Account not loaded" };
+            f.render_widget(Paragraph::new(msg).style(dim).block(block), rows[1]);
+        }
+    }
+
+    if matches!(app.input_mode, InputMode::ChangingAccount) {
+        let prompt = app.account_prompt();
+        let shown = if is_secret_prompt(prompt) { "*".repeat(app.input_account.chars().count()) } else { app.input_account.clone() };
+        let steps = app.account_field().prompts().len();
+        let title = form_title(format!("{} ({}/{}, Esc cancels)", prompt, app.account_form.len() + 1, steps), app);
+        let input = Paragraph::new(format!("{}|", shown)).block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(input, rows[2]);
+    } else if let Some(ref msg) = app.error_message {
+        let error = Paragraph::new(msg.as_str())
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(error, rows[2]);
+    }
+}
+
+// The server's terms, shown before a login it holds back; Markdown as-is
+fn draw_terms(f: &mut ratatui::Frame<'_>, app: &App) {
+    let size = f.area();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(3)])
+        .split(size);
+
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓/PgUp/PgDn", b), Span::raw(" scroll, "),
+        Span::styled("a", b), Span::raw(" accept and log in, "),
+        Span::styled("d", b), Span::raw("/"),
+        Span::styled("Esc", b), Span::raw(" decline"),
+    ]))
+    .alignment(Alignment::Left);
+    f.render_widget(help, rows[0]);
+
+    let terms = app.terms.as_ref().and_then(|p| p.terms.as_ref());
+    let title = match terms {
+        Some(t) if !t.language.is_empty() => format!("Terms of service ({})", t.language),
+        _ => "Terms of service".to_string(),
+    };
+    let text = terms.map(|t| t.content.as_str()).unwrap_or("Loading…");
+    let scroll = app.terms.as_ref().map(|p| p.scroll).unwrap_or(0);
+    let body = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    f.render_widget(body, rows[1]);
+
+    if let Some(ref msg) = app.error_message {
+        let error = Paragraph::new(msg.as_str())
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(error, rows[2]);
+    }
+}
+
+// Cleared, centered area for a popup with `rows` lines of content
+fn popup_area(f: &mut ratatui::Frame<'_>, rows: usize) -> Rect {
+    let size = f.area();
+    let width = size.width.saturating_sub(4).min(50);
+    let height = size.height.saturating_sub(4).min(rows.max(1) as u16 + 5);
+    let area = Rect {
+        x: size.x + (size.width.saturating_sub(width)) / 2,
+        y: size.y + (size.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+    f.render_widget(Clear, area);
+    area
+}
+
+// Popup over the card: assign board members to it
+fn draw_member_picker(f: &mut ratatui::Frame<'_>, app: &App) {
+    let members = app.picker_members();
+    let area = popup_area(f, members.len());
+    let card = app.view_card.as_ref();
+    let checked = card.map(|c| c.member_ids.clone()).unwrap_or_default();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Members of {}", card.map(|c| c.name.as_str()).unwrap_or("card")));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let items: Vec<ListItem> = members
+        .iter()
+        .map(|u| {
+            let mark = if checked.contains(&u.id) { "[x] " } else { "[ ] " };
+            let mut spans = vec![Span::raw(mark), member_chip(u), Span::raw(format!(" {}", u.name))];
+            if let Some(ref username) = u.username {
+                spans.push(Span::styled(format!(" @{}", username), Style::default().fg(Color::DarkGray)));
+            }
+            if app.my_user_id.as_deref() == Some(u.id.as_str()) {
+                spans.push(Span::styled(" (you)", Style::default().fg(Color::DarkGray)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    if items.is_empty() {
+        let msg = if app.is_busy() { "Loading members…" } else { "This board has no members" };
+        f.render_widget(Paragraph::new(msg).style(Style::default().fg(Color::DarkGray)), rows[0]);
+    } else {
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(app.member_index.min(items.len() - 1)));
+        let list = List::new(items)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        f.render_stateful_widget(list, rows[0], &mut state);
+    }
+
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("Space", b), Span::raw(" assign/unassign, "),
+            Span::styled("Esc", b), Span::raw(" back"),
+        ])),
+        rows[1],
+    );
+}
+
+// Popup over the card (toggle labels) or the task list (filter by label)
+fn draw_label_picker(f: &mut ratatui::Frame<'_>, app: &App) {
+    let labels = app.picker_labels();
+    let area = popup_area(f, labels.len());
+
+    let filtering = app.input_mode == InputMode::FilteringLabel;
+    let (title, checked): (String, Vec<String>) = if filtering {
+        ("Filter by label".to_string(), app.label_filter.iter().cloned().collect())
+    } else {
+        let card = app.view_card.as_ref();
+        (
+            format!("Labels of {}", card.map(|c| c.name.as_str()).unwrap_or("card")),
+            card.map(|c| c.label_ids.clone()).unwrap_or_default(),
+        )
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(2)])
+        .split(inner);
+
+    let items: Vec<ListItem> = labels
+        .iter()
+        .map(|l| {
+            let mark = if checked.contains(&l.id) { "[x] " } else { "[ ] " };
+            ListItem::new(Line::from(vec![Span::raw(mark), label_chip(l)]))
+        })
+        .collect();
+    if items.is_empty() {
+        let msg = if app.is_busy() { "Loading labels…" } else { "This board has no labels" };
+        f.render_widget(Paragraph::new(msg).style(Style::default().fg(Color::DarkGray)), rows[0]);
+    } else {
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(app.label_index.min(items.len() - 1)));
+        let list = List::new(items)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        f.render_stateful_widget(list, rows[0], &mut state);
+    }
+
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let footer = if app.input_mode == InputMode::CreatingLabel {
+        let color = planka::LABEL_COLORS[app.input_label_color];
+        let preview = PlankaLabel { id: String::new(), name: app.input_label.clone(), color: color.to_string(), position: 0.0 };
+        vec![
+            Line::from(vec![Span::raw("New label: "), label_chip(&preview), Span::raw("|")]),
+            Line::from(vec![Span::styled("Tab", b), Span::raw(format!(" color ({}), ", color)), Span::styled("Enter", b), Span::raw(" create")]),
+        ]
+    } else if filtering {
+        vec![Line::from(vec![
+            Span::styled("Enter", b), Span::raw(" filter, "),
+            Span::styled("x", b), Span::raw(" show all, "),
+            Span::styled("Esc", b), Span::raw(" back"),
+        ])]
+    } else {
+        vec![Line::from(vec![
+            Span::styled("Space", b), Span::raw(" toggle, "),
+            Span::styled("n", b), Span::raw(" new label, "),
+            Span::styled("Esc", b), Span::raw(" back"),
+        ])]
+    };
+    f.render_widget(Paragraph::new(footer), rows[1]);
+}
+
+// Avatar stand-in: the user's initials on a color derived from their id
+fn member_chip(user: &PlankaUser) -> Span<'static> {
+    let source = if user.name.trim().is_empty() { user.username.as_deref().unwrap_or("?") } else { user.name.as_str() };
+    let initials: String = source
+        .split_whitespace()
+        .filter_map(|w| w.chars().next())
+        .take(2)
+        .flat_map(|c| c.to_uppercase())
+        .collect();
+    let hash = user.id.bytes().fold(0usize, |h, b| h.wrapping_mul(31).wrapping_add(b as usize));
+    let bg = label_color(planka::LABEL_COLORS[hash % planka::LABEL_COLORS.len()]);
+    Span::styled(format!(" {} ", initials), Style::default().bg(bg).fg(chip_text_color(bg)).add_modifier(Modifier::BOLD))
+}
+
+fn label_chip(label: &PlankaLabel) -> Span<'static> {
+    // Color-only labels still get a visible block
+    let text = if label.name.is_empty() { "   ".to_string() } else { format!(" {} ", label.name) };
+    let bg = label_color(&label.color);
+    Span::styled(text, Style::default().bg(bg).fg(chip_text_color(bg)))
+}
+
+// Black on light chips, white on dark ones
+fn chip_text_color(bg: Color) -> Color {
+    match bg {
+        Color::Rgb(r, g, b) if (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000 < 128 => Color::White,
+        _ => Color::Black,
+    }
+}
+
+// Planka's label palette, approximated in RGB
+fn label_color(name: &str) -> Color {
+    let hex: u32 = match name {
+        "berry-red" => 0xe04556,
+        "pumpkin-orange" => 0xf0982d,
+        "lagoon-blue" => 0x109dc0,
+        "pink-tulip" => 0xf97394,
+        "light-mud" => 0xc7a57b,
+        "orange-peel" => 0xfab623,
+        "bright-moss" => 0xa5c261,
+        "antique-blue" => 0x6c99bb,
+        "dark-granite" => 0x8b8680,
+        "lagune-blue" => 0x00b4b1,
+        "sunny-grass" => 0xbfca02,
+        "morning-sky" => 0x52bad5,
+        "light-orange" => 0xffc66d,
+        "midnight-blue" => 0x004d73,
+        "tank-green" => 0x8aa177,
+        "gun-metal" => 0x355263,
+        "wet-moss" => 0x4a8753,
+        "red-burgundy" => 0xad5f7d,
+        "light-concrete" => 0xafb0a4,
+        "apricot-red" => 0xfd7d66,
+        "desert-sand" => 0xedcb76,
+        "navy-blue" => 0x166a8f,
+        "egg-yellow" => 0xf7d036,
+        "coral-green" => 0x2b6a6c,
+        "light-cocoa" => 0x87564a,
+        _ => return Color::Gray,
+    };
+    Color::Rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+}
+
+fn list_label(app: &App, project: &str, list_id: Option<&str>, done: bool) -> String {
+    match (app.planka_lists_by_board.get(project), list_id) {
+        (Some(l), Some(id)) if done || l.contains(id) => l.stages[l.stage_index(Some(id), done)].name.clone(),
+        (Some(l), None) => l.stages[l.stage_index(None, done)].name.clone(),
+        (None, _) if done => "Done".to_string(),
+        (None, None) => "Todo".to_string(),
+        (_, Some(id)) => id.to_string(),
+    }
+}
+
+fn draw_conflicts(f: &mut ratatui::Frame<'_>, app: &App) {
+    use crate::app::ConflictField;
+    let size = f.area();
+    let editing = matches!(app.input_mode, InputMode::EditingConflictValue);
+    let mut constraints = vec![Constraint::Length(1), Constraint::Min(3), Constraint::Length(6)];
+    if editing {
+        constraints.push(Constraint::Length(3));
+    }
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(size);
+
+    let b = Style::default().add_modifier(Modifier::BOLD);
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓", b), Span::raw(" select, "),
+        Span::styled("l", b), Span::raw(" keep local, "),
+        Span::styled("r", b), Span::raw(" take remote, "),
+        Span::styled("m", b), Span::raw(" merge manually, "),
+        Span::styled("Esc", b), Span::raw(" back"),
+    ]))
+    .alignment(Alignment::Left);
+    f.render_widget(help, rows[0]);
+
+    let field_label = |fl: ConflictField| match fl {
+        ConflictField::Name => "name",
+        ConflictField::Due => "due",
+        ConflictField::List => "list",
+    };
+    let find_todo = |card_id: &str| app.todos.iter().find(|t| t.planka_card_id.as_deref() == Some(card_id));
+
+    let items: Vec<ListItem> = app
+        .conflicts
+        .iter()
+        .map(|c| {
+            let name = find_todo(&c.card_id).map(|t| t.description.as_str()).unwrap_or(c.remote.name.as_str());
+            ListItem::new(Line::from(format!("{} › {} ({})", app.project_name(&c.project), name, field_label(c.field))))
+        })
+        .collect();
+    let mut state = ratatui::widgets::ListState::default();
+    if !items.is_empty() {
+        state.select(Some(app.conflict_index.min(items.len() - 1)));
+    }
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(format!("Sync conflicts ({})", app.conflicts.len())))
+        .highlight_style(Style::default().bg(Color::Blue).fg(Color::White).add_modifier(Modifier::BOLD))
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(list, rows[1], &mut state);
+
+    let mut detail: Vec<Line> = Vec::new();
+    if let Some(c) = app.conflicts.get(app.conflict_index)
+        && let Some(t) = find_todo(&c.card_id)
+    {
+        let none = || "(none)".to_string();
+        let (base, local, remote) = match c.field {
+            ConflictField::Name => (
+                t.base.as_ref().map(|x| x.name.clone()).unwrap_or_else(none),
+                t.description.clone(),
+                c.remote.name.clone(),
+            ),
+            ConflictField::Due => (
+                t.base.as_ref().and_then(|x| x.due.clone()).unwrap_or_else(none),
+                t.due_date.clone().unwrap_or_else(none),
+                c.remote.due.clone().unwrap_or_else(none),
+            ),
+            ConflictField::List => (
+                t.base
+                    .as_ref()
+                    .map(|x| list_label(app, &c.project, x.list_id.as_deref(), x.done))
+                    .unwrap_or_else(none),
+                list_label(app, &c.project, t.planka_list_id.as_deref(), t.done),
+                list_label(app, &c.project, c.remote.list_id.as_deref(), c.remote.done),
+            ),
+        };
+        detail.push(Line::from(vec![Span::styled("Base:   ", b), Span::raw(base)]));
+        detail.push(Line::from(vec![
+            Span::styled("Local:  ", b),
+            Span::styled(local, Style::default().fg(Color::Yellow)),
+        ]));
+        detail.push(Line::from(vec![
+            Span::styled("Remote: ", b),
+            Span::styled(remote, Style::default().fg(Color::Cyan)),
+        ]));
+    }
+    let detail = Paragraph::new(detail)
+        .block(Block::default().borders(Borders::ALL).title("Base / Local / Remote"))
+        .wrap(Wrap { trim: true });
+    f.render_widget(detail, rows[2]);
+
+    if editing {
+        let style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let caret = "|";
+        let text = if app.input_conflict.is_empty() { caret.to_string() } else { format!("{}{}", app.input_conflict, caret) };
+        let widget = Paragraph::new(text)
+            .block(Block::default().borders(Borders::ALL).title("Merged Value"))
+            .style(style)
+            .wrap(Wrap { trim: true });
+        f.render_widget(widget, rows[3]);
+    }
+
+    if let Some(ref msg) = app.error_message {
+        let error = Paragraph::new(msg.as_str())
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        let area = ratatui::layout::Rect {
+            x: size.x,
+            y: size.height.saturating_sub(1),
+            width: size.width,
+            height: 1,
+        };
+        f.render_widget(error, area);
+    }
+}
+
+// Check if a due date string represents an overdue task
+fn is_overdue(due_date_str: &str) -> bool {
+    if due_date_str == "No due date" {
+        return false;
+    }
+
+    let now = Local::now();
+
+    // Try to parse as datetime first
+    if let Ok(dt) = NaiveDateTime::parse_from_str(due_date_str, "%Y-%m-%d %H:%M") {
+        if let Some(dt_local) = Local.from_local_datetime(&dt).single() {
+            return dt_local < now;
+        }
+    }
+
+    // Try to parse as date only
+    if let Ok(date) = NaiveDate::parse_from_str(due_date_str, "%Y-%m-%d") {
+        let today = now.date_naive();
+        return date < today;
+    }
+
+    false
+}
+
+pub fn parse_due_date(input: &str) -> Result<String, String> {
+    let input = input.trim().to_lowercase();
+    let now = Local::now();
+    let today = now.date_naive();
+
+    // Handle empty input
+    if input.is_empty() {
+        return Err("Please enter a due date".to_string());
+    }
+
+    let words: Vec<&str> = input.split_whitespace().collect();
+
+    let raw_date = match words.as_slice() {
+        // Immediate times
         ["now"] => Ok(now.format("%Y-%m-%d %H:%M").to_string()),
 
         // Relative days
@@ -1701,3 +3224,39 @@ fn paste_from_clipboard() -> Result<String, String> {
 fn paste_from_clipboard() -> Result<String, String> {
     Err("Clipboard paste not supported on this OS".into())
 }
+
+// Edit `text` in $VISUAL / $EDITOR (vi if neither is set) through a private
+// temp file, with the TUI suspended meanwhile. None when the editor failed.
+fn run_editor<B: Backend>(terminal: &mut Terminal<B>, text: &str) -> io::Result<Option<String>>
+where
+    std::io::Error: From<<B as Backend>::Error>,
+{
+    use crossterm::execute;
+    use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode};
+
+    let path = std::env::temp_dir().join(format!("planky-{}.md", std::process::id()));
+    crate::secrets::open_private(&path, false)?.write_all(text.as_bytes())?;
+
+    let editor = std::env::var("VISUAL")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .or_else(|| std::env::var("EDITOR").ok().filter(|v| !v.trim().is_empty()))
+        .unwrap_or_else(|| "vi".to_string());
+    // Allow arguments, e.g. EDITOR="code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)?;
+    let status = Command::new(program).args(parts).arg(&path).status();
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    terminal.clear()?;
+
+    let result = match status {
+        Ok(s) if s.success() => std::fs::read_to_string(&path).ok(),
+        _ => None,
+    };
+    std::fs::remove_file(&path).ok();
+    Ok(result)
+}
@@ -0,0 +1,390 @@
+// webhook.rs
+//
+// Local HTTP receiver for Planka webhooks. Planka POSTs every event as
+// `{event, data: {item, included}, prevData, user}` with the webhook's access
+// token as a Bearer header; we answer at once and then refresh only what the
+// event touched, instead of pulling whole boards.
+use crate::app::Delta;
+use crate::planka::{self, PlankaClient, PlankaError, PlankaLists, WebhookConfig};
+use serde_json::Value;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:8791";
+
+// Events the webhook is registered for
+pub const EVENTS: &[&str] = &[
+    "cardCreate", "cardUpdate", "cardDelete",
+    "cardLabelCreate", "cardLabelDelete", "cardMembershipCreate", "cardMembershipDelete",
+    "labelCreate", "labelUpdate", "labelDelete",
+    "listCreate", "listUpdate", "listDelete",
+    "boardCreate", "boardUpdate", "boardDelete",
+    "notificationCreate",
+];
+
+const MAX_HEAD: usize = 16 * 1024;
+const MAX_BODY: usize = 4 * 1024 * 1024;
+
+// State shared with the sync thread
+#[derive(Default)]
+pub struct Shared {
+    // Boards and workflow lists of the last pull, to map cards to projects
+    pub boards: Mutex<Vec<(String, PlankaLists)>>,
+    // The receiver is listening, so the sync thread can poll less often
+    pub live: AtomicBool,
+    // An event changed the board layout: the sync thread should pull now
+    pub resync: AtomicBool,
+}
+
+pub fn spawn(hook: WebhookConfig, shared: Arc<Shared>, tx: Sender<Delta>, stop: Arc<AtomicBool>) {
+    thread::spawn(move || {
+        // The receiver of a previous profile may still hold the port for a moment
+        let mut listener = None;
+        let mut bind_error = String::new();
+        for _ in 0..10 {
+            match TcpListener::bind(&hook.listen) {
+                Ok(l) => {
+                    listener = Some(l);
+                    break;
+                }
+                Err(e) => bind_error = e.to_string(),
+            }
+            if stop.load(Ordering::Relaxed) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(300));
+        }
+        let Some(listener) = listener else {
+            let _ = tx.send(Delta::Message(format!("Webhook receiver can't listen on {}: {}", hook.listen, bind_error)));
+            return;
+        };
+        serve(listener, &hook.access_token, &shared, &tx, &stop, refresh_card);
+    });
+}
+
+// Answer requests one at a time until `stop`; `refresh` fetches a card an event points at
+fn serve(
+    listener: TcpListener,
+    token: &str,
+    shared: &Shared,
+    tx: &Sender<Delta>,
+    stop: &AtomicBool,
+    refresh: impl Fn(&str, &Shared, &Sender<Delta>),
+) {
+    if listener.set_nonblocking(true).is_err() {
+        return;
+    }
+    shared.live.store(true, Ordering::Relaxed);
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Some(body) = receive(stream, token) {
+                    handle_event(&body, shared, tx, &refresh);
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(Duration::from_millis(200)),
+            Err(_) => thread::sleep(Duration::from_secs(1)),
+        }
+    }
+    shared.live.store(false, Ordering::Relaxed);
+}
+
+fn respond(stream: &mut TcpStream, status: &str) {
+    let _ = write!(stream, "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+}
+
+// Compare without stopping at the first differing byte
+fn same_token(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// Read one request, answer it, and return its JSON body if it is an authentic event
+fn receive(mut stream: TcpStream, token: &str) -> Option<Value> {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let mut buf: Vec<u8> = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(i) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break i + 4;
+        }
+        if buf.len() > MAX_HEAD {
+            respond(&mut stream, "431 Request Header Fields Too Large");
+            return None;
+        }
+        let n = stream.read(&mut chunk).ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+    let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+    let mut lines = head.lines();
+    let method = lines.next().and_then(|l| l.split_whitespace().next()).unwrap_or_default();
+    let mut auth = "";
+    // None for a Content-Length that isn't a number
+    let mut length = Some(0usize);
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else { continue; };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "authorization" => auth = value.trim(),
+            "content-length" => length = value.trim().parse().ok(),
+            _ => {}
+        }
+    }
+    if method != "POST" {
+        respond(&mut stream, "405 Method Not Allowed");
+        return None;
+    }
+    let presented = auth.strip_prefix("Bearer ").unwrap_or_default();
+    if !same_token(presented.as_bytes(), token.as_bytes()) {
+        respond(&mut stream, "401 Unauthorized");
+        return None;
+    }
+    let Some(length) = length else {
+        respond(&mut stream, "400 Bad Request");
+        return None;
+    };
+    if length > MAX_BODY {
+        respond(&mut stream, "413 Payload Too Large");
+        return None;
+    }
+    let mut body = buf[head_end..].to_vec();
+    while body.len() < length {
+        match stream.read(&mut chunk) {
+            // The sender hung up before the whole body arrived
+            Ok(0) => {
+                respond(&mut stream, "400 Bad Request");
+                return None;
+            }
+            Ok(n) => body.extend_from_slice(&chunk[..n]),
+            Err(_) => {
+                respond(&mut stream, "408 Request Timeout");
+                return None;
+            }
+        }
+    }
+    body.truncate(length);
+    match serde_json::from_slice::<Value>(&body) {
+        Ok(v) => {
+            respond(&mut stream, "200 OK");
+            Some(v)
+        }
+        Err(_) => {
+            respond(&mut stream, "400 Bad Request");
+            None
+        }
+    }
+}
+
+fn handle_event(v: &Value, shared: &Shared, tx: &Sender<Delta>, refresh: &impl Fn(&str, &Shared, &Sender<Delta>)) {
+    let event = v.get("event").and_then(|x| x.as_str()).unwrap_or_default();
+    let Some(item) = v.get("data").and_then(|d| d.get("item")) else { return; };
+    let str_of = |key: &str| item.get(key).and_then(|x| x.as_str()).map(|s| s.to_string());
+    let project_of = |board_id: Option<String>| {
+        let boards = shared.boards.lock().ok()?;
+        boards.iter().find(|(_, l)| Some(&l.board_id) == board_id.as_ref()).map(|(p, _)| p.clone())
+    };
+    match event {
+        "cardCreate" | "cardUpdate" => {
+            if let Some(id) = str_of("id") {
+                refresh(&id, shared, tx);
+            }
+        }
+        "cardLabelCreate" | "cardLabelDelete" | "cardMembershipCreate" | "cardMembershipDelete" => {
+            if let Some(id) = str_of("cardId") {
+                refresh(&id, shared, tx);
+            }
+        }
+        "cardDelete" => {
+            if let (Some(id), Some(project)) = (str_of("id"), project_of(str_of("boardId"))) {
+                let _ = tx.send(Delta::Delete { project, id });
+            }
+        }
+        "labelCreate" | "labelUpdate" | "labelDelete" => {
+            if let (Some(project), Some(label)) = (project_of(str_of("boardId")), planka::parse_label(item)) {
+                let _ = tx.send(Delta::Label { project, label, deleted: event == "labelDelete" });
+            }
+        }
+        "notificationCreate" => {
+            // Webhooks see everyone's notifications; only ours are fetched
+            if let Some(client) = client()
+                && let Ok(list) = client.fetch_notifications()
+            {
+                let _ = tx.send(Delta::Notifications(list));
+            }
+        }
+        "listCreate" | "listUpdate" | "listDelete" | "boardCreate" | "boardUpdate" | "boardDelete" => {
+            shared.resync.store(true, Ordering::Relaxed);
+        }
+        _ => {}
+    }
+}
+
+fn client() -> Option<PlankaClient> {
+    let cfg = planka::load_config()?;
+    PlankaClient::from_config(cfg).ok().map(|(c, _)| c)
+}
+
+// Fetch one card and send it as an Upsert, or as Archived when it left the workflow lists
+fn refresh_card(card_id: &str, shared: &Shared, tx: &Sender<Delta>) {
+    let Some(client) = client() else { return; };
+    let d = match client.fetch_card_details(card_id) {
+        Ok(d) => d,
+        // Gone already (a delete follows as its own event); anything else: pull everything
+        Err(PlankaError::NotFound(_)) => return,
+        Err(_) => {
+            shared.resync.store(true, Ordering::Relaxed);
+            return;
+        }
+    };
+    let Ok(boards) = shared.boards.lock() else { return; };
+    let Some((project, lists)) = boards.iter().find(|(_, l)| Some(&l.board_id) == d.board_id.as_ref()) else { return; };
+    match d.list_id.filter(|l| lists.contains(l)) {
+        Some(list_id) => {
+            let _ = tx.send(Delta::Upsert {
+                project: project.clone(),
+                id: d.id,
+                name: d.name,
+                due: d.due,
+                created: d.created,
+                done: list_id == lists.done_list_id(),
+                list_id,
+                labels: Some(d.label_ids),
+                members: Some(d.member_ids),
+            });
+        }
+        None => {
+            let _ = tx.send(Delta::Archived { project: project.clone(), id: d.id });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Shutdown;
+    use std::sync::mpsc::{self, Receiver};
+    use std::time::Instant;
+
+    const TOKEN: &str = "hook-token";
+
+    struct TestReceiver {
+        port: u16,
+        shared: Arc<Shared>,
+        rx: Receiver<Delta>,
+        refreshed: Arc<Mutex<Vec<String>>>,
+        stop: Arc<AtomicBool>,
+    }
+
+    impl Drop for TestReceiver {
+        fn drop(&mut self) {
+            self.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    // A receiver on an ephemeral port that records card refreshes instead of fetching
+    fn start() -> TestReceiver {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let shared = Arc::new(Shared::default());
+        let (tx, rx) = mpsc::channel();
+        let refreshed = Arc::new(Mutex::new(Vec::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (s, r, st) = (shared.clone(), refreshed.clone(), stop.clone());
+        thread::spawn(move || {
+            serve(listener, TOKEN, &s, &tx, &st, move |id: &str, _: &Shared, _: &Sender<Delta>| {
+                r.lock().unwrap().push(id.to_string());
+            });
+        });
+        TestReceiver { port, shared, rx, refreshed, stop }
+    }
+
+    // Send raw bytes, optionally closing our side, and return the status line of the answer
+    fn send(port: u16, raw: &[u8], hang_up: bool) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(15))).unwrap();
+        let _ = stream.write_all(raw);
+        if hang_up {
+            let _ = stream.shutdown(Shutdown::Write);
+        }
+        let mut answer = String::new();
+        let _ = stream.read_to_string(&mut answer);
+        answer.lines().next().unwrap_or_default().to_string()
+    }
+
+    fn post(port: u16, auth: Option<&str>, body: &str) -> String {
+        let auth = auth.map(|t| format!("Authorization: Bearer {}\r\n", t)).unwrap_or_default();
+        let raw = format!("POST /planka HTTP/1.1\r\nHost: x\r\n{}Content-Length: {}\r\n\r\n{}", auth, body.len(), body);
+        send(port, raw.as_bytes(), true)
+    }
+
+    fn event(name: &str, item: &str) -> String {
+        format!(r#"{{"event":"{}","data":{{"item":{}}}}}"#, name, item)
+    }
+
+    #[test]
+    fn rejects_a_missing_or_wrong_token() {
+        let r = start();
+        let body = event("cardUpdate", r#"{"id":"c1"}"#);
+        assert_eq!(post(r.port, None, &body), "HTTP/1.1 401 Unauthorized");
+        assert_eq!(post(r.port, Some("hook-tokeN"), &body), "HTTP/1.1 401 Unauthorized");
+        assert_eq!(post(r.port, Some(""), &body), "HTTP/1.1 401 Unauthorized");
+        let raw = format!("GET / HTTP/1.1\r\nAuthorization: Bearer {}\r\n\r\n", TOKEN);
+        assert_eq!(send(r.port, raw.as_bytes(), true), "HTTP/1.1 405 Method Not Allowed");
+        assert!(r.refreshed.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn survives_malformed_requests() {
+        let r = start();
+        let auth = format!("Authorization: Bearer {}\r\n", TOKEN);
+        let truncated = format!("POST / HTTP/1.1\r\n{}Content-Length: 100\r\n\r\n{{\"event\"", auth);
+        assert_eq!(send(r.port, truncated.as_bytes(), true), "HTTP/1.1 400 Bad Request");
+        let bad_length = format!("POST / HTTP/1.1\r\n{}Content-Length: -1\r\n\r\n{{}}", auth);
+        assert_eq!(send(r.port, bad_length.as_bytes(), true), "HTTP/1.1 400 Bad Request");
+        let oversized = format!("POST / HTTP/1.1\r\n{}Content-Length: {}\r\n\r\n", auth, MAX_BODY + 1);
+        assert_eq!(send(r.port, oversized.as_bytes(), true), "HTTP/1.1 413 Payload Too Large");
+        let long_head = format!("POST / HTTP/1.1\r\nX-Pad: {}\r\n", "a".repeat(MAX_HEAD + 1));
+        assert_eq!(send(r.port, long_head.as_bytes(), false), "HTTP/1.1 431 Request Header Fields Too Large");
+        assert_eq!(post(r.port, Some(TOKEN), "not json"), "HTTP/1.1 400 Bad Request");
+        // Nothing hangs up: the read timeout answers for a sender that stalls mid-body
+        let started = Instant::now();
+        assert_eq!(send(r.port, truncated.as_bytes(), false), "HTTP/1.1 408 Request Timeout");
+        assert!(started.elapsed() < Duration::from_secs(10));
+        // And the receiver still serves the next request
+        assert_eq!(post(r.port, Some(TOKEN), &event("cardUpdate", r#"{"id":"c1"}"#)), "HTTP/1.1 200 OK");
+    }
+
+    #[test]
+    fn ignores_unknown_events() {
+        let r = start();
+        assert_eq!(post(r.port, Some(TOKEN), &event("projectCreate", r#"{"id":"p1"}"#)), "HTTP/1.1 200 OK");
+        assert_eq!(post(r.port, Some(TOKEN), r#"{"event":"cardUpdate"}"#), "HTTP/1.1 200 OK");
+        // Handling is serial, so once this is answered the events above are done with
+        assert_eq!(post(r.port, Some(TOKEN), &event("attachmentCreate", "{}")), "HTTP/1.1 200 OK");
+        thread::sleep(Duration::from_millis(50));
+        assert!(r.refreshed.lock().unwrap().is_empty());
+        assert!(r.rx.try_recv().is_err());
+        assert!(!r.shared.resync.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn refreshes_only_the_card_an_event_names() {
+        let r = start();
+        assert_eq!(post(r.port, Some(TOKEN), &event("cardUpdate", r#"{"id":"c1","name":"x"}"#)), "HTTP/1.1 200 OK");
+        assert_eq!(post(r.port, Some(TOKEN), &event("cardLabelCreate", r#"{"id":"cl9","cardId":"c2"}"#)), "HTTP/1.1 200 OK");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while r.refreshed.lock().unwrap().len() < 2 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(*r.refreshed.lock().unwrap(), vec!["c1".to_string(), "c2".to_string()]);
+        assert!(r.rx.try_recv().is_err());
+        assert!(!r.shared.resync.load(Ordering::Relaxed));
+    }
+}
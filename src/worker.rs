@@ -0,0 +1,128 @@
+// worker.rs
+//
+// Single background thread that performs Planka requests for the UI. Jobs
+// run in submission order; each returns a closure that is applied to the
+// `App` on the UI thread by `App::drain_worker`.
+use crate::app::App;
+use crate::planka::{PlankaClient, PlankaConfig, PlankaError};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+pub type Apply = Box<dyn FnOnce(&mut App) + Send>;
+type Job = Box<dyn FnOnce(Result<&PlankaClient, PlankaError>) -> Apply + Send>;
+
+#[derive(Default)]
+pub struct Worker {
+    // The flag runs a job on an anonymous client (nothing to log in with yet)
+    tx: Option<Sender<(PlankaConfig, bool, Job)>>,
+    rx: Option<Receiver<Apply>>,
+    pub in_flight: usize,
+}
+
+impl Worker {
+    fn start(&mut self) {
+        let (job_tx, job_rx) = mpsc::channel::<(PlankaConfig, bool, Job)>();
+        let (res_tx, res_rx) = mpsc::channel::<Apply>();
+        thread::spawn(move || {
+            // Tokens (with their session cookie) replaced by a re-login, so jobs
+            // queued before it don't each log in again
+            let mut renewed: HashMap<String, (Option<String>, Option<String>)> = HashMap::new();
+            for (mut cfg, anonymous, job) in job_rx {
+                if anonymous {
+                    let apply = match PlankaClient::anonymous(&cfg.server_url) {
+                        Ok(client) => job(Ok(&client)),
+                        Err(e) => job(Err(e)),
+                    };
+                    if res_tx.send(apply).is_err() {
+                        break;
+                    }
+                    continue;
+                }
+                if let Some((token, cookie)) = cfg.token.as_ref().and_then(|t| renewed.get(t)) {
+                    cfg.token = token.clone();
+                    cfg.http_only_token = cookie.clone();
+                }
+                let sent_token = cfg.token.clone();
+                let apply = match PlankaClient::from_config(cfg) {
+                    Ok((client, _)) => {
+                        let apply = job(Ok(&client));
+                        let cfg = client.config();
+                        if let Some(old) = sent_token
+                            && cfg.token.as_ref().is_some_and(|new| *new != old)
+                        {
+                            renewed.insert(old, (cfg.token.clone(), cfg.http_only_token.clone()));
+                        }
+                        let login_required = client.login_required();
+                        // Keep a freshly issued token so the next job doesn't log in again
+                        Box::new(move |app: &mut App| {
+                            if let Some(ref mut current) = app.planka_config
+                                && current.server_url == cfg.server_url
+                                && current.token != cfg.token
+                            {
+                                current.token = cfg.token;
+                                current.http_only_token = cfg.http_only_token;
+                            }
+                            if login_required {
+                                app.prompt_relogin();
+                            }
+                            apply(app);
+                        }) as Apply
+                    }
+                    Err(e) => {
+                        let login_required = matches!(e, PlankaError::Unauthorized(_) | PlankaError::TermsRequired { .. });
+                        let apply = job(Err(e));
+                        Box::new(move |app: &mut App| {
+                            if login_required {
+                                app.prompt_relogin();
+                            }
+                            apply(app);
+                        }) as Apply
+                    }
+                };
+                if res_tx.send(apply).is_err() {
+                    break;
+                }
+            }
+        });
+        self.tx = Some(job_tx);
+        self.rx = Some(res_rx);
+    }
+
+    pub fn submit(&mut self, cfg: PlankaConfig, job: Job) {
+        self.send(cfg, false, job);
+    }
+
+    pub fn submit_anonymous(&mut self, server_url: String, job: Job) {
+        let cfg = PlankaConfig { server_url, ..Default::default() };
+        self.send(cfg, true, job);
+    }
+
+    fn send(&mut self, cfg: PlankaConfig, anonymous: bool, job: Job) {
+        if self.tx.is_none() {
+            self.start();
+        }
+        if let Some(ref tx) = self.tx
+            && tx.send((cfg, anonymous, job)).is_ok()
+        {
+            self.in_flight += 1;
+        }
+    }
+
+    // Finished jobs, without blocking
+    pub fn finished(&mut self) -> Vec<Apply> {
+        let done: Vec<Apply> = match self.rx {
+            Some(ref rx) => rx.try_iter().collect(),
+            None => Vec::new(),
+        };
+        self.in_flight = self.in_flight.saturating_sub(done.len());
+        done
+    }
+
+    // Block until the next job finishes (used by the CLI, which has no event loop)
+    pub fn wait_one(&mut self) -> Option<Apply> {
+        let apply = self.rx.as_ref()?.recv().ok()?;
+        self.in_flight = self.in_flight.saturating_sub(1);
+        Some(apply)
+    }
+}